@@ -1,32 +1,205 @@
 //! Cargo command execution.
 //!
-//! This module provides functions for executing cargo doc commands
-//! with proper error handling and HTML generation validation.
+//! This module provides functions for executing cargo doc and cargo rustdoc
+//! commands with proper error handling and output validation.
 
-use std::path::PathBuf;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::OnceLock;
 
-use anyhow::{Context, Result, bail};
+use anyhow::{Context, Result, bail, ensure};
 use serde::Deserialize;
 use tracing::{debug, trace};
 
 /// Cargo metadata output structure.
 ///
-/// This struct represents the JSON output from `cargo metadata --no-deps --format-version 1`.
-#[derive(Debug, Deserialize)]
+/// This struct represents the JSON output from `cargo metadata --format-version 1`,
+/// with or without `--no-deps`.
+#[derive(Debug, Clone, Deserialize)]
 pub struct Metadata {
     pub packages: Vec<Package>,
+    /// Package IDs of the workspace members
+    #[serde(default)]
+    pub workspace_members: Vec<String>,
     pub target_directory: String,
+    /// Root directory of the workspace, where Cargo.lock lives
+    #[serde(default)]
+    pub workspace_root: String,
+    /// The resolved dependency graph, absent when `--no-deps` is used
+    #[serde(default)]
+    pub resolve: Option<Resolve>,
 }
 
 /// Package information from cargo metadata.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
 pub struct Package {
+    /// Name of the package
+    pub name: String,
+    /// Version of the package
+    #[serde(default)]
+    pub version: String,
+    /// Opaque package ID used by cargo to refer to the package
+    pub id: String,
     pub dependencies: Vec<Dependency>,
+    /// Build targets of the package (lib, bin, test, ...)
+    #[serde(default)]
+    pub targets: Vec<Target>,
+    /// Path to the Cargo.toml of the package
+    #[serde(default)]
+    pub manifest_path: String,
+    /// Features defined by the package, with the features and optional
+    /// dependencies each one enables
+    #[serde(default)]
+    pub features: BTreeMap<String, Vec<String>>,
+    /// The `[package.metadata]` table of the manifest, `null` when absent
+    #[serde(default)]
+    pub metadata: serde_json::Value,
+}
+
+/// Build settings of the `[package.metadata.docs.rs]` table of a manifest.
+#[derive(Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DocsRsMetadata {
+    /// Features to enable
+    #[serde(default)]
+    pub features: Vec<String>,
+    /// Enable every feature
+    #[serde(default)]
+    pub all_features: bool,
+    /// Do not enable the `default` feature
+    #[serde(default)]
+    pub no_default_features: bool,
+    /// Extra flags passed to rustc
+    #[serde(default)]
+    pub rustc_args: Vec<String>,
+    /// Extra flags passed to rustdoc (e.g., `["--cfg", "docsrs"]`)
+    #[serde(default)]
+    pub rustdoc_args: Vec<String>,
+}
+
+/// Build target information for a package.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Target {
+    /// Name of the target, as written in Cargo.toml
+    pub name: String,
+    /// Target kinds, such as "lib", "rlib", "proc-macro" or "bin"
+    pub kind: Vec<String>,
+}
+
+impl Package {
+    /// Return the library name rustdoc uses for this package's lib target.
+    ///
+    /// Returns `None` for packages without a library target.
+    pub fn lib_name(&self) -> Option<String> {
+        self.targets
+            .iter()
+            .find(|target| {
+                target.kind.iter().any(|kind| {
+                    matches!(
+                        kind.as_str(),
+                        "lib" | "rlib" | "dylib" | "cdylib" | "staticlib" | "proc-macro"
+                    )
+                })
+            })
+            .map(|target| target.name.replace('-', "_"))
+    }
+
+    /// Return the features enabled by a feature selection, sorted.
+    ///
+    /// The selected features, and `default` unless disabled, enable the
+    /// features they list in turn. Optional dependencies and features of
+    /// dependencies they enable are not included.
+    pub fn enabled_features(&self, options: &DocOptions) -> Vec<String> {
+        let mut pending: Vec<&str> = if options.all_features {
+            self.features.keys().map(String::as_str).collect()
+        } else {
+            options.features.iter().map(String::as_str).collect()
+        };
+        if !options.no_default_features && self.features.contains_key("default") {
+            pending.push("default");
+        }
+
+        let mut enabled = BTreeSet::new();
+        while let Some(feature) = pending.pop() {
+            if !enabled.insert(feature.to_string()) {
+                continue;
+            }
+            if let Some(enables) = self.features.get(feature) {
+                pending.extend(
+                    enables
+                        .iter()
+                        .map(String::as_str)
+                        .filter(|f| !f.contains(':') && !f.contains('/')),
+                );
+            }
+        }
+        enabled.into_iter().collect()
+    }
+
+    /// Return the docs.rs build settings of the package.
+    ///
+    /// A package without a `[package.metadata.docs.rs]` table, or with one
+    /// that cannot be parsed, has the default settings.
+    pub fn docs_rs_metadata(&self) -> DocsRsMetadata {
+        let Some(table) = self.metadata.pointer("/docs/rs") else {
+            return DocsRsMetadata::default();
+        };
+        serde_json::from_value(table.clone()).unwrap_or_else(|e| {
+            debug!(
+                "Ignoring invalid docs.rs metadata of '{}': {}",
+                self.name, e
+            );
+            DocsRsMetadata::default()
+        })
+    }
+
+    /// Check whether the package comes from a local path, such as a
+    /// workspace member, rather than from a registry or git source.
+    pub fn is_local(&self) -> bool {
+        self.id.contains("path+file://")
+    }
+}
+
+/// Resolved dependency graph from cargo metadata.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Resolve {
+    pub nodes: Vec<Node>,
+}
+
+/// A package in the resolved dependency graph.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Node {
+    /// Package ID of this node
+    pub id: String,
+    /// Resolved dependencies of this node
+    pub deps: Vec<NodeDep>,
+    /// Features enabled for this package
+    #[serde(default)]
+    pub features: Vec<String>,
+}
+
+/// A resolved dependency edge.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NodeDep {
+    /// Package ID of the dependency
+    pub pkg: String,
+    /// Kinds of the dependency edge
+    #[serde(default)]
+    pub dep_kinds: Vec<DepKindInfo>,
+}
+
+/// Kind of a resolved dependency edge.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DepKindInfo {
+    /// Kind of dependency: "dev", "build" or null for regular dependencies
+    pub kind: Option<String>,
 }
 
 /// Dependency information for a package.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct Dependency {
     /// Name of the dependency crate
     pub name: String,
@@ -34,13 +207,397 @@ pub struct Dependency {
     pub kind: Option<String>,
 }
 
+impl Metadata {
+    /// Return the workspace members, optionally restricted to a single package.
+    ///
+    /// Returns an error listing the workspace members if the requested
+    /// package is not part of the workspace.
+    pub fn workspace_packages(&self, package: Option<&str>) -> Result<Vec<&Package>> {
+        let members: Vec<&Package> = self
+            .packages
+            .iter()
+            .filter(|p| self.workspace_members.is_empty() || self.workspace_members.contains(&p.id))
+            .collect();
+
+        let Some(package) = package else {
+            return Ok(members);
+        };
+
+        match members.iter().find(|p| p.name == package) {
+            Some(p) => Ok(vec![*p]),
+            None => {
+                let names: Vec<&str> = members.iter().map(|p| p.name.as_str()).collect();
+                bail!(
+                    "package '{}' is not a workspace member. Workspace members: {}",
+                    package,
+                    names.join(", ")
+                )
+            }
+        }
+    }
+
+    /// Map every dependency name to the workspace members that declare it.
+    ///
+    /// Dependencies are sorted by name, and each member appears once per
+    /// dependency even if it declares it with several kinds.
+    pub fn dependency_owners(&self, package: Option<&str>) -> Result<BTreeMap<&str, Vec<&str>>> {
+        let mut owners: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for member in self.workspace_packages(package)? {
+            for dep in &member.dependencies {
+                let entry = owners.entry(dep.name.as_str()).or_default();
+                if !entry.contains(&member.name.as_str()) {
+                    entry.push(member.name.as_str());
+                }
+            }
+        }
+        Ok(owners)
+    }
+
+    /// Find the chain of regular dependencies that brings a crate into the graph.
+    ///
+    /// Walks the resolved dependency graph breadth-first from the selected
+    /// workspace members and returns the shortest chain, starting with the
+    /// workspace member and ending with the requested package. Returns `None`
+    /// if the metadata has no resolve graph or the crate is not reachable.
+    pub fn dependency_chain(
+        &self,
+        crate_name: &str,
+        package: Option<&str>,
+    ) -> Result<Option<Vec<&Package>>> {
+        let Some(resolve) = &self.resolve else {
+            return Ok(None);
+        };
+
+        let packages: HashMap<&str, &Package> =
+            self.packages.iter().map(|p| (p.id.as_str(), p)).collect();
+        let nodes: HashMap<&str, &Node> =
+            resolve.nodes.iter().map(|n| (n.id.as_str(), n)).collect();
+
+        let mut parents: HashMap<&str, Option<&str>> = HashMap::new();
+        let mut queue: VecDeque<&str> = VecDeque::new();
+        for member in self.workspace_packages(package)? {
+            parents.insert(member.id.as_str(), None);
+            queue.push_back(member.id.as_str());
+        }
+
+        while let Some(id) = queue.pop_front() {
+            let is_root = matches!(parents.get(id), Some(None));
+            if !is_root && packages.get(id).is_some_and(|p| p.name == crate_name) {
+                let mut chain = Vec::new();
+                let mut current = Some(id);
+                while let Some(current_id) = current {
+                    if let Some(p) = packages.get(current_id) {
+                        chain.push(*p);
+                    }
+                    current = parents.get(current_id).copied().flatten();
+                }
+                chain.reverse();
+                return Ok(Some(chain));
+            }
+
+            let Some(node) = nodes.get(id) else {
+                continue;
+            };
+            for dep in &node.deps {
+                let is_regular =
+                    dep.dep_kinds.is_empty() || dep.dep_kinds.iter().any(|k| k.kind.is_none());
+                if !is_regular || parents.contains_key(dep.pkg.as_str()) {
+                    continue;
+                }
+                parents.insert(dep.pkg.as_str(), Some(id));
+                queue.push_back(dep.pkg.as_str());
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Find the package matching a `name` or `name@version` spec.
+    ///
+    /// A partial version such as `syn@1` matches `1.0.109`. Returns `None`
+    /// when no package or more than one package matches.
+    pub fn find_package(&self, spec: &str) -> Option<&Package> {
+        let (name, version) = match spec.split_once('@') {
+            Some((name, version)) => (name, Some(version)),
+            None => (spec, None),
+        };
+
+        let mut matches = self
+            .packages
+            .iter()
+            .filter(|p| p.name == name && version.is_none_or(|v| version_matches(&p.version, v)));
+
+        match (matches.next(), matches.next()) {
+            (Some(package), None) => Some(package),
+            _ => None,
+        }
+    }
+
+    /// Find the package a `name` or `name@version` spec refers to.
+    ///
+    /// Like `find_package`, but a name without version that matches several
+    /// packages resolves to the version the selected workspace members
+    /// depend on directly, when there is exactly one.
+    pub fn select_package(&self, spec: &str, package: Option<&str>) -> Option<&Package> {
+        if let Some(found) = self.find_package(spec) {
+            return Some(found);
+        }
+        if spec.contains('@') {
+            return None;
+        }
+
+        let direct = self.resolved_dependencies(package, false).ok()?;
+        let mut matches = direct.into_iter().filter(|p| p.name == spec);
+        match (matches.next(), matches.next()) {
+            (Some(package), None) => Some(package),
+            _ => None,
+        }
+    }
+
+    /// Check whether a package is the version selected for its name alone.
+    ///
+    /// Other versions of the package are documented under a versioned
+    /// directory, see `select_package`.
+    pub fn is_default_version(&self, package: &Package, member: Option<&str>) -> bool {
+        self.select_package(&package.name, member)
+            .is_some_and(|selected| selected.id == package.id)
+    }
+
+    /// Return the package spec cargo needs to select a package: its name, or
+    /// `name@version` when several versions of the package are in the graph.
+    pub fn package_spec(&self, package: &Package) -> String {
+        let versions = self
+            .packages
+            .iter()
+            .filter(|p| p.name == package.name)
+            .count();
+        if versions > 1 {
+            format!("{}@{}", package.name, package.version)
+        } else {
+            package.name.clone()
+        }
+    }
+
+    /// Return the features enabled for a package in the resolved graph.
+    pub fn resolved_features(&self, package_id: &str) -> Vec<String> {
+        let mut features = self
+            .resolve
+            .iter()
+            .flat_map(|resolve| &resolve.nodes)
+            .find(|node| node.id == package_id)
+            .map(|node| node.features.clone())
+            .unwrap_or_default();
+        features.sort();
+        features
+    }
+
+    /// Return the regular dependencies of the selected workspace members.
+    ///
+    /// Uses the resolved dependency graph, so the result contains the exact
+    /// package versions cargo selected. With `transitive`, every package
+    /// reachable through regular dependency edges is returned. Workspace
+    /// members themselves are never included.
+    pub fn resolved_dependencies(
+        &self,
+        package: Option<&str>,
+        transitive: bool,
+    ) -> Result<Vec<&Package>> {
+        let Some(resolve) = &self.resolve else {
+            bail!("cargo metadata does not contain the resolved dependency graph");
+        };
+
+        let packages: HashMap<&str, &Package> =
+            self.packages.iter().map(|p| (p.id.as_str(), p)).collect();
+        let nodes: HashMap<&str, &Node> =
+            resolve.nodes.iter().map(|n| (n.id.as_str(), n)).collect();
+
+        let members = self.workspace_packages(package)?;
+        let mut seen: HashSet<&str> = members.iter().map(|p| p.id.as_str()).collect();
+        let mut queue: VecDeque<&str> = members.iter().map(|p| p.id.as_str()).collect();
+        let mut dependencies = Vec::new();
+
+        while let Some(id) = queue.pop_front() {
+            let Some(node) = nodes.get(id) else {
+                continue;
+            };
+            for dep in &node.deps {
+                let is_regular =
+                    dep.dep_kinds.is_empty() || dep.dep_kinds.iter().any(|k| k.kind.is_none());
+                if !is_regular || !seen.insert(dep.pkg.as_str()) {
+                    continue;
+                }
+                if self.workspace_members.contains(&dep.pkg) {
+                    continue;
+                }
+                if let Some(p) = packages.get(dep.pkg.as_str()) {
+                    dependencies.push(*p);
+                }
+                if transitive {
+                    queue.push_back(dep.pkg.as_str());
+                }
+            }
+        }
+
+        dependencies.sort_by(|a, b| {
+            a.name
+                .cmp(&b.name)
+                .then_with(|| version_key(&b.version).cmp(&version_key(&a.version)))
+        });
+        Ok(dependencies)
+    }
+}
+
+#[cfg(test)]
+impl Metadata {
+    /// Create the metadata of a workspace at `/ws` with the given members
+    /// and no resolved dependency graph.
+    pub(crate) fn workspace(members: Vec<Package>) -> Self {
+        Metadata {
+            workspace_members: members.iter().map(|p| p.id.clone()).collect(),
+            packages: members,
+            target_directory: "/ws/target".to_string(),
+            workspace_root: "/ws".to_string(),
+            resolve: None,
+        }
+    }
+
+    /// Add a dependency edge of the given kind to the resolved dependency
+    /// graph, between the packages with IDs `from` and `to`.
+    pub(crate) fn add_resolved_dependency(&mut self, from: &str, to: &str, kind: Option<&str>) {
+        let nodes = &mut self
+            .resolve
+            .get_or_insert(Resolve { nodes: Vec::new() })
+            .nodes;
+        for id in [from, to] {
+            if !nodes.iter().any(|node| node.id == id) {
+                nodes.push(Node {
+                    id: id.to_string(),
+                    deps: Vec::new(),
+                    features: Vec::new(),
+                });
+            }
+        }
+        if let Some(node) = nodes.iter_mut().find(|node| node.id == from) {
+            node.deps.push(NodeDep {
+                pkg: to.to_string(),
+                dep_kinds: vec![DepKindInfo {
+                    kind: kind.map(str::to_string),
+                }],
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+impl Package {
+    /// Create a workspace package at `/ws/<name>` declaring the given
+    /// dependencies with their kinds, and without build targets.
+    pub(crate) fn local(name: &str, dependencies: &[(&str, Option<&str>)]) -> Self {
+        Package {
+            name: name.to_string(),
+            version: "0.1.0".to_string(),
+            id: format!("path+file:///ws/{}#0.1.0", name),
+            dependencies: dependencies
+                .iter()
+                .map(|(dep, kind)| Dependency {
+                    name: dep.to_string(),
+                    kind: kind.map(str::to_string),
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Create a crates.io package with a library target.
+    pub(crate) fn registry(name: &str, version: &str) -> Self {
+        Package {
+            name: name.to_string(),
+            version: version.to_string(),
+            id: format!(
+                "registry+https://github.com/rust-lang/crates.io-index#{}@{}",
+                name, version
+            ),
+            targets: vec![Target {
+                name: name.to_string(),
+                kind: vec!["lib".to_string()],
+            }],
+            ..Default::default()
+        }
+    }
+}
+
+/// Check whether a version matches a full or partial version, as in a
+/// `name@version` spec. `1` and `1.0` match `1.0.109`, `1.0.1` does not.
+pub fn version_matches(version: &str, partial: &str) -> bool {
+    version == partial || version.starts_with(&format!("{}.", partial))
+}
+
+/// Numeric components of a version string, used to order package versions.
+///
+/// Pre-release and build suffixes are ignored.
+pub fn version_key(version: &str) -> Vec<u64> {
+    version
+        .split(['-', '+'])
+        .next()
+        .unwrap_or_default()
+        .split('.')
+        .map(|part| part.parse().unwrap_or(0))
+        .collect()
+}
+
+/// Format a dependency chain as `member -> dep v1.0.0 -> dep v2.0.0`.
+pub fn format_dependency_chain(chain: &[&Package]) -> String {
+    let entries: Vec<String> = chain
+        .iter()
+        .enumerate()
+        .map(|(index, p)| {
+            if index == 0 || p.version.is_empty() {
+                p.name.clone()
+            } else {
+                format!("{} v{}", p.name, p.version)
+            }
+        })
+        .collect();
+    entries.join(" -> ")
+}
+
+/// Format dependency owners as `dep (member, member), dep (member)`.
+pub fn format_dependency_owners(owners: &BTreeMap<&str, Vec<&str>>) -> String {
+    let entries: Vec<String> = owners
+        .iter()
+        .map(|(dep, members)| format!("{} ({})", dep, members.join(", ")))
+        .collect();
+    entries.join(", ")
+}
+
 /// Get cargo metadata for the current project.
 ///
 /// This function executes `cargo metadata --no-deps --format-version 1`
-/// and parses the JSON output into a Metadata struct.
+/// and parses the JSON output into a Metadata struct. The metadata is read
+/// once per process, so that the queries of a long-running `cargo txt mcp`
+/// server do not run cargo every time.
 pub fn metadata() -> Result<Metadata> {
+    static METADATA: OnceLock<Metadata> = OnceLock::new();
+
+    if let Some(metadata) = METADATA.get() {
+        return Ok(metadata.clone());
+    }
+    let metadata = run_metadata(&["metadata", "--no-deps", "--format-version", "1"])?;
+    Ok(METADATA.get_or_init(|| metadata).clone())
+}
+
+/// Get cargo metadata including the resolved dependency graph.
+///
+/// This function executes `cargo metadata --format-version 1`, which lists
+/// every package in the dependency graph and fills in `resolve`.
+pub fn metadata_with_deps() -> Result<Metadata> {
+    run_metadata(&["metadata", "--format-version", "1"])
+}
+
+/// Execute a cargo metadata command and parse its JSON output.
+fn run_metadata(args: &[&str]) -> Result<Metadata> {
     let output = Command::new("cargo")
-        .args(["metadata", "--no-deps", "--format-version", "1"])
+        .args(args)
         .output()
         .context("failed to execute cargo metadata command")?;
 
@@ -56,26 +613,511 @@ pub fn metadata() -> Result<Metadata> {
     Ok(metadata)
 }
 
+/// Options forwarded to `cargo doc` and `cargo rustdoc`.
+#[derive(Debug, Default, Clone)]
+pub struct DocOptions {
+    /// Document private items (`--document-private-items`)
+    pub document_private_items: bool,
+    /// Features of the documented package to enable (`--features`)
+    pub features: Vec<String>,
+    /// Enable every feature of the documented package (`--all-features`)
+    pub all_features: bool,
+    /// Do not enable the `default` feature (`--no-default-features`)
+    pub no_default_features: bool,
+    /// Target triple to document for (`--target`)
+    pub target: Option<String>,
+    /// Generated package that enables the features of a dependency,
+    /// see `feature_manifest`
+    pub feature_manifest: Option<FeatureManifest>,
+    /// Build like docs.rs: with the nightly toolchain and `--cfg docsrs`
+    pub docsrs: bool,
+    /// Extra flags passed to rustc, from the docs.rs metadata
+    pub rustc_args: Vec<String>,
+    /// Extra flags passed to rustdoc, from the docs.rs metadata
+    pub rustdoc_args: Vec<String>,
+}
+
+impl DocOptions {
+    /// Check whether features other than the ones of the resolved dependency
+    /// graph are requested.
+    pub fn selects_features(&self) -> bool {
+        !self.features.is_empty() || self.all_features || self.no_default_features
+    }
+
+    /// Apply the docs.rs metadata of a package to the options.
+    ///
+    /// Features selected on the command line take precedence over the
+    /// features of the metadata.
+    pub fn with_docs_rs(&self, package: &Package) -> DocOptions {
+        let docs_rs = package.docs_rs_metadata();
+        debug!("docs.rs metadata of '{}': {:?}", package.name, docs_rs);
+
+        let mut options = self.clone();
+        if !self.selects_features() {
+            options.features = docs_rs.features;
+            options.all_features = docs_rs.all_features;
+            options.no_default_features = docs_rs.no_default_features;
+        }
+        options.rustc_args = docs_rs.rustc_args;
+        options.rustdoc_args = docs_rs.rustdoc_args;
+        options
+    }
+
+    /// Create a cargo command, with the nightly toolchain and the docs.rs
+    /// compiler flags in docs.rs mode.
+    ///
+    /// The flags are appended to the ones of the environment, through the
+    /// `CARGO_ENCODED_RUSTDOCFLAGS` and `CARGO_ENCODED_RUSTFLAGS` variables
+    /// so that arguments containing spaces are kept intact. With a feature
+    /// manifest, rustdoc lints are capped to warnings: cargo caps the lints of
+    /// registry dependencies, but the generated package depends on their
+    /// sources through a path. Only rustdoc runs on the documented package,
+    /// so its `RUSTFLAGS`, and the artifacts of the target directory, are left
+    /// untouched.
+    fn cargo_command(&self) -> Command {
+        let mut cmd = Command::new("cargo");
+        let mut rustdoc_flags = Vec::new();
+        if self.docsrs {
+            cmd.arg("+nightly");
+            rustdoc_flags.extend(["--cfg", "docsrs", "-Z", "unstable-options"].map(String::from));
+            rustdoc_flags.extend(self.rustdoc_args.iter().cloned());
+            if !self.rustc_args.is_empty() {
+                cmd.env(
+                    "CARGO_ENCODED_RUSTFLAGS",
+                    encoded_flags("RUSTFLAGS", &self.rustc_args),
+                );
+            }
+        }
+        if self.feature_manifest.is_some() {
+            rustdoc_flags.extend(["--cap-lints", "warn"].map(String::from));
+        }
+        if !rustdoc_flags.is_empty() {
+            cmd.env(
+                "CARGO_ENCODED_RUSTDOCFLAGS",
+                encoded_flags("RUSTDOCFLAGS", &rustdoc_flags),
+            );
+        }
+        cmd
+    }
+
+    /// Add the package and target selection flags to a cargo command.
+    ///
+    /// With a feature manifest, cargo runs on the generated package, whose
+    /// manifest already enables the features, and builds into the target
+    /// directory of the workspace.
+    fn add_cargo_args(&self, cmd: &mut Command) {
+        match &self.feature_manifest {
+            Some(manifest) => {
+                cmd.arg("--manifest-path").arg(&manifest.manifest_path);
+                cmd.args(["--target-dir", &manifest.target_directory]);
+            }
+            None => {
+                if !self.features.is_empty() {
+                    cmd.args(["--features", &self.features.join(",")]);
+                }
+                if self.all_features {
+                    cmd.arg("--all-features");
+                }
+                if self.no_default_features {
+                    cmd.arg("--no-default-features");
+                }
+            }
+        }
+        if let Some(target) = &self.target {
+            cmd.args(["--target", target]);
+        }
+    }
+}
+
+/// Return the compiler flags of an environment variable followed by `extra`,
+/// separated by the 0x1f character of the `CARGO_ENCODED_*` variables.
+fn encoded_flags(var: &str, extra: &[String]) -> String {
+    let mut flags: Vec<String> = match env::var(format!("CARGO_ENCODED_{}", var)) {
+        Ok(encoded) if !encoded.is_empty() => encoded.split('\x1f').map(str::to_string).collect(),
+        _ => env::var(var)
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(str::to_string)
+            .collect(),
+    };
+    flags.extend(extra.iter().cloned());
+    flags.join("\x1f")
+}
+
+/// A generated package depending on a single dependency with selected
+/// features.
+///
+/// Cargo only accepts feature flags for workspace members, so the features of
+/// a dependency are enabled by the manifest of a generated package instead,
+/// which is documented into the target directory of the workspace.
+#[derive(Debug, Clone)]
+pub struct FeatureManifest {
+    /// Path to the generated Cargo.toml
+    pub manifest_path: PathBuf,
+    /// Target directory of the workspace
+    pub target_directory: String,
+}
+
+/// Write the manifest of a package that depends on `package` with the
+/// features selected by `options`.
+///
+/// The package is generated under `<target>/txt-features/<name>-<version>`
+/// and depends on the sources of `package` through a path dependency. The
+/// Cargo.lock of the workspace is copied next to it, so the other
+/// dependencies keep their locked versions, along with the `[patch]` and
+/// `[replace]` tables of the workspace manifest the lockfile refers to.
+pub fn feature_manifest(
+    metadata: &Metadata,
+    package: &Package,
+    options: &DocOptions,
+) -> Result<FeatureManifest> {
+    let source_dir = Path::new(&package.manifest_path)
+        .parent()
+        .with_context(|| format!("package '{}' has no manifest path", package.name))?;
+    let features: Vec<&String> = if options.all_features {
+        package.features.keys().collect()
+    } else {
+        options.features.iter().collect()
+    };
+
+    let manifest = format!(
+        concat!(
+            "[package]\n",
+            "name = \"cargo-txt-features\"\n",
+            "version = \"0.0.0\"\n",
+            "edition = \"2021\"\n",
+            "publish = false\n",
+            "\n",
+            "[lib]\n",
+            "path = \"lib.rs\"\n",
+            "\n",
+            "[dependencies]\n",
+            "{} = {{ path = {}, default-features = {}, features = {} }}\n",
+            "\n",
+            "[workspace]\n"
+        ),
+        package.name,
+        serde_json::to_string(&source_dir)?,
+        !options.no_default_features,
+        serde_json::to_string(&features)?
+    );
+
+    let workspace_root = Path::new(&metadata.workspace_root);
+    let root_manifest = workspace_root.join("Cargo.toml");
+    let manifest = match fs::read_to_string(&root_manifest) {
+        Ok(root) => format!("{}{}", manifest, override_tables(&root, workspace_root)),
+        Err(e) => {
+            debug!("Failed to read {}: {}", root_manifest.display(), e);
+            manifest
+        }
+    };
+
+    let dir = Path::new(&metadata.target_directory)
+        .join("txt-features")
+        .join(format!("{}-{}", package.name, package.version));
+    fs::create_dir_all(&dir)
+        .with_context(|| format!("failed to create directory '{}'", dir.display()))?;
+    let manifest_path = dir.join("Cargo.toml");
+    fs::write(&manifest_path, manifest)
+        .with_context(|| format!("failed to write file '{}'", manifest_path.display()))?;
+    fs::write(dir.join("lib.rs"), "")
+        .with_context(|| format!("failed to write file '{}'", dir.join("lib.rs").display()))?;
+
+    let lockfile = Path::new(&metadata.workspace_root).join("Cargo.lock");
+    if lockfile.exists() {
+        fs::copy(&lockfile, dir.join("Cargo.lock"))
+            .with_context(|| format!("failed to copy '{}'", lockfile.display()))?;
+    }
+    debug!("Generated feature manifest: {}", manifest_path.display());
+
+    Ok(FeatureManifest {
+        manifest_path,
+        target_directory: metadata.target_directory.clone(),
+    })
+}
+
+/// Return the `[patch]` and `[replace]` tables of a manifest.
+///
+/// The tables are copied as written, except for relative `path` values,
+/// which are resolved against `manifest_dir` since the copy lives in another
+/// directory.
+fn override_tables(manifest: &str, manifest_dir: &Path) -> String {
+    let mut tables = String::new();
+    let mut in_override = false;
+    for line in manifest.lines() {
+        let trimmed = line.trim_start();
+        if let Some(header) = trimmed.strip_prefix('[') {
+            let name = header.split(']').next().unwrap_or_default().trim();
+            in_override = ["patch", "replace"]
+                .iter()
+                .any(|table| name == *table || name.starts_with(&format!("{}.", table)));
+            if in_override {
+                tables.push('\n');
+            }
+        }
+        if in_override {
+            tables.push_str(&absolute_paths(line, manifest_dir));
+            tables.push('\n');
+        }
+    }
+    tables
+}
+
+/// Resolve the relative `path = "..."` values of a manifest line against
+/// `base`.
+fn absolute_paths(line: &str, base: &Path) -> String {
+    let mut result = String::new();
+    let mut rest = line;
+    while let Some(index) = rest.find("path") {
+        let (before, after) = rest.split_at(index + "path".len());
+        let is_key = before[..index]
+            .chars()
+            .next_back()
+            .is_none_or(|c| !(c.is_alphanumeric() || c == '-' || c == '_' || c == '.'));
+        result.push_str(before);
+        rest = after;
+
+        let Some(value) = rest.trim_start().strip_prefix('=').map(str::trim_start) else {
+            continue;
+        };
+        let Some(quote) = value
+            .chars()
+            .next()
+            .filter(|c| is_key && (*c == '"' || *c == '\''))
+        else {
+            continue;
+        };
+        let Some(end) = value[1..].find(quote) else {
+            continue;
+        };
+        let literal = &value[..end + 2];
+        let path = match quote {
+            '"' => serde_json::from_str(literal).unwrap_or_else(|_| value[1..=end].to_string()),
+            _ => value[1..=end].to_string(),
+        };
+
+        result.push_str(&rest[..rest.len() - value.len()]);
+        match serde_json::to_string(&base.join(path)) {
+            Ok(absolute) => result.push_str(&absolute),
+            Err(_) => result.push_str(literal),
+        }
+        rest = &value[end + 2..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Return the directory cargo writes documentation to, which depends on
+/// the target triple (e.g., "target/doc" or "target/x86_64-pc-windows-msvc/doc").
+pub fn doc_directory(target_directory: &str, options: &DocOptions) -> PathBuf {
+    let mut dir = PathBuf::from(target_directory);
+    if let Some(target) = &options.target {
+        dir.push(target);
+    }
+    dir.join("doc")
+}
+
+/// A warning emitted by rustdoc while generating documentation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocWarning {
+    /// Package the warning was emitted for, when cargo reported it
+    pub package: Option<String>,
+    /// The warning message, followed by its source location if any
+    /// (e.g., "unresolved link to `Missing` (src/lib.rs:1:10)")
+    pub message: String,
+}
+
 /// Generate HTML documentation for a specific crate.
 ///
 /// This function executes `cargo doc --package <crate> --no-deps`,
 /// parses the output to find the generated directory, and returns the path
-/// to the HTML documentation directory.
-pub fn doc(crate_name: &str) -> Result<PathBuf> {
+/// to the HTML documentation directory with the warnings rustdoc emitted.
+pub fn doc(crate_name: &str, options: &DocOptions) -> Result<(PathBuf, Vec<DocWarning>)> {
     debug!("Starting documentation build for crate '{}'", crate_name);
 
-    let mut cmd = Command::new("cargo");
+    let mut cmd = options.cargo_command();
     cmd.args(["doc", "--package", crate_name, "--no-deps"]);
+    options.add_cargo_args(&mut cmd);
+    if options.document_private_items {
+        cmd.arg("--document-private-items");
+    }
+
+    debug!("Executing: {:?}", cmd);
+
+    let stderr = run_doc_command(cmd, crate_name)?;
+
+    Ok((doc_output_dir(&stderr)?, doc_warnings(&stderr)))
+}
 
-    debug!("Executing: cargo doc --package {} --no-deps", crate_name);
+/// Generate HTML documentation for several packages in a single run.
+///
+/// This function executes `cargo doc --no-deps` with one `--package` flag
+/// per spec. The generated directories live under `<target>/doc/<lib_name>`.
+/// Returns the warnings rustdoc emitted for all packages.
+pub fn doc_packages(specs: &[String], options: &DocOptions) -> Result<Vec<DocWarning>> {
+    debug!("Starting documentation build for {} packages", specs.len());
+
+    let mut cmd = options.cargo_command();
+    cmd.args(["doc", "--no-deps"]);
+    for spec in specs {
+        cmd.args(["--package", spec]);
+    }
+    options.add_cargo_args(&mut cmd);
+    if options.document_private_items {
+        cmd.arg("--document-private-items");
+    }
+
+    debug!("Executing: {:?}", cmd);
+
+    let stderr = run_doc_command(cmd, &specs.join(", "))?;
 
+    Ok(doc_warnings(&stderr))
+}
+
+/// Generate rustdoc JSON for a specific crate.
+///
+/// This function executes `cargo rustdoc --package <crate> --lib -- -Z
+/// unstable-options --output-format json`, using the nightly toolchain when
+/// one is installed, and returns the path to the generated JSON file with the
+/// warnings rustdoc emitted. rustdoc names the file after the library target,
+/// `lib_name`, which may differ from the package name.
+pub fn rustdoc_json(
+    crate_name: &str,
+    lib_name: &str,
+    target_directory: &str,
+    options: &DocOptions,
+) -> Result<(PathBuf, Vec<DocWarning>)> {
+    debug!("Starting rustdoc JSON build for crate '{}'", crate_name);
+
+    // In docs.rs mode, cargo_command already selects the nightly toolchain.
+    let mut cmd = options.cargo_command();
+    let nightly = options.docsrs || has_nightly_toolchain();
+    if !options.docsrs {
+        if nightly {
+            cmd.arg("+nightly");
+        } else {
+            debug!("Nightly toolchain not found, using the active toolchain");
+        }
+    }
+    // Extra rustdoc arguments can only be passed to one target, so select
+    // the library explicitly for packages that also have bins or examples.
+    cmd.args(["rustdoc", "--package", crate_name, "--lib"]);
+    options.add_cargo_args(&mut cmd);
+    cmd.args(["--", "-Z", "unstable-options", "--output-format", "json"]);
+    if options.document_private_items {
+        cmd.arg("--document-private-items");
+    }
+
+    debug!("Executing: {:?}", cmd);
+
+    let result = run_doc_command(cmd, crate_name);
+    let stderr = if nightly {
+        result?
+    } else {
+        result.context(
+            "rustdoc JSON output requires a nightly toolchain. Install one with `rustup toolchain install nightly`",
+        )?
+    };
+
+    let json_path = rustdoc_json_path(target_directory, options, lib_name);
+    ensure!(
+        json_path.exists(),
+        "rustdoc JSON output not found at '{}'",
+        json_path.display()
+    );
+
+    Ok((json_path, doc_warnings(&stderr)))
+}
+
+/// Return the path of the rustdoc JSON output of the library `lib_name`.
+fn rustdoc_json_path(target_directory: &str, options: &DocOptions, lib_name: &str) -> PathBuf {
+    doc_directory(target_directory, options).join(format!("{}.json", lib_name))
+}
+
+/// Get the version of the compiler used to generate documentation.
+///
+/// With `nightly`, the version of the rustup nightly toolchain is returned,
+/// matching the toolchain `rustdoc_json` picks.
+pub fn rustc_version(nightly: bool) -> Result<String> {
+    let mut cmd = if nightly {
+        let mut cmd = Command::new("rustup");
+        cmd.args(["run", "nightly", "rustc"]);
+        cmd
+    } else {
+        Command::new("rustc")
+    };
+    cmd.arg("--version");
+
+    debug!("Executing: {:?}", cmd);
+
+    let output = cmd.output().context("failed to execute rustc --version")?;
+    ensure!(
+        output.status.success(),
+        "rustc --version failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Locate the HTML documentation of the standard library crates.
+///
+/// The `rust-docs` rustup component installs it under
+/// `<sysroot>/share/doc/rust/html`, with one directory per crate
+/// (`std`, `core`, `alloc`, ...). The sysroot is the one of the toolchain
+/// active in the current directory.
+pub fn rust_docs_dir() -> Result<PathBuf> {
+    let mut cmd = Command::new("rustc");
+    cmd.args(["--print", "sysroot"]);
+
+    debug!("Executing: {:?}", cmd);
+
+    let output = cmd
+        .output()
+        .context("failed to execute rustc --print sysroot")?;
+    ensure!(
+        output.status.success(),
+        "rustc --print sysroot failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let sysroot = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    let docs_dir = sysroot.join("share").join("doc").join("rust").join("html");
+    ensure!(
+        docs_dir.join("std").join("all.html").exists(),
+        concat!(
+            "standard library documentation not found in '{}'.\n",
+            "\n",
+            "Install the rust-docs component of the active toolchain with ",
+            "`rustup component add rust-docs`."
+        ),
+        docs_dir.display()
+    );
+
+    Ok(docs_dir)
+}
+
+/// Check whether a nightly toolchain is installed through rustup.
+pub fn has_nightly_toolchain() -> bool {
+    Command::new("rustup")
+        .args(["run", "nightly", "rustc", "--version"])
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+/// Execute a cargo documentation command and return its stderr output.
+///
+/// Failures are turned into descriptive errors, with dedicated messages
+/// for panics and ambiguous package specifications.
+fn run_doc_command(mut cmd: Command, crate_name: &str) -> Result<String> {
     let output = cmd.output().context(format!(
         "failed to execute cargo doc for crate '{}'",
         crate_name
     ))?;
 
     let stdout = String::from_utf8_lossy(&output.stdout);
-    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
 
     trace!("Exit code: {}", output.status);
     trace!("stdout len: {}", stdout.len());
@@ -137,7 +1179,51 @@ pub fn doc(crate_name: &str) -> Result<PathBuf> {
         );
     }
 
-    doc_output_dir(&stderr)
+    Ok(stderr)
+}
+
+/// Parse the warnings rustdoc emitted from the stderr of a cargo doc run.
+///
+/// Cargo prints the diagnostics of a package together, followed by a
+/// "`<package>` (lib doc) generated N warnings" line, which assigns the
+/// preceding warnings to that package. The source location on the line after
+/// a warning is appended to its message.
+pub fn doc_warnings(stderr: &str) -> Vec<DocWarning> {
+    let mut warnings = Vec::new();
+    let mut pending: Vec<DocWarning> = Vec::new();
+    let mut lines = stderr.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        let Some(message) = line.strip_prefix("warning: ") else {
+            continue;
+        };
+
+        if let Some(rest) = message.strip_prefix('`')
+            && let Some((package, summary)) = rest.split_once('`')
+            && summary.contains(" generated ")
+        {
+            for mut warning in pending.drain(..) {
+                warning.package = Some(package.to_string());
+                warnings.push(warning);
+            }
+            continue;
+        }
+
+        let location = lines
+            .peek()
+            .and_then(|next| next.trim_start().strip_prefix("--> "));
+        let message = match location {
+            Some(location) => format!("{} ({})", message, location.trim()),
+            None => message.to_string(),
+        };
+        pending.push(DocWarning {
+            package: None,
+            message,
+        });
+    }
+
+    warnings.extend(pending);
+    warnings
 }
 
 /// Parse cargo doc output to extract the generated HTML directory path.
@@ -214,6 +1300,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn doc_warnings_are_assigned_to_packages() {
+        let stderr = concat!(
+            " Documenting probe v0.1.0 (/tmp/probe)\n",
+            "warning: unresolved link to `Missing`\n",
+            " --> src/lib.rs:1:10\n",
+            "  |\n",
+            "1 | //! See [Missing].\n",
+            "  |          ^^^^^^^ no item named `Missing` in scope\n",
+            "\n",
+            "warning: `probe` (lib doc) generated 1 warning\n",
+            "warning: unused manifest key: package.extra\n",
+            "    Finished `dev` profile [unoptimized + debuginfo] target(s) in 0.80s\n",
+        );
+        assert_eq!(
+            doc_warnings(stderr),
+            vec![
+                DocWarning {
+                    package: Some("probe".to_string()),
+                    message: "unresolved link to `Missing` (src/lib.rs:1:10)".to_string(),
+                },
+                DocWarning {
+                    package: None,
+                    message: "unused manifest key: package.extra".to_string(),
+                },
+            ]
+        );
+    }
+
     #[test]
     fn doc_output_dir_returns_error_without_generated_line() {
         let stdout = "  Documenting serde v1.0.193\n  some other output\n";
@@ -224,6 +1339,469 @@ mod tests {
         assert!(error_msg.contains("could not find 'Generated' line"));
     }
 
+    fn workspace() -> Metadata {
+        Metadata::workspace(vec![
+            Package::local("app", &[("serde", None), ("tempfile", Some("dev"))]),
+            Package::local("core", &[("serde", None), ("anyhow", None)]),
+        ])
+    }
+
+    fn workspace_with_resolve() -> Metadata {
+        let mut metadata = workspace();
+        let reqwest = Package::registry("reqwest", "0.12.0");
+        let http = Package::registry("http", "1.1.0");
+        let criterion = Package::registry("criterion", "0.5.0");
+
+        let app = metadata.packages[0].id.clone();
+        metadata.add_resolved_dependency(&app, &reqwest.id, None);
+        metadata.add_resolved_dependency(&app, &criterion.id, Some("dev"));
+        metadata.add_resolved_dependency(&reqwest.id, &http.id, None);
+        metadata.packages.extend([reqwest, http, criterion]);
+        metadata
+    }
+
+    #[test]
+    fn dependency_chain_finds_transitive_dependency() {
+        let metadata = workspace_with_resolve();
+        let chain = metadata.dependency_chain("http", None).unwrap().unwrap();
+        assert_eq!(
+            format_dependency_chain(&chain),
+            "app -> reqwest v0.12.0 -> http v1.1.0"
+        );
+    }
+
+    #[test]
+    fn dependency_chain_skips_dev_dependencies() {
+        let metadata = workspace_with_resolve();
+        assert!(
+            metadata
+                .dependency_chain("criterion", None)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn dependency_chain_respects_package_filter() {
+        let metadata = workspace_with_resolve();
+        assert!(
+            metadata
+                .dependency_chain("http", Some("core"))
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            metadata
+                .dependency_chain("http", Some("app"))
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn find_package_by_spec() {
+        let mut metadata = workspace_with_resolve();
+        metadata.packages.push(Package::registry("http", "0.2.12"));
+
+        assert_eq!(metadata.find_package("reqwest").unwrap().version, "0.12.0");
+        assert!(metadata.find_package("http").is_none(), "ambiguous spec");
+        assert_eq!(metadata.find_package("http@1").unwrap().version, "1.1.0");
+        assert_eq!(
+            metadata.find_package("http@0.2.12").unwrap().version,
+            "0.2.12"
+        );
+        assert!(metadata.find_package("http@0.1").is_none());
+        assert!(metadata.find_package("missing").is_none());
+    }
+
+    #[test]
+    fn select_package_prefers_direct_dependency() {
+        let mut metadata = workspace_with_resolve();
+        let http_02 = Package::registry("http", "0.2.12");
+        let app = metadata.packages[0].id.clone();
+        metadata.add_resolved_dependency(&app, &http_02.id, None);
+        metadata.packages.push(http_02);
+
+        let selected = metadata.select_package("http", None).unwrap();
+        assert_eq!(selected.version, "0.2.12");
+        assert!(metadata.is_default_version(selected, None));
+        assert_eq!(metadata.package_spec(selected), "http@0.2.12");
+
+        let http_1 = metadata.select_package("http@1", None).unwrap();
+        assert_eq!(http_1.version, "1.1.0");
+        assert!(!metadata.is_default_version(http_1, None));
+        assert!(metadata.select_package("http", Some("core")).is_none());
+
+        let reqwest = metadata.select_package("reqwest", None).unwrap();
+        assert_eq!(metadata.package_spec(reqwest), "reqwest");
+    }
+
+    #[test]
+    fn enabled_features_follow_feature_table() {
+        let mut p = Package::local("tokio", &[]);
+        p.features = BTreeMap::from([
+            ("default".to_string(), vec!["rt".to_string()]),
+            (
+                "full".to_string(),
+                vec!["rt".to_string(), "net".to_string()],
+            ),
+            (
+                "net".to_string(),
+                vec!["dep:socket2".to_string(), "mio/net".to_string()],
+            ),
+            ("rt".to_string(), Vec::new()),
+            ("tracing".to_string(), Vec::new()),
+        ]);
+
+        let options = |features: &[&str], all_features, no_default_features| DocOptions {
+            features: features.iter().map(|f| f.to_string()).collect(),
+            all_features,
+            no_default_features,
+            ..Default::default()
+        };
+        assert_eq!(
+            p.enabled_features(&options(&[], false, false)),
+            ["default", "rt"]
+        );
+        assert_eq!(
+            p.enabled_features(&options(&["full"], false, true)),
+            ["full", "net", "rt"]
+        );
+        assert!(p.enabled_features(&options(&[], false, true)).is_empty());
+        assert_eq!(
+            p.enabled_features(&options(&[], true, false)),
+            ["default", "full", "net", "rt", "tracing"]
+        );
+    }
+
+    #[test]
+    fn docs_rs_metadata_applies_to_options() {
+        let mut p = Package::local("tokio", &[]);
+        assert_eq!(p.docs_rs_metadata(), DocsRsMetadata::default());
+
+        p.metadata = serde_json::json!({
+            "docs": {
+                "rs": {
+                    "all-features": true,
+                    "rustc-args": ["--cfg", "tokio_unstable"],
+                    "rustdoc-args": ["--cfg", "docsrs", "--cfg", "tokio_unstable"]
+                }
+            }
+        });
+        let options = DocOptions {
+            docsrs: true,
+            ..Default::default()
+        }
+        .with_docs_rs(&p);
+        assert!(options.all_features);
+        assert_eq!(options.rustc_args, ["--cfg", "tokio_unstable"]);
+        assert_eq!(options.rustdoc_args.len(), 4);
+
+        let options = DocOptions {
+            docsrs: true,
+            features: vec!["rt".to_string()],
+            ..Default::default()
+        }
+        .with_docs_rs(&p);
+        assert!(
+            !options.all_features,
+            "Command-line features take precedence"
+        );
+        assert_eq!(options.features, ["rt"]);
+
+        p.metadata = serde_json::json!({"docs": {"rs": {"features": "full"}}});
+        assert_eq!(p.docs_rs_metadata(), DocsRsMetadata::default());
+    }
+
+    #[test]
+    fn doc_options_add_cargo_args() {
+        let args = |options: &DocOptions| {
+            let mut cmd = Command::new("cargo");
+            options.add_cargo_args(&mut cmd);
+            cmd.get_args()
+                .map(|arg| arg.to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert!(args(&DocOptions::default()).is_empty());
+
+        let options = DocOptions {
+            features: vec!["derive".to_string(), "rc".to_string()],
+            no_default_features: true,
+            target: Some("wasm32-unknown-unknown".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            args(&options),
+            [
+                "--features",
+                "derive,rc",
+                "--no-default-features",
+                "--target",
+                "wasm32-unknown-unknown"
+            ]
+        );
+        assert_eq!(
+            doc_directory("/ws/target", &options),
+            PathBuf::from("/ws/target/wasm32-unknown-unknown/doc")
+        );
+
+        let options = DocOptions {
+            feature_manifest: Some(FeatureManifest {
+                manifest_path: PathBuf::from("/ws/target/txt-features/serde-1.0.0/Cargo.toml"),
+                target_directory: "/ws/target".to_string(),
+            }),
+            ..options
+        };
+        assert_eq!(
+            args(&options),
+            [
+                "--manifest-path",
+                "/ws/target/txt-features/serde-1.0.0/Cargo.toml",
+                "--target-dir",
+                "/ws/target",
+                "--target",
+                "wasm32-unknown-unknown"
+            ]
+        );
+    }
+
+    #[test]
+    fn feature_manifest_depends_on_package_sources() {
+        let workspace_dir = tempfile::tempdir().unwrap();
+        let root = workspace_dir.path();
+        fs::write(root.join("Cargo.lock"), "version = 4\n").unwrap();
+
+        let mut metadata = workspace();
+        metadata.workspace_root = root.display().to_string();
+        metadata.target_directory = root.join("target").display().to_string();
+        let mut serde = Package::local("serde", &[]);
+        serde.version = "1.0.228".to_string();
+        serde.manifest_path = "/registry/serde-1.0.228/Cargo.toml".to_string();
+
+        let options = DocOptions {
+            features: vec!["derive".to_string()],
+            no_default_features: true,
+            ..Default::default()
+        };
+        let manifest = feature_manifest(&metadata, &serde, &options).unwrap();
+
+        let dir = root
+            .join("target")
+            .join("txt-features")
+            .join("serde-1.0.228");
+        assert_eq!(manifest.manifest_path, dir.join("Cargo.toml"));
+        let content = fs::read_to_string(&manifest.manifest_path).unwrap();
+        assert!(content.contains(
+            "serde = { path = \"/registry/serde-1.0.228\", default-features = false, features = [\"derive\"] }"
+        ));
+        assert!(content.contains("[workspace]"));
+        assert!(!content.contains("[patch"));
+        assert!(dir.join("lib.rs").exists());
+        assert_eq!(
+            fs::read_to_string(dir.join("Cargo.lock")).unwrap(),
+            "version = 4\n"
+        );
+    }
+
+    #[test]
+    fn feature_manifest_keeps_workspace_patches() {
+        let workspace_dir = tempfile::tempdir().unwrap();
+        let root = workspace_dir.path();
+        fs::write(
+            root.join("Cargo.toml"),
+            concat!(
+                "[package]\n",
+                "name = \"app\"\n",
+                "\n",
+                "[dependencies]\n",
+                "serde = { path = \"ignored\" }\n",
+                "\n",
+                "[patch.crates-io]\n",
+                "serde = { path = \"vendor/serde\" }\n",
+                "syn = { git = \"https://github.com/dtolnay/syn\", branch = \"master\" }\n",
+                "\n",
+                "[patch.crates-io.tokio]\n",
+                "path = '../tokio'\n",
+                "\n",
+                "[replace]\n",
+                "\"log:0.4.22\" = { path = \"/src/log\" }\n",
+                "\n",
+                "[profile.release]\n",
+                "debug = true\n",
+            ),
+        )
+        .unwrap();
+
+        let mut metadata = workspace();
+        metadata.workspace_root = root.display().to_string();
+        metadata.target_directory = root.join("target").display().to_string();
+        let mut serde = Package::local("serde", &[]);
+        serde.version = "1.0.228".to_string();
+        serde.manifest_path = "/registry/serde-1.0.228/Cargo.toml".to_string();
+
+        let options = DocOptions {
+            features: vec!["derive".to_string()],
+            ..Default::default()
+        };
+        let manifest = feature_manifest(&metadata, &serde, &options).unwrap();
+        let content = fs::read_to_string(&manifest.manifest_path).unwrap();
+
+        let path = |path: &Path| serde_json::to_string(path).unwrap();
+        assert!(content.contains(&format!(
+            "[patch.crates-io]\nserde = {{ path = {} }}\nsyn = {{ git = \"https://github.com/dtolnay/syn\", branch = \"master\" }}\n",
+            path(&root.join("vendor/serde"))
+        )));
+        assert!(content.contains(&format!(
+            "[patch.crates-io.tokio]\npath = {}\n",
+            path(&root.join("../tokio"))
+        )));
+        assert!(content.contains("[replace]\n\"log:0.4.22\" = { path = \"/src/log\" }\n"));
+        assert!(!content.contains("ignored"));
+        assert!(!content.contains("[profile.release]"));
+    }
+
+    #[test]
+    fn feature_manifest_caps_rustdoc_lints() {
+        let flags = |options: &DocOptions| {
+            options
+                .cargo_command()
+                .get_envs()
+                .find(|(key, _)| *key == "CARGO_ENCODED_RUSTDOCFLAGS")
+                .and_then(|(_, value)| value.map(|v| v.to_string_lossy().to_string()))
+        };
+
+        assert_eq!(flags(&DocOptions::default()), None);
+        let options = DocOptions {
+            feature_manifest: Some(FeatureManifest {
+                manifest_path: PathBuf::from("/ws/target/txt-features/serde-1.0.228/Cargo.toml"),
+                target_directory: "/ws/target".to_string(),
+            }),
+            ..Default::default()
+        };
+        assert!(flags(&options).unwrap().ends_with("--cap-lints\x1fwarn"));
+    }
+
+    #[test]
+    fn rustdoc_json_path_uses_lib_name() {
+        let mut package = Package::local("rustdoc-types", &[]);
+        package.targets = vec![Target {
+            name: "rdt".to_string(),
+            kind: vec!["lib".to_string()],
+        }];
+        let lib_name = package.lib_name().unwrap();
+
+        assert_eq!(
+            rustdoc_json_path("/ws/target", &DocOptions::default(), &lib_name),
+            PathBuf::from("/ws/target/doc/rdt.json")
+        );
+        let options = DocOptions {
+            target: Some("wasm32-unknown-unknown".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            rustdoc_json_path("/ws/target", &options, &lib_name),
+            PathBuf::from("/ws/target/wasm32-unknown-unknown/doc/rdt.json")
+        );
+    }
+
+    #[test]
+    fn version_matches_partial_versions() {
+        assert!(version_matches("1.0.109", "1"));
+        assert!(version_matches("1.0.109", "1.0"));
+        assert!(version_matches("1.0.109", "1.0.109"));
+        assert!(!version_matches("1.0.109", "1.0.1"));
+        assert!(!version_matches("10.0.0", "1"));
+    }
+
+    #[test]
+    fn resolved_features_are_sorted() {
+        let mut metadata = workspace_with_resolve();
+        let resolve = metadata.resolve.as_mut().unwrap();
+        resolve.nodes[0].features = vec!["std".to_string(), "derive".to_string()];
+        let id = resolve.nodes[0].id.clone();
+
+        assert_eq!(metadata.resolved_features(&id), vec!["derive", "std"]);
+        assert!(metadata.resolved_features("unknown").is_empty());
+    }
+
+    #[test]
+    fn resolved_dependencies_direct_and_transitive() {
+        let metadata = workspace_with_resolve();
+
+        let direct = metadata.resolved_dependencies(None, false).unwrap();
+        let names: Vec<&str> = direct.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["reqwest"]);
+
+        let transitive = metadata.resolved_dependencies(None, true).unwrap();
+        let names: Vec<&str> = transitive.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["http", "reqwest"]);
+
+        let core = metadata.resolved_dependencies(Some("core"), true).unwrap();
+        assert!(core.is_empty());
+    }
+
+    #[test]
+    fn resolved_dependencies_requires_resolve() {
+        let metadata = workspace();
+        assert!(metadata.resolved_dependencies(None, false).is_err());
+    }
+
+    #[test]
+    fn lib_name_uses_library_target() {
+        let mut p = Package::local("rustdoc-types", &[]);
+        assert_eq!(p.lib_name(), None);
+
+        p.targets = vec![
+            Target {
+                name: "cli".to_string(),
+                kind: vec!["bin".to_string()],
+            },
+            Target {
+                name: "rustdoc-types".to_string(),
+                kind: vec!["lib".to_string()],
+            },
+        ];
+        assert_eq!(p.lib_name(), Some("rustdoc_types".to_string()));
+    }
+
+    #[test]
+    fn dependency_chain_without_resolve() {
+        let metadata = workspace();
+        assert!(metadata.dependency_chain("serde", None).unwrap().is_none());
+    }
+
+    #[test]
+    fn dependency_owners_spans_all_workspace_members() {
+        let metadata = workspace();
+        let owners = metadata.dependency_owners(None).unwrap();
+        assert_eq!(owners.get("serde"), Some(&vec!["app", "core"]));
+        assert_eq!(owners.get("anyhow"), Some(&vec!["core"]));
+        assert_eq!(owners.get("tempfile"), Some(&vec!["app"]));
+        assert_eq!(
+            format_dependency_owners(&owners),
+            "anyhow (core), serde (app, core), tempfile (app)"
+        );
+    }
+
+    #[test]
+    fn dependency_owners_restricted_to_package() {
+        let metadata = workspace();
+        let owners = metadata.dependency_owners(Some("app")).unwrap();
+        assert!(owners.contains_key("serde"));
+        assert!(!owners.contains_key("anyhow"));
+    }
+
+    #[test]
+    fn workspace_packages_rejects_unknown_package() {
+        let metadata = workspace();
+        let result = metadata.workspace_packages(Some("missing"));
+        assert!(result.is_err());
+        let error_msg = result.unwrap_err().to_string();
+        assert!(error_msg.contains("not a workspace member"));
+        assert!(error_msg.contains("app, core"));
+    }
+
     #[test]
     fn doc_output_dir_handles_multiple_lines() {
         let stdout = "line 1\nline 2\n   Generated /path/to/doc/index.html\nline 4\n";
@@ -3,31 +3,142 @@
 //! This module handles building documentation by executing cargo doc,
 //! converting the generated HTML to markdown, and writing the result.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
+use std::io::Write;
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+use std::time::Instant;
 
-use anyhow::{Context, Result, bail, ensure};
+use anyhow::{Context, Result, anyhow, bail, ensure};
 use scraper::{Html, Selector};
 use serde::{Deserialize, Serialize};
-use tracing::{debug, info};
+use tracing::{debug, info, trace};
 
-use crate::cargo;
+use crate::cargo::{self, DocWarning};
+use crate::commands::list::ItemKind;
+use crate::commands::{OutputFormat, write_json};
 use crate::html2md;
+use crate::item_info::ItemInfo;
+use crate::json2md;
+use crate::reexports::{self, Reexport};
+use crate::search_index::SearchIndex;
+use crate::sections::{MemberKind, Section};
+
+/// Version of the metadata.json schema written by this version of cargo-txt.
+///
+/// Documentation recorded with another schema version is never up to date.
+pub const SCHEMA_VERSION: u32 = 1;
 
 /// Metadata about a crate's documentation.
 ///
 /// This struct contains information about the relationship between the
 /// crate name (from Cargo.toml) and the library name (from cargo doc output),
-/// as well as a mapping of item paths to their markdown files.
-#[derive(Debug, Serialize, Deserialize)]
+/// as well as a mapping of item paths to their markdown files and metadata.
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct CrateDocMetadata {
+    /// Version of the metadata schema, 0 for metadata written before the
+    /// schema was versioned
+    #[serde(default)]
+    pub schema_version: u32,
     /// The dependency name from Cargo.toml (e.g., "rustdoc-types")
     pub crate_name: String,
     /// The root namespace name from cargo doc (e.g., "rustdoc_types")
     pub lib_name: String,
     /// A mapping of full Rust paths to markdown file paths
     pub item_map: HashMap<String, String>,
+    /// Inputs the documentation was generated from
+    #[serde(flatten)]
+    pub fingerprint: BuildFingerprint,
+    /// A content hash of every converted source HTML page, keyed by the
+    /// page path relative to the cargo doc output directory
+    #[serde(default)]
+    pub page_hashes: HashMap<String, String>,
+    /// A content hash of the crate-wide inputs the links of the converted
+    /// pages depend on, see `link_hash`
+    #[serde(default)]
+    pub link_hash: String,
+    /// A mapping of full Rust paths of members, such as methods, enum
+    /// variants and struct fields, to their rustdoc anchors on the page of
+    /// their item (e.g., "serde::de::Error::custom" to "tymethod.custom")
+    #[serde(default)]
+    pub member_map: HashMap<String, String>,
+    /// A mapping of the other public paths of items, through re-exports, to
+    /// their canonical paths in `item_map` (e.g., "tokio::spawn" to
+    /// "tokio::task::spawn")
+    #[serde(default)]
+    pub alias_map: HashMap<String, String>,
+    /// Kind, signature, summary, deprecation and stability of every item,
+    /// keyed by the full Rust paths of `item_map`
+    #[serde(default)]
+    pub items: HashMap<String, ItemInfo>,
+}
+
+#[cfg(test)]
+impl CrateDocMetadata {
+    /// Write the metadata of a built library to `<docmd_dir>/<dir_name>`,
+    /// and return the library directory.
+    pub(crate) fn write_lib(&self, docmd_dir: &Path, dir_name: &str) -> PathBuf {
+        let lib_dir = docmd_dir.join(dir_name);
+        fs::create_dir_all(&lib_dir).unwrap();
+        fs::write(
+            lib_dir.join("metadata.json"),
+            serde_json::to_string(self).unwrap(),
+        )
+        .unwrap();
+        lib_dir
+    }
+}
+
+/// Create a temporary `docmd` directory with the metadata of the given
+/// libraries, keyed by directory name.
+#[cfg(test)]
+pub(crate) fn temp_docmd(libs: &[(&str, &CrateDocMetadata)]) -> tempfile::TempDir {
+    let docmd_dir = tempfile::tempdir().unwrap();
+    for (dir_name, metadata) in libs {
+        metadata.write_lib(docmd_dir.path(), dir_name);
+    }
+    docmd_dir
+}
+
+/// Inputs that determine the documentation generated for a crate.
+///
+/// A crate whose recorded fingerprint matches the current one does not need
+/// to be rebuilt.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildFingerprint {
+    /// The crate version (e.g., "1.0.228")
+    #[serde(default)]
+    pub version: String,
+    /// Features enabled for the crate, in the resolved dependency graph or
+    /// as selected with `--features`, `--all-features` and
+    /// `--no-default-features`
+    #[serde(default)]
+    pub features: Vec<String>,
+    /// Target triple the documentation was generated for, `None` for the host
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    /// Whether the documentation was generated like docs.rs, with the
+    /// `[package.metadata.docs.rs]` settings and `--cfg docsrs`
+    #[serde(default)]
+    pub docsrs: bool,
+    /// Output of `rustc --version` for the toolchain that generated the docs
+    #[serde(default)]
+    pub rustc_version: String,
+    /// Version of cargo-txt that converted the documentation
+    #[serde(default)]
+    pub generator: String,
+    /// Documentation source that was converted
+    #[serde(default)]
+    pub backend: Backend,
+    /// Whether private items were documented
+    #[serde(default)]
+    pub document_private_items: bool,
+    /// Sections left out of item pages
+    #[serde(default)]
+    pub skipped_sections: Vec<Section>,
 }
 
 /// Cargo documentation output from HTML files.
@@ -37,6 +148,9 @@ pub struct CrateDocMetadata {
 struct CargoDocOutput {
     /// The path to the cargo doc output directory
     path: PathBuf,
+    /// The directory the markdown documentation is written to
+    /// (e.g., "target/docmd/serde")
+    output_path: PathBuf,
     /// A mapping of file paths to their HTML content
     files: HashMap<String, String>,
     /// Metadata extracted from all.html
@@ -51,16 +165,190 @@ struct DocOutput {
     path: PathBuf,
     /// A mapping of file paths to their markdown content
     files: HashMap<String, String>,
+    /// Metadata written to metadata.json once all files are saved
+    metadata: CrateDocMetadata,
+}
+
+/// Source of the documentation converted to markdown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Backend {
+    /// Scrape the HTML generated by `cargo doc`
+    #[default]
+    Html,
+    /// Render the typed rustdoc JSON output (uses nightly when installed)
+    Json,
+}
+
+/// Set of crates documented by `build --all`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BuildScope {
+    /// Workspace members and their direct regular dependencies
+    #[default]
+    Workspace,
+    /// Direct regular dependencies of the workspace members only
+    DirectOnly,
+    /// Workspace members and every regular dependency in their graph
+    Transitive,
+}
+
+/// A crate selected for documentation by `build --all`.
+#[derive(Debug, PartialEq)]
+struct DocTarget {
+    /// Package spec passed to cargo (`name` or `name@version`)
+    spec: String,
+    /// The package name from Cargo.toml
+    crate_name: String,
+    /// The library name rustdoc uses for the output directory
+    lib_name: String,
+    /// Whether the package comes from a local path
+    local: bool,
+    /// Whether the documentation is stored under `<lib_name>@<version>`,
+    /// because the package is not the version selected for its name alone
+    versioned: bool,
+    /// Whether other versions of the package are in the dependency graph,
+    /// so rustdoc writes them to the same directory
+    shared_doc_dir: bool,
+    /// Inputs the documentation is generated from
+    fingerprint: BuildFingerprint,
+}
+
+impl DocTarget {
+    /// Name of the directory of the documentation under `docmd`.
+    fn dir_name(&self) -> String {
+        doc_dir_name(
+            &self.lib_name,
+            self.versioned.then_some(self.fingerprint.version.as_str()),
+        )
+    }
+}
+
+/// Number of documented items of a crate.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct ItemCounts {
+    /// Number of items, as listed in `item_map`
+    item_count: usize,
+    /// Number of members of items, as listed in `member_map`
+    member_count: usize,
+    /// Number of re-exported paths, as listed in `alias_map`
+    alias_count: usize,
+    /// Number of items of each kind
+    kinds: BTreeMap<ItemKind, usize>,
+}
+
+impl ItemCounts {
+    /// Count the items recorded in the metadata of a crate.
+    fn of(metadata: &CrateDocMetadata) -> ItemCounts {
+        let mut kinds = BTreeMap::new();
+        for file in metadata.item_map.values() {
+            if let Some(kind) = ItemKind::from_file(file) {
+                *kinds.entry(kind).or_default() += 1;
+            }
+        }
+        ItemCounts {
+            item_count: metadata.item_map.len(),
+            member_count: metadata.member_map.len(),
+            alias_count: metadata.alias_map.len(),
+            kinds,
+        }
+    }
+}
+
+/// Result of building the documentation of a single crate.
+#[derive(Debug, PartialEq)]
+enum BuildOutcome {
+    /// The documentation was generated, with the warnings rustdoc emitted
+    Built(ItemCounts, Vec<String>),
+    /// The existing documentation was up to date
+    UpToDate(ItemCounts),
+}
+
+/// Build results printed by `build --format json`.
+#[derive(Debug, Serialize)]
+struct BuildReport {
+    /// One report per crate, in build order
+    crates: Vec<CrateReport>,
+}
+
+/// Build result of a single crate.
+#[derive(Debug, Serialize)]
+struct CrateReport {
+    /// The package name from Cargo.toml
+    crate_name: String,
+    /// The library name, which is the directory under `docmd`
+    lib_name: String,
+    /// The crate version
+    version: String,
+    /// One of "built", "up-to-date" or "failed"
+    status: &'static str,
+    /// Item counts, unless the build failed
+    #[serde(flatten)]
+    counts: Option<ItemCounts>,
+    /// Warnings rustdoc emitted while generating the documentation
+    warnings: Vec<String>,
+    /// The error of a failed build
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl CrateReport {
+    /// Create the report of a crate from its build result.
+    fn new(crate_name: &str, lib_name: &str, version: &str, result: &Result<BuildOutcome>) -> Self {
+        let (status, counts, warnings, error) = match result {
+            Ok(BuildOutcome::Built(counts, warnings)) => {
+                ("built", Some(counts.clone()), warnings.clone(), None)
+            }
+            Ok(BuildOutcome::UpToDate(counts)) => {
+                ("up-to-date", Some(counts.clone()), Vec::new(), None)
+            }
+            Err(e) => ("failed", None, Vec::new(), Some(format!("{:#}", e))),
+        };
+        CrateReport {
+            crate_name: crate_name.to_string(),
+            lib_name: lib_name.to_string(),
+            version: version.to_string(),
+            status,
+            counts,
+            warnings,
+            error,
+        }
+    }
+}
+
+/// Standard library crates documented by the `rust-docs` rustup component.
+const RUST_DOCS_CRATES: [&str; 5] = ["std", "core", "alloc", "proc_macro", "test"];
+
+/// Check whether `crate_name` refers to a standard library crate of
+/// `RUST_DOCS_CRATES`.
+///
+/// Packages of the dependency graph take precedence, since `test`, `core`
+/// and `alloc` are valid package names.
+fn is_rust_docs_crate(crate_name: &str, cargo_metadata: &cargo::Metadata) -> bool {
+    RUST_DOCS_CRATES.contains(&crate_name)
+        && !cargo_metadata.packages.iter().any(|p| p.name == crate_name)
 }
 
-/// Build markdown documentation from rustdoc HTML.
+/// Build markdown documentation from rustdoc output.
 ///
-/// This function takes a crate name, generates HTML documentation using cargo doc,
-/// converts the generated HTML to markdown, and writes the result to the output directory.
-pub fn build(crate_name: &str) -> Result<()> {
+/// This function takes a crate name, generates documentation using cargo doc
+/// (or cargo rustdoc for the JSON backend), converts it to markdown, and
+/// writes the result to the output directory. The standard library crates of
+/// `RUST_DOCS_CRATES` that no package of the dependency graph is named after
+/// are converted from the HTML of the `rust-docs` rustup component instead,
+/// without running cargo doc. With `OutputFormat::Json`, a report with the
+/// item counts and rustdoc warnings is printed to stdout.
+pub fn build(
+    crate_name: &str,
+    package: Option<&str>,
+    backend: Backend,
+    skipped_sections: &[Section],
+    doc_options: &cargo::DocOptions,
+    format: OutputFormat,
+    out: &mut dyn Write,
+) -> Result<()> {
     debug!("Building documentation for crate: {}", crate_name);
 
-    let cargo_metadata = cargo::metadata()?;
+    let cargo_metadata = cargo::metadata_with_deps()?;
 
     debug!("Target directory: {}", cargo_metadata.target_directory);
 
@@ -69,40 +357,828 @@ pub fn build(crate_name: &str) -> Result<()> {
     // dependency list only contains the base name.
     let base_crate_name = crate_name.split('@').next().unwrap_or(crate_name);
 
-    validate_crate_name(base_crate_name, &cargo_metadata)?;
+    let rust_docs = is_rust_docs_crate(crate_name, &cargo_metadata);
+    if rust_docs {
+        ensure!(
+            backend == Backend::Html,
+            "the rust-docs component only ships HTML documentation. Build '{}' with `--backend html`.",
+            crate_name
+        );
+        ensure!(
+            !doc_options.selects_features() && doc_options.target.is_none() && !doc_options.docsrs,
+            "the rust-docs component is documented for the host with its default features. Build '{}' without feature, target and docs.rs flags.",
+            crate_name
+        );
+    } else {
+        validate_crate_name(base_crate_name, &cargo_metadata, package)?;
+    }
+
+    ensure!(
+        !doc_options.docsrs || cargo::has_nightly_toolchain(),
+        "docs.rs mode requires a nightly toolchain. Install one with `rustup toolchain install nightly`"
+    );
+
+    let base_fingerprint = base_fingerprint(backend, skipped_sections, doc_options)?;
+    let resolved_package = if rust_docs {
+        None
+    } else {
+        cargo_metadata.select_package(crate_name, package)
+    };
+    let docs_rs_options;
+    let doc_options = match resolved_package {
+        Some(p) if doc_options.docsrs => {
+            docs_rs_options = doc_options.with_docs_rs(p);
+            &docs_rs_options
+        }
+        _ => doc_options,
+    };
+    let spec = match resolved_package {
+        Some(p) => cargo_metadata.package_spec(p),
+        None => crate_name.to_string(),
+    };
+    let versioned =
+        resolved_package.is_some_and(|p| !cargo_metadata.is_default_version(p, package));
+    let fingerprint = match resolved_package {
+        Some(p) if doc_options.selects_features() => BuildFingerprint {
+            features: p.enabled_features(doc_options),
+            ..package_fingerprint(&cargo_metadata, p, &base_fingerprint)
+        },
+        Some(p) => package_fingerprint(&cargo_metadata, p, &base_fingerprint),
+        None if rust_docs => rust_docs_fingerprint(&base_fingerprint),
+        None => base_fingerprint,
+    };
+    let version = fingerprint.version.clone();
+    let lib_name = resolved_package
+        .and_then(|p| p.lib_name())
+        .unwrap_or_else(|| base_crate_name.replace('-', "_"));
+
+    // Cargo only accepts feature flags for workspace members.
+    let dependency_options;
+    let doc_options = match resolved_package {
+        Some(p)
+            if doc_options.selects_features()
+                && !cargo_metadata.workspace_members.contains(&p.id) =>
+        {
+            dependency_options = cargo::DocOptions {
+                feature_manifest: Some(cargo::feature_manifest(&cargo_metadata, p, doc_options)?),
+                ..doc_options.clone()
+            };
+            &dependency_options
+        }
+        _ => doc_options,
+    };
+
+    let docmd_dir = PathBuf::from(&cargo_metadata.target_directory).join("docmd");
+    let dir_name = doc_dir_name(&lib_name, versioned.then_some(version.as_str()));
+    let outcome = match resolved_package {
+        Some(p) if p.lib_name().is_some() => {
+            up_to_date_counts(&docmd_dir, &dir_name, p.is_local(), &fingerprint)
+        }
+        None if rust_docs => up_to_date_counts(&docmd_dir, &dir_name, false, &fingerprint),
+        _ => None,
+    };
+    let outcome = match outcome {
+        Some(counts) => {
+            info!(
+                "Documentation for {} {} is up to date ({} items)",
+                lib_name, version, counts.item_count
+            );
+            info!("Run `cargo txt list {}` to see all items", dir_name);
+            BuildOutcome::UpToDate(counts)
+        }
+        None if rust_docs => {
+            let rust_docs_dir = cargo::rust_docs_dir()?;
+            info!(
+                "Converting {} documentation from {}",
+                crate_name,
+                rust_docs_dir.display()
+            );
+
+            let counts = convert_cargo_doc_output(
+                rust_docs_dir.join(crate_name),
+                crate_name,
+                &docmd_dir,
+                fingerprint,
+                false,
+            )?;
+            BuildOutcome::Built(counts, Vec::new())
+        }
+        None if backend == Backend::Json => build_json(
+            &spec,
+            base_crate_name,
+            &lib_name,
+            &cargo_metadata.target_directory,
+            doc_options,
+            fingerprint,
+            versioned,
+        )?,
+        None => {
+            info!("Running cargo doc --package {} --no-deps", spec);
 
-    info!("Running cargo doc --package {} --no-deps", crate_name);
+            if spec.contains('@') {
+                clear_doc_output(&cargo_metadata.target_directory, doc_options, &lib_name)?;
+            }
+            let (cargo_doc_output_dir, warnings) = cargo::doc(&spec, doc_options)?;
 
-    let cargo_doc_output_dir = cargo::doc(crate_name)?;
+            debug!("Cargo doc output directory: {:?}", cargo_doc_output_dir);
 
-    debug!("Cargo doc output directory: {:?}", cargo_doc_output_dir);
+            let counts = convert_cargo_doc_output(
+                &cargo_doc_output_dir,
+                base_crate_name,
+                &docmd_dir,
+                fingerprint,
+                versioned,
+            )?;
+            BuildOutcome::Built(counts, warning_messages(&warnings, None))
+        }
+    };
 
-    let cargo_doc_output = read_cargo_doc_output(&cargo_doc_output_dir, base_crate_name)?;
-    let doc_output = process_cargo_doc_output(cargo_doc_output)?;
-    save_doc(doc_output)?;
+    if format == OutputFormat::Json {
+        write_json(
+            out,
+            &BuildReport {
+                crates: vec![CrateReport::new(
+                    base_crate_name,
+                    &lib_name,
+                    &version,
+                    &Ok(outcome),
+                )],
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Return the messages of the warnings emitted for `package`, or of every
+/// warning if `package` is `None`.
+fn warning_messages(warnings: &[DocWarning], package: Option<&str>) -> Vec<String> {
+    warnings
+        .iter()
+        .filter(|warning| package.is_none() || warning.package.as_deref() == package)
+        .map(|warning| warning.message.clone())
+        .collect()
+}
+
+/// Build markdown documentation for many crates at once.
+///
+/// The crates are selected from the workspace according to `scope`. With the
+/// HTML backend, `cargo doc` runs once for all of them and each generated
+/// directory is converted to `docmd/<lib_name>`. If the combined run fails,
+/// every crate is built on its own so a single broken crate does not block
+/// the others. Crates whose documentation is up to date are skipped. A
+/// per-crate summary is printed at the end, as a JSON report with
+/// `OutputFormat::Json`.
+pub fn build_all(
+    package: Option<&str>,
+    scope: BuildScope,
+    backend: Backend,
+    skipped_sections: &[Section],
+    doc_options: &cargo::DocOptions,
+    format: OutputFormat,
+    out: &mut dyn Write,
+) -> Result<()> {
+    debug!("Building documentation for all crates (scope: {:?})", scope);
+
+    let cargo_metadata = cargo::metadata_with_deps()?;
+    let base_fingerprint = base_fingerprint(backend, skipped_sections, doc_options)?;
+    let targets = select_doc_targets(&cargo_metadata, package, scope, &base_fingerprint)?;
+    ensure!(
+        !targets.is_empty(),
+        "no crates with a library target found to document"
+    );
+
+    let docmd_dir = PathBuf::from(&cargo_metadata.target_directory).join("docmd");
+    let mut results: Vec<(&DocTarget, Result<BuildOutcome>)> = Vec::new();
+    let mut stale: Vec<&DocTarget> = Vec::new();
+    for target in &targets {
+        match up_to_date_counts(
+            &docmd_dir,
+            &target.dir_name(),
+            target.local,
+            &target.fingerprint,
+        ) {
+            Some(counts) => results.push((target, Ok(BuildOutcome::UpToDate(counts)))),
+            None => stale.push(target),
+        }
+    }
+
+    if !stale.is_empty() {
+        match backend {
+            Backend::Json => {
+                for target in stale {
+                    let result = build_json(
+                        &target.spec,
+                        &target.crate_name,
+                        &target.lib_name,
+                        &cargo_metadata.target_directory,
+                        doc_options,
+                        target.fingerprint.clone(),
+                        target.versioned,
+                    );
+                    results.push((target, result));
+                }
+            }
+            Backend::Html => results.extend(build_all_html(
+                &stale,
+                &cargo_metadata.target_directory,
+                doc_options,
+            )),
+        }
+    }
+    results.sort_by_key(|(target, _)| targets.iter().position(|t| t.spec == target.spec));
+
+    info!("Summary:");
+    let mut failures = 0;
+    for (target, result) in &results {
+        match result {
+            Ok(BuildOutcome::Built(counts, _)) => {
+                info!("  {}: {} items", target.dir_name(), counts.item_count)
+            }
+            Ok(BuildOutcome::UpToDate(counts)) => {
+                info!(
+                    "  {}: {} items (up to date)",
+                    target.dir_name(),
+                    counts.item_count
+                )
+            }
+            Err(e) => {
+                failures += 1;
+                let message = e.to_string();
+                info!(
+                    "  {}: failed: {}",
+                    target.dir_name(),
+                    message.lines().next().unwrap_or_default()
+                );
+                debug!("Failed to build '{}': {:#}", target.spec, e);
+            }
+        }
+    }
+    info!(
+        "Built documentation for {} of {} crates",
+        results.len() - failures,
+        results.len()
+    );
+
+    if format == OutputFormat::Json {
+        let crates = results
+            .iter()
+            .map(|(target, result)| {
+                CrateReport::new(
+                    &target.crate_name,
+                    &target.lib_name,
+                    &target.fingerprint.version,
+                    result,
+                )
+            })
+            .collect();
+        write_json(out, &BuildReport { crates })?;
+    }
+
+    ensure!(
+        failures < results.len(),
+        "failed to build documentation for every selected crate"
+    );
+
+    Ok(())
+}
+
+/// Run `cargo doc` for every target and convert the generated output.
+///
+/// rustdoc writes every crate to `doc/<lib_name>`, so a single `cargo doc`
+/// run documents one crate per library name, preferring the default
+/// versions. The other versions are documented by one run each, converted
+/// before the next run overwrites them.
+fn build_all_html<'a>(
+    targets: &[&'a DocTarget],
+    target_directory: &str,
+    doc_options: &cargo::DocOptions,
+) -> Vec<(&'a DocTarget, Result<BuildOutcome>)> {
+    let (combined, separate) = split_doc_runs(targets);
+
+    for target in targets.iter().filter(|t| t.shared_doc_dir) {
+        if let Err(e) = clear_doc_output(target_directory, doc_options, &target.lib_name) {
+            debug!("{:#}", e);
+        }
+    }
+    let mut results = build_combined_html(&combined, target_directory, doc_options);
+    for target in separate {
+        info!("Running cargo doc --package {} --no-deps", target.spec);
+        results.push((target, build_html(target, target_directory, doc_options)));
+    }
+    results
+}
+
+/// Split targets into the crates of a combined `cargo doc` run, with
+/// distinct library names, and the crates documented on their own.
+fn split_doc_runs<'a>(targets: &[&'a DocTarget]) -> (Vec<&'a DocTarget>, Vec<&'a DocTarget>) {
+    let defaults_first = targets
+        .iter()
+        .filter(|t| !t.versioned)
+        .chain(targets.iter().filter(|t| t.versioned));
+
+    let mut combined: Vec<&DocTarget> = Vec::new();
+    let mut separate = Vec::new();
+    for target in defaults_first {
+        if combined.iter().any(|t| t.lib_name == target.lib_name) {
+            separate.push(*target);
+        } else {
+            combined.push(*target);
+        }
+    }
+    (combined, separate)
+}
+
+/// Run `cargo doc` once for targets with distinct library names.
+///
+/// Falls back to one `cargo doc` invocation per crate when the combined
+/// invocation fails.
+fn build_combined_html<'a>(
+    targets: &[&'a DocTarget],
+    target_directory: &str,
+    doc_options: &cargo::DocOptions,
+) -> Vec<(&'a DocTarget, Result<BuildOutcome>)> {
+    if targets.is_empty() {
+        return Vec::new();
+    }
+    let specs: Vec<String> = targets.iter().map(|t| t.spec.clone()).collect();
+
+    info!("Running cargo doc --no-deps for {} crates", specs.len());
+
+    let docmd_dir = PathBuf::from(target_directory).join("docmd");
+    match cargo::doc_packages(&specs, doc_options) {
+        Ok(warnings) => {
+            let doc_dir = cargo::doc_directory(target_directory, doc_options);
+            targets
+                .iter()
+                .map(|target| {
+                    let result = convert_cargo_doc_output(
+                        doc_dir.join(&target.lib_name),
+                        &target.crate_name,
+                        &docmd_dir,
+                        target.fingerprint.clone(),
+                        target.versioned,
+                    )
+                    .map(|counts| {
+                        let warnings = warning_messages(&warnings, Some(&target.crate_name));
+                        BuildOutcome::Built(counts, warnings)
+                    });
+                    (*target, result)
+                })
+                .collect()
+        }
+        Err(e) => {
+            info!("Combined cargo doc run failed, building crates one by one");
+            debug!("Combined cargo doc error: {:#}", e);
+            targets
+                .iter()
+                .map(|target| {
+                    info!("Running cargo doc --package {} --no-deps", target.spec);
+                    (*target, build_html(target, target_directory, doc_options))
+                })
+                .collect()
+        }
+    }
+}
+
+/// Run `cargo doc` for a single target and convert the generated output.
+fn build_html(
+    target: &DocTarget,
+    target_directory: &str,
+    doc_options: &cargo::DocOptions,
+) -> Result<BuildOutcome> {
+    let docmd_dir = PathBuf::from(target_directory).join("docmd");
+    if target.shared_doc_dir {
+        clear_doc_output(target_directory, doc_options, &target.lib_name)?;
+    }
+    let (dir, warnings) = cargo::doc(&target.spec, doc_options)?;
+    let counts = convert_cargo_doc_output(
+        &dir,
+        &target.crate_name,
+        &docmd_dir,
+        target.fingerprint.clone(),
+        target.versioned,
+    )?;
+    Ok(BuildOutcome::Built(
+        counts,
+        warning_messages(&warnings, None),
+    ))
+}
 
+/// Remove the rustdoc output of a library before documenting it again.
+///
+/// Versions of a package share `doc/<lib_name>`, and cargo does not run
+/// rustdoc again for a version whose documentation it considers fresh,
+/// even when another version overwrote it since.
+fn clear_doc_output(
+    target_directory: &str,
+    doc_options: &cargo::DocOptions,
+    lib_name: &str,
+) -> Result<()> {
+    let dir = cargo::doc_directory(target_directory, doc_options).join(lib_name);
+    if dir.exists() {
+        debug!("Removing previous rustdoc output {:?}", dir);
+        fs::remove_dir_all(&dir)
+            .with_context(|| format!("failed to remove directory '{}'", dir.display()))?;
+    }
     Ok(())
 }
 
-/// Validate that a crate name exists in the project dependencies.
+/// Select the crates documented by `build --all`.
+///
+/// Packages without a library target are skipped. When several versions of
+/// the same package are selected, `name@version` specs are used so cargo can
+/// tell them apart. Every version is kept: the one the workspace depends on
+/// directly is stored under `docmd/<lib_name>`, the others under
+/// `docmd/<lib_name>@<version>`. When several packages would share a
+/// directory, only the first one is kept.
+fn select_doc_targets(
+    cargo_metadata: &cargo::Metadata,
+    package: Option<&str>,
+    scope: BuildScope,
+    base_fingerprint: &BuildFingerprint,
+) -> Result<Vec<DocTarget>> {
+    let mut packages = match scope {
+        BuildScope::DirectOnly => Vec::new(),
+        BuildScope::Workspace | BuildScope::Transitive => {
+            cargo_metadata.workspace_packages(package)?
+        }
+    };
+    packages
+        .extend(cargo_metadata.resolved_dependencies(package, scope == BuildScope::Transitive)?);
+
+    let mut name_counts: HashMap<&str, usize> = HashMap::new();
+    for p in &packages {
+        *name_counts.entry(p.name.as_str()).or_default() += 1;
+    }
+
+    let mut targets: Vec<DocTarget> = Vec::new();
+    for p in packages {
+        let Some(lib_name) = p.lib_name() else {
+            debug!("Skipping '{}': no library target", p.name);
+            continue;
+        };
+        let spec = if name_counts[p.name.as_str()] > 1 {
+            format!("{}@{}", p.name, p.version)
+        } else {
+            p.name.clone()
+        };
+        let target = DocTarget {
+            spec,
+            crate_name: p.name.clone(),
+            lib_name,
+            local: p.is_local(),
+            versioned: !cargo_metadata.is_default_version(p, package),
+            shared_doc_dir: cargo_metadata.package_spec(p).contains('@'),
+            fingerprint: package_fingerprint(cargo_metadata, p, base_fingerprint),
+        };
+        if targets.iter().any(|t| t.dir_name() == target.dir_name()) {
+            debug!(
+                "Skipping '{}@{}': directory '{}' is already selected",
+                p.name,
+                p.version,
+                target.dir_name()
+            );
+            continue;
+        }
+        targets.push(target);
+    }
+
+    Ok(targets)
+}
+
+/// Build the fingerprint fields shared by every crate of a build.
+fn base_fingerprint(
+    backend: Backend,
+    skipped_sections: &[Section],
+    doc_options: &cargo::DocOptions,
+) -> Result<BuildFingerprint> {
+    let nightly =
+        doc_options.docsrs || (backend == Backend::Json && cargo::has_nightly_toolchain());
+
+    let mut skipped_sections = skipped_sections.to_vec();
+    skipped_sections.sort();
+    skipped_sections.dedup();
+
+    Ok(BuildFingerprint {
+        rustc_version: cargo::rustc_version(nightly)?,
+        generator: env!("CARGO_PKG_VERSION").to_string(),
+        backend,
+        document_private_items: doc_options.document_private_items,
+        target: doc_options.target.clone(),
+        docsrs: doc_options.docsrs,
+        skipped_sections,
+        ..Default::default()
+    })
+}
+
+/// Complete a base fingerprint with the version and features of a package.
+fn package_fingerprint(
+    cargo_metadata: &cargo::Metadata,
+    package: &cargo::Package,
+    base_fingerprint: &BuildFingerprint,
+) -> BuildFingerprint {
+    BuildFingerprint {
+        version: package.version.clone(),
+        features: cargo_metadata.resolved_features(&package.id),
+        ..base_fingerprint.clone()
+    }
+}
+
+/// Complete a base fingerprint for a standard library crate.
+///
+/// The documentation of the `rust-docs` component changes with the toolchain,
+/// so its version is the version of `rustc` (e.g., "1.95.0").
+fn rust_docs_fingerprint(base_fingerprint: &BuildFingerprint) -> BuildFingerprint {
+    let version = base_fingerprint
+        .rustc_version
+        .split_whitespace()
+        .nth(1)
+        .unwrap_or_default();
+    BuildFingerprint {
+        version: version.to_string(),
+        ..base_fingerprint.clone()
+    }
+}
+
+/// Return the item counts of up-to-date documentation, if any.
+///
+/// The documentation under `<docmd_dir>/<dir_name>` is up to date when its
+/// metadata has the current schema version and its recorded fingerprint
+/// matches `fingerprint`. Local packages, such as workspace members, are
+/// never considered up to date because their sources can change without a
+/// version bump.
+fn up_to_date_counts(
+    docmd_dir: &Path,
+    dir_name: &str,
+    local: bool,
+    fingerprint: &BuildFingerprint,
+) -> Option<ItemCounts> {
+    if local {
+        return None;
+    }
+
+    let previous = read_previous_metadata(&docmd_dir.join(dir_name))?;
+    if previous.schema_version != SCHEMA_VERSION {
+        debug!(
+            "Documentation for '{}' has metadata schema version {}, expected {}",
+            dir_name, previous.schema_version, SCHEMA_VERSION
+        );
+        return None;
+    }
+    if previous.fingerprint != *fingerprint {
+        debug!(
+            "Documentation for '{}' is outdated: {:?} != {:?}",
+            dir_name, previous.fingerprint, fingerprint
+        );
+        return None;
+    }
+
+    Some(ItemCounts::of(&previous))
+}
+
+/// Return the name of the directory of a library under `docmd`.
+///
+/// The version selected for a crate name alone is stored under the library
+/// name, other versions under `<lib_name>@<version>` so that they do not
+/// overwrite it.
+pub fn doc_dir_name(lib_name: &str, version: Option<&str>) -> String {
+    match version {
+        Some(version) => format!("{}@{}", lib_name, version),
+        None => lib_name.to_string(),
+    }
+}
+
+/// Find the documentation directory of a library under `docmd_dir`.
+///
+/// `version` is a full or partial version, such as "1" for `syn@1`. The
+/// library name directory is used when its recorded version matches, or
+/// when no version is requested. Otherwise the newest matching
+/// `<lib_name>@<version>` directory is used. Returns `None` when no
+/// matching documentation is built.
+pub fn find_doc_dir(docmd_dir: &Path, lib_name: &str, version: Option<&str>) -> Option<PathBuf> {
+    let default_dir = docmd_dir.join(lib_name);
+    let default_matches = match version {
+        None => default_dir.join("metadata.json").exists(),
+        Some(version) => read_previous_metadata(&default_dir)
+            .is_some_and(|metadata| cargo::version_matches(&metadata.fingerprint.version, version)),
+    };
+    if default_matches {
+        return Some(default_dir);
+    }
+
+    let prefix = format!("{}@", lib_name);
+    let entries = fs::read_dir(docmd_dir).ok()?;
+    entries
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let name = entry.file_name().into_string().ok()?;
+            let dir_version = name.strip_prefix(&prefix)?.to_string();
+            let matches = version.is_none_or(|v| cargo::version_matches(&dir_version, v));
+            (matches && entry.path().join("metadata.json").exists())
+                .then(|| (dir_version, entry.path()))
+        })
+        .max_by_key(|(dir_version, _)| cargo::version_key(dir_version))
+        .map(|(_, path)| path)
+}
+
+/// Read the metadata.json of previously generated documentation.
+///
+/// Returns `None` if the file is missing or cannot be parsed.
+fn read_previous_metadata(output_dir: &Path) -> Option<CrateDocMetadata> {
+    let metadata_path = output_dir.join("metadata.json");
+    let content = fs::read_to_string(&metadata_path).ok()?;
+
+    match serde_json::from_str(&content) {
+        Ok(metadata) => Some(metadata),
+        Err(e) => {
+            debug!(
+                "Ignoring unreadable metadata '{}': {}",
+                metadata_path.display(),
+                e
+            );
+            None
+        }
+    }
+}
+
+/// Read the `trait.impl` script of a trait page, if rustdoc generated one.
+///
+/// The scripts live next to the crate directories, e.g.
+/// `doc/trait.impl/serde_core/ser/trait.Serialize.js` for
+/// `doc/serde_core/ser/trait.Serialize.html`.
+fn read_trait_implementors(
+    cargo_doc_dir: &Path,
+    lib_name: &str,
+    html_path: &str,
+) -> Option<String> {
+    let file_name = Path::new(html_path).file_name()?.to_str()?;
+    if !file_name.starts_with("trait.") {
+        return None;
+    }
+    let script_path = cargo_doc_dir
+        .parent()?
+        .join("trait.impl")
+        .join(lib_name)
+        .join(Path::new(html_path).with_extension("js"));
+    fs::read_to_string(script_path).ok()
+}
+
+/// Compute a stable content hash (64-bit FNV-1a) as a hex string.
+///
+/// The hash only needs to detect changed pages between two builds, so a
+/// simple non-cryptographic hash that is stable across releases is enough.
+fn content_hash(content: &str) -> String {
+    let hash = content
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+        });
+    format!("{:016x}", hash)
+}
+
+/// Compute a content hash of the crate-wide inputs the links of item pages
+/// depend on.
+///
+/// Links to other crates resolve to local pages when those crates are
+/// documented in the same doc directory, the parent of `cargo_doc_dir`, and
+/// item paths resolve through the alias and member maps. Unlike the HTML of
+/// a page, these change when other pages or crates are documented.
+fn link_hash(
+    cargo_doc_dir: &Path,
+    alias_map: &HashMap<String, String>,
+    member_map: &HashMap<String, String>,
+) -> String {
+    let mut crates: Vec<String> = cargo_doc_dir
+        .parent()
+        .and_then(|doc_dir| fs::read_dir(doc_dir).ok())
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            entry
+                .path()
+                .join("index.html")
+                .exists()
+                .then(|| entry.file_name().to_string_lossy().to_string())
+        })
+        .collect();
+    crates.sort();
+
+    let mut entries: Vec<String> = alias_map
+        .iter()
+        .chain(member_map)
+        .map(|(path, target)| format!("{} {}", path, target))
+        .collect();
+    entries.sort();
+
+    content_hash(&format!("{}\n\n{}", crates.join("\n"), entries.join("\n")))
+}
+
+/// Generate rustdoc JSON for a crate and save it as markdown.
+///
+/// With `versioned`, the markdown is saved under `<lib_name>@<version>`.
+fn build_json(
+    spec: &str,
+    crate_name: &str,
+    lib_name: &str,
+    target_directory: &str,
+    doc_options: &cargo::DocOptions,
+    fingerprint: BuildFingerprint,
+    versioned: bool,
+) -> Result<BuildOutcome> {
+    info!("Running cargo rustdoc --package {} (JSON output)", spec);
+
+    let (json_path, warnings) = cargo::rustdoc_json(spec, lib_name, target_directory, doc_options)?;
+
+    debug!("Rustdoc JSON output: {:?}", json_path);
+
+    let output_dir = PathBuf::from(target_directory).join("docmd");
+    let doc_output =
+        process_rustdoc_json(&json_path, crate_name, &output_dir, fingerprint, versioned)?;
+    let counts = save_doc(doc_output)?;
+    Ok(BuildOutcome::Built(
+        counts,
+        warning_messages(&warnings, None),
+    ))
+}
+
+/// Convert a cargo doc output directory to markdown and save it under
+/// `docmd_dir`, in a `<lib_name>@<version>` directory with `versioned`.
+///
+/// Returns the counts of documented items.
+fn convert_cargo_doc_output(
+    cargo_doc_output_dir: impl AsRef<Path>,
+    crate_name: &str,
+    docmd_dir: &Path,
+    fingerprint: BuildFingerprint,
+    versioned: bool,
+) -> Result<ItemCounts> {
+    let version = versioned.then_some(fingerprint.version.as_str());
+    let mut cargo_doc_output =
+        read_cargo_doc_output(cargo_doc_output_dir, crate_name, docmd_dir, version)?;
+    cargo_doc_output.metadata.fingerprint = fingerprint;
+    let doc_output = process_cargo_doc_output(cargo_doc_output)?;
+    save_doc(doc_output)
+}
+
+/// Validate that a crate name is a workspace member or a project dependency.
 ///
-/// Returns an error if the crate name is not found in the list of
-/// available dependencies from cargo metadata.
-fn validate_crate_name(crate_name: &str, cargo_metadata: &cargo::Metadata) -> Result<()> {
+/// Workspace members are always accepted. Dependencies of every workspace
+/// member are considered, unless `package` restricts the lookup to a single
+/// member. Transitive dependencies are accepted when the metadata contains
+/// the resolved dependency graph, and the chain that brings them in is
+/// reported. Returns an error if the crate name is neither a workspace member
+/// nor a regular direct or transitive dependency of the selected members.
+fn validate_crate_name(
+    crate_name: &str,
+    cargo_metadata: &cargo::Metadata,
+    package: Option<&str>,
+) -> Result<()> {
     let base_crate_name = crate_name.split('@').next().unwrap_or(crate_name);
-    let dependencies = &cargo_metadata.packages[0].dependencies;
-    let dependency = dependencies.iter().find(|dep| dep.name == base_crate_name);
 
-    match dependency {
-        Some(dep) => {
+    let is_member = cargo_metadata
+        .workspace_packages(None)?
+        .iter()
+        .any(|member| member.name == base_crate_name);
+    if is_member {
+        debug!("Crate '{}' is a workspace member", base_crate_name);
+        return Ok(());
+    }
+
+    let members = cargo_metadata.workspace_packages(package)?;
+    let matches: Vec<(&cargo::Package, &cargo::Dependency)> = members
+        .iter()
+        .flat_map(|member| member.dependencies.iter().map(move |dep| (*member, dep)))
+        .filter(|(_, dep)| dep.name == base_crate_name)
+        .collect();
+
+    if let Some((member, dep)) = matches.iter().find(|(_, dep)| dep.kind.is_none()) {
+        debug!(
+            "Found dependency '{}' of '{}' with kind: {:?}",
+            base_crate_name, member.name, dep.kind
+        );
+        return Ok(());
+    }
+
+    if let Some(chain) = cargo_metadata.dependency_chain(base_crate_name, package)? {
+        info!(
+            "Crate '{}' is a transitive dependency: {}",
+            base_crate_name,
+            cargo::format_dependency_chain(&chain)
+        );
+        return Ok(());
+    }
+
+    match matches.first() {
+        Some((member, dep)) => {
             debug!(
-                "Found dependency '{}' with kind: {:?}",
-                base_crate_name, dep.kind
+                "Found dependency '{}' of '{}' with kind: {:?}",
+                base_crate_name, member.name, dep.kind
             );
-            ensure!(
-                dep.kind.is_none(),
+            bail!(
                 concat!(
-                    "Crate '{}' is a {} dependency.\n",
+                    "Crate '{}' is a {} dependency of '{}'.\n",
                     "\n",
                     "Build and dev dependencies cannot be built directly because they are not part of ",
                     "the regular dependency graph and cargo does not activate them for ",
@@ -113,24 +1189,30 @@ fn validate_crate_name(crate_name: &str, cargo_metadata: &cargo::Metadata) -> Re
                     "2. Use `cargo doc` in a temporary project with the crate as a regular dependency\n"
                 ),
                 base_crate_name,
-                dep.kind.as_deref().unwrap_or("unknown")
+                dep.kind.as_deref().unwrap_or("unknown"),
+                member.name
             );
-            Ok(())
         }
         None => {
-            let available_crates: Vec<&str> =
-                dependencies.iter().map(|dep| dep.name.as_str()).collect();
+            let owners = cargo_metadata.dependency_owners(package)?;
             bail!(
                 concat!(
                     "Crate '{}' is not an installed dependency.\n",
                     "\n",
                     "Available crates: {}\n",
+                    "Workspace members: {}\n",
                     "\n",
-                    "Only installed dependencies can be built. ",
+                    "Only workspace members and crates in their dependency graph can be built. ",
                     "Add the crate to Cargo.toml as a dependency first."
                 ),
                 base_crate_name,
-                available_crates.join(", ")
+                cargo::format_dependency_owners(&owners),
+                cargo_metadata
+                    .workspace_packages(None)?
+                    .iter()
+                    .map(|member| member.name.as_str())
+                    .collect::<Vec<&str>>()
+                    .join(", ")
             )
         }
     }
@@ -139,10 +1221,14 @@ fn validate_crate_name(crate_name: &str, cargo_metadata: &cargo::Metadata) -> Re
 /// Read cargo doc output directory and extract all HTML files and metadata.
 ///
 /// This function reads all HTML files from the cargo doc output directory
-/// and builds metadata by parsing the all.html file.
+/// and builds metadata by parsing the all.html file. The markdown is written
+/// to the library name directory of `docmd_dir`, or to
+/// `<lib_name>@<version>` for a `version` other than the default one.
 fn read_cargo_doc_output(
     cargo_doc_output_dir: impl AsRef<Path>,
     crate_name: &str,
+    docmd_dir: &Path,
+    version: Option<&str>,
 ) -> Result<CargoDocOutput> {
     let cargo_doc_output_dir = cargo_doc_output_dir.as_ref();
 
@@ -188,13 +1274,21 @@ fn read_cargo_doc_output(
         .unwrap_or(crate_name);
 
     let metadata = CrateDocMetadata {
+        schema_version: SCHEMA_VERSION,
         crate_name: crate_name.to_string(),
         lib_name: lib_name.to_string(),
         item_map,
+        fingerprint: BuildFingerprint::default(),
+        page_hashes: HashMap::new(),
+        link_hash: String::new(),
+        member_map: HashMap::new(),
+        alias_map: HashMap::new(),
+        items: HashMap::new(),
     };
 
     Ok(CargoDocOutput {
         path: cargo_doc_output_dir.to_path_buf(),
+        output_path: docmd_dir.join(doc_dir_name(lib_name, version)),
         files,
         metadata,
     })
@@ -238,7 +1332,13 @@ fn extract_item_mappings_from_html(html: &str) -> Result<HashMap<String, String>
 /// Process cargo doc output and convert to markdown.
 ///
 /// Transforms HTML files to markdown format and builds the output
-/// structure ready to be saved.
+/// structure ready to be saved. Item pages are converted in parallel and
+/// written to the output directory as soon as they are ready, so only the
+/// crate overview, the list of all items and the metadata are left for
+/// `save_doc`. Item pages whose HTML is unchanged since the previous
+/// conversion, according to the recorded page hashes, are not converted again,
+/// unless the crates and paths their links resolve against changed, according
+/// to the recorded link hash.
 fn process_cargo_doc_output(cargo_doc_output: CargoDocOutput) -> Result<DocOutput> {
     debug!("Processing cargo doc output");
 
@@ -246,80 +1346,471 @@ fn process_cargo_doc_output(cargo_doc_output: CargoDocOutput) -> Result<DocOutpu
     let lib_name = &cargo_doc_output.metadata.lib_name;
     let item_map = &cargo_doc_output.metadata.item_map;
 
+    let output_path = cargo_doc_output.output_path.clone();
+
+    // Page hashes are only reusable when the pages were converted by the
+    // same version of cargo-txt from HTML, with the same sections.
+    let fingerprint = &cargo_doc_output.metadata.fingerprint;
+    let (previous_hashes, previous_items, previous_link_hash) =
+        read_previous_metadata(&output_path)
+            .filter(|previous| {
+                previous.fingerprint.backend == Backend::Html
+                    && previous.fingerprint.generator == fingerprint.generator
+                    && previous.fingerprint.skipped_sections == fingerprint.skipped_sections
+            })
+            .map(|previous| (previous.page_hashes, previous.items, previous.link_hash))
+            .unwrap_or_default();
+
     let index_html_key = "index.html";
     let Some(index_html_content) = cargo_doc_output.files.get(index_html_key) else {
         bail!("index.html not found in cargo doc output files");
     };
-    let index_markdown = html2md::convert(index_html_content)?;
-    files.insert("index.md".to_string(), index_markdown);
+    let index_page = html2md::DocPage {
+        lib_name,
+        path: index_html_key,
+        skipped_sections: &fingerprint.skipped_sections,
+        implementors: None,
+    };
+    let index_markdown = html2md::convert_page(index_html_content, &index_page)?;
+    files.insert(
+        "index.md".to_string(),
+        format_index_md(&index_markdown, fingerprint),
+    );
     debug!("Converted index.html to index.md");
 
+    let items: Vec<(&String, &String)> = item_map.iter().collect();
+    let started = Instant::now();
+    let pages = convert_item_pages(
+        &items,
+        lib_name,
+        &fingerprint.skipped_sections,
+        &cargo_doc_output.path,
+        &output_path,
+        (&previous_hashes, &previous_items),
+    )?;
+    let elapsed = started.elapsed();
+
+    let mut updated_item_map = HashMap::new();
+    let mut page_hashes = HashMap::new();
+    let mut member_map = HashMap::new();
+    let mut item_infos = HashMap::new();
+    let mut converted = 0;
+    let mut unchanged = Vec::new();
+
+    for ((item_name, html_path), page) in items.into_iter().zip(pages) {
+        if page.converted {
+            converted += 1;
+        } else {
+            unchanged.push((item_name, html_path));
+        }
+        let item_path = format!("{}::{}", lib_name, item_name);
+        insert_members(&mut member_map, &item_path, page.members);
+        page_hashes.insert(html_path.clone(), page.hash);
+        item_infos.insert(item_path.clone(), page.info);
+        updated_item_map.insert(item_path, page.md_path);
+    }
+
+    let reexports = read_module_reexports(&cargo_doc_output.path, lib_name)?;
+    let alias_map = reexports::alias_map(updated_item_map.keys(), &reexports);
+    debug!(
+        "Resolved {} aliases from {} re-exports",
+        alias_map.len(),
+        reexports.len()
+    );
+
+    // Unchanged pages still link to the crates and paths of the previous
+    // conversion, so they are converted again when those changed.
+    let link_hash = link_hash(&cargo_doc_output.path, &alias_map, &member_map);
+    if link_hash != previous_link_hash && !unchanged.is_empty() {
+        debug!(
+            "Link context changed, converting {} unchanged pages again",
+            unchanged.len()
+        );
+        convert_item_pages(
+            &unchanged,
+            lib_name,
+            &fingerprint.skipped_sections,
+            &cargo_doc_output.path,
+            &output_path,
+            (&HashMap::new(), &HashMap::new()),
+        )?;
+        converted += unchanged.len();
+    }
+
+    // all.md lists the summaries of the items, known once they are converted.
     let all_html_key = "all.html";
     let Some(all_html_content) = cargo_doc_output.files.get(all_html_key) else {
         bail!("all.html not found in cargo doc output files");
     };
     let all_markdown_raw = html2md::convert(all_html_content)?;
-    let all_markdown_formatted = format_all_md(lib_name, &all_markdown_raw);
+    let dir_name = output_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(lib_name);
+    let all_markdown_formatted = format_all_md(lib_name, dir_name, &all_markdown_raw, &item_infos);
     files.insert("all.md".to_string(), all_markdown_formatted);
     debug!("Converted all.html to all.md");
 
-    let mut updated_item_map = HashMap::new();
-
-    for (item_name, html_path) in item_map {
-        let full_item_path = format!("{}::{}", lib_name, item_name);
-        debug!("Converting item: {}", full_item_path);
-
-        let full_html_path = cargo_doc_output.path.join(html_path);
-        let html_content = fs::read_to_string(&full_html_path)
-            .with_context(|| format!("failed to read HTML file '{}'", full_html_path.display()))?;
-
-        let markdown_content = html2md::convert(&html_content)?;
-
-        let md_path = PathBuf::from(html_path).with_extension("md");
-        let md_key = md_path.to_string_lossy().to_string();
-
-        files.insert(md_key, markdown_content);
-        updated_item_map.insert(full_item_path, md_path.to_string_lossy().to_string());
-    }
-
-    info!("Converted {} items to markdown", files.len() - 2);
+    info!(
+        "Converted {} items to markdown ({} unchanged)",
+        converted,
+        updated_item_map.len() - converted
+    );
+    trace!(
+        "Processed {} item pages in {:.2?} ({:.0} pages/s)",
+        updated_item_map.len(),
+        elapsed,
+        updated_item_map.len() as f64 / elapsed.as_secs_f64().max(f64::EPSILON)
+    );
 
     let updated_metadata = CrateDocMetadata {
+        schema_version: SCHEMA_VERSION,
         crate_name: cargo_doc_output.metadata.crate_name.clone(),
         lib_name: cargo_doc_output.metadata.lib_name.clone(),
         item_map: updated_item_map,
+        fingerprint: cargo_doc_output.metadata.fingerprint.clone(),
+        page_hashes,
+        link_hash,
+        member_map,
+        alias_map,
+        items: item_infos,
     };
 
-    let metadata_json = serde_json::to_string_pretty(&updated_metadata)
-        .with_context(|| "failed to serialize metadata to JSON")?;
-    files.insert("metadata.json".to_string(), metadata_json);
-    debug!("Added metadata.json with updated item_map");
-
-    let docmd_base_path = match cargo_doc_output.path.parent() {
-        Some(val) => val,
-        None => bail!("cargo doc output path has no parent directory"),
-    };
-    let docmd_base_path = match docmd_base_path.parent() {
-        Some(val) => val,
-        None => bail!("doc base path has no parent directory"),
-    };
-    let output_path = docmd_base_path.join("docmd").join(lib_name);
-
     Ok(DocOutput {
         path: output_path,
         files,
+        metadata: updated_metadata,
     })
 }
 
-/// Save documentation output to disk.
+/// Read the re-exports listed on every module page of a cargo doc output.
 ///
-/// Writes all markdown files to the output directory, creating
-/// subdirectories as needed.
-fn save_doc(doc_output: DocOutput) -> Result<()> {
-    debug!("Saving documentation to: {:?}", doc_output.path);
+/// Module pages are the `index.html` files of the output directory and its
+/// subdirectories, the crate root included. Subdirectories of private
+/// modules only contain redirect pages and have no `index.html`.
+fn read_module_reexports(cargo_doc_dir: &Path, lib_name: &str) -> Result<Vec<Reexport>> {
+    let mut reexports = Vec::new();
+    let mut pending = vec![(cargo_doc_dir.to_path_buf(), lib_name.to_string())];
+
+    while let Some((dir, module)) = pending.pop() {
+        let index_path = dir.join("index.html");
+        if index_path.exists() {
+            let html = fs::read_to_string(&index_path)
+                .with_context(|| format!("failed to read file '{}'", index_path.display()))?;
+            reexports.extend(html2md::reexports(&html, &module)?);
+        }
 
-    if !doc_output.path.exists() {
-        fs::create_dir_all(&doc_output.path).with_context(|| {
+        let entries = fs::read_dir(&dir)
+            .with_context(|| format!("failed to read directory '{}'", dir.display()))?;
+        for entry in entries {
+            let path = entry?.path();
+            if path.is_dir()
+                && let Some(name) = path.file_name().and_then(|name| name.to_str())
+            {
+                let module = format!("{}::{}", module, name);
+                pending.push((path, module));
+            }
+        }
+    }
+
+    Ok(reexports)
+}
+
+/// An item page handled by `convert_item_pages`.
+struct ItemPage {
+    /// Path of the markdown file relative to the output directory
+    md_path: String,
+    /// Content hash of the source HTML page
+    hash: String,
+    /// Whether the page was converted, as opposed to reused unchanged
+    converted: bool,
+    /// Rustdoc anchors of the members documented on the page
+    members: Vec<String>,
+    /// Metadata of the item
+    info: ItemInfo,
+}
+
+/// Add the members of an item to a member map, keyed by their full path.
+///
+/// When several members share a path, such as a method provided by both an
+/// inherent impl and a trait impl, the first one is kept.
+fn insert_members(member_map: &mut HashMap<String, String>, item_path: &str, anchors: Vec<String>) {
+    for anchor in anchors {
+        let Some((_, path)) = MemberKind::from_anchor(&anchor) else {
+            continue;
+        };
+        member_map
+            .entry(format!("{}::{}", item_path, path))
+            .or_insert(anchor);
+    }
+}
+
+/// Convert item pages to markdown on a pool of worker threads.
+///
+/// Each worker reads an HTML page, converts it and writes the markdown file
+/// under `output_path` before taking the next page, so memory use is bounded
+/// by the number of workers rather than the number of items. Pages whose hash
+/// matches the previous page hashes and whose markdown file exists are
+/// skipped, and keep their previous item metadata. Returns one `ItemPage` per
+/// entry of `items`, in the same order.
+fn convert_item_pages(
+    items: &[(&String, &String)],
+    lib_name: &str,
+    skipped_sections: &[Section],
+    cargo_doc_dir: &Path,
+    output_path: &Path,
+    previous: PreviousPages,
+) -> Result<Vec<ItemPage>> {
+    let workers = thread::available_parallelism()
+        .map(NonZeroUsize::get)
+        .unwrap_or(1)
+        .min(items.len())
+        .max(1);
+    debug!(
+        "Converting {} item pages with {} threads",
+        items.len(),
+        workers
+    );
+
+    let next = AtomicUsize::new(0);
+    let failed = AtomicBool::new(false);
+
+    let worker = || -> Result<Vec<(usize, ItemPage)>> {
+        let mut pages = Vec::new();
+        while !failed.load(Ordering::Relaxed) {
+            let index = next.fetch_add(1, Ordering::Relaxed);
+            let Some((item_name, html_path)) = items.get(index) else {
+                break;
+            };
+            match convert_item_page(
+                item_name,
+                html_path,
+                lib_name,
+                skipped_sections,
+                cargo_doc_dir,
+                output_path,
+                previous,
+            ) {
+                Ok(page) => pages.push((index, page)),
+                Err(e) => {
+                    failed.store(true, Ordering::Relaxed);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(pages)
+    };
+
+    let results: Vec<Result<Vec<(usize, ItemPage)>>> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers).map(|_| scope.spawn(worker)).collect();
+        handles
+            .into_iter()
+            .map(|handle| match handle.join() {
+                Ok(result) => result,
+                Err(_) => Err(anyhow!("item page conversion thread panicked")),
+            })
+            .collect()
+    });
+
+    let mut pages = Vec::with_capacity(items.len());
+    for result in results {
+        pages.extend(result?);
+    }
+    pages.sort_by_key(|(index, _)| *index);
+
+    Ok(pages.into_iter().map(|(_, page)| page).collect())
+}
+
+/// Page hashes and item metadata of the previous conversion of a crate, keyed
+/// by HTML page path and by full item path.
+type PreviousPages<'a> = (&'a HashMap<String, String>, &'a HashMap<String, ItemInfo>);
+
+/// Convert a single item page and write it under `output_path`.
+///
+/// The implementors that trait pages load from other crates are part of the
+/// page hash, so documenting a new implementor converts the trait again.
+fn convert_item_page(
+    item_name: &str,
+    html_path: &str,
+    lib_name: &str,
+    skipped_sections: &[Section],
+    cargo_doc_dir: &Path,
+    output_path: &Path,
+    (previous_hashes, previous_items): PreviousPages,
+) -> Result<ItemPage> {
+    let full_html_path = cargo_doc_dir.join(html_path);
+    let html_content = fs::read_to_string(&full_html_path)
+        .with_context(|| format!("failed to read HTML file '{}'", full_html_path.display()))?;
+    let implementors = read_trait_implementors(cargo_doc_dir, lib_name, html_path);
+    let hash = match &implementors {
+        Some(script) => content_hash(&format!("{}{}", html_content, script)),
+        None => content_hash(&html_content),
+    };
+
+    let md_path = PathBuf::from(html_path)
+        .with_extension("md")
+        .to_string_lossy()
+        .to_string();
+    let full_md_path = output_path.join(&md_path);
+    let members = html2md::member_anchors(&html_content);
+    let kind = ItemKind::from_file(&md_path);
+
+    if previous_hashes.get(html_path) == Some(&hash) && full_md_path.exists() {
+        trace!("Unchanged item: {}", item_name);
+        let info = match previous_items.get(&format!("{}::{}", lib_name, item_name)) {
+            Some(info) => info.clone(),
+            None => ItemInfo {
+                kind,
+                ..html2md::item_info(&html_content)?
+            },
+        };
+        return Ok(ItemPage {
+            md_path,
+            hash,
+            converted: false,
+            members,
+            info,
+        });
+    }
+
+    debug!("Converting item: {}", item_name);
+    let page = html2md::DocPage {
+        lib_name,
+        path: html_path,
+        skipped_sections,
+        implementors: implementors.as_deref(),
+    };
+    let (markdown_content, info) = html2md::convert_item_page(&html_content, &page)
+        .with_context(|| format!("failed to convert '{}'", full_html_path.display()))?;
+
+    let parent = match full_md_path.parent() {
+        Some(p) => p,
+        None => bail!("file path has no parent directory"),
+    };
+    fs::create_dir_all(parent)
+        .with_context(|| format!("failed to create directory '{}'", parent.display()))?;
+    fs::write(&full_md_path, markdown_content)
+        .with_context(|| format!("failed to write file '{}'", full_md_path.display()))?;
+
+    debug!("Generated markdown: {}", full_md_path.display());
+
+    Ok(ItemPage {
+        md_path,
+        hash,
+        converted: true,
+        members,
+        info: ItemInfo { kind, ..info },
+    })
+}
+
+/// Process rustdoc JSON output and convert to markdown.
+///
+/// Parses the JSON file into `rustdoc_types::Crate`, renders the crate
+/// overview, the list of all items and every item page, and builds the
+/// output structure ready to be saved under `<docmd_dir>/<lib_name>`.
+fn process_rustdoc_json(
+    json_path: impl AsRef<Path>,
+    crate_name: &str,
+    docmd_dir: impl AsRef<Path>,
+    fingerprint: BuildFingerprint,
+    versioned: bool,
+) -> Result<DocOutput> {
+    let json_path = json_path.as_ref();
+    debug!("Processing rustdoc JSON: {:?}", json_path);
+
+    let json_content = fs::read_to_string(json_path)
+        .with_context(|| format!("failed to read file '{}'", json_path.display()))?;
+    let krate = parse_rustdoc_json(&json_content)?;
+
+    let json_doc = json2md::convert(&krate, &fingerprint.skipped_sections)?;
+    let lib_name = json_doc.lib_name;
+
+    let mut files = HashMap::new();
+    files.insert(
+        "index.md".to_string(),
+        format_index_md(&json_doc.index, &fingerprint),
+    );
+
+    let mut item_map = HashMap::new();
+    let mut member_map = HashMap::new();
+    let mut items = HashMap::new();
+    for page in json_doc.items {
+        let item_path = format!("{}::{}", lib_name, page.path);
+        insert_members(&mut member_map, &item_path, page.members);
+        let info = ItemInfo {
+            kind: ItemKind::from_file(&page.file),
+            ..page.info
+        };
+        items.insert(item_path.clone(), info);
+        item_map.insert(item_path, page.file.clone());
+        files.insert(page.file, page.markdown);
+    }
+    let dir_name = doc_dir_name(&lib_name, versioned.then_some(&fingerprint.version));
+    files.insert(
+        "all.md".to_string(),
+        format_all_md(&lib_name, &dir_name, &json_doc.all, &items),
+    );
+    let alias_map = reexports::alias_map(item_map.keys(), &json_doc.reexports);
+
+    info!("Converted {} items to markdown", item_map.len());
+
+    let metadata = CrateDocMetadata {
+        schema_version: SCHEMA_VERSION,
+        crate_name: crate_name.to_string(),
+        lib_name,
+        item_map,
+        fingerprint,
+        page_hashes: HashMap::new(),
+        link_hash: String::new(),
+        member_map,
+        alias_map,
+        items,
+    };
+
+    Ok(DocOutput {
+        path: docmd_dir.as_ref().join(dir_name),
+        files,
+        metadata,
+    })
+}
+
+/// Parse rustdoc JSON content into a `rustdoc_types::Crate`.
+///
+/// The format version is checked first so that output from an incompatible
+/// toolchain produces a clear error instead of a deserialization failure.
+fn parse_rustdoc_json(json_content: &str) -> Result<rustdoc_types::Crate> {
+    #[derive(Deserialize)]
+    struct FormatVersion {
+        format_version: u32,
+    }
+
+    let version: FormatVersion = serde_json::from_str(json_content)
+        .with_context(|| "failed to read format_version from rustdoc JSON")?;
+    ensure!(
+        version.format_version == rustdoc_types::FORMAT_VERSION,
+        concat!(
+            "rustdoc JSON format version {} is not supported (expected {}).\n",
+            "\n",
+            "Use a nightly toolchain that emits format version {}, or use the HTML backend."
+        ),
+        version.format_version,
+        rustdoc_types::FORMAT_VERSION,
+        rustdoc_types::FORMAT_VERSION
+    );
+
+    serde_json::from_str(json_content).with_context(|| "failed to parse rustdoc JSON")
+}
+
+/// Save documentation output to disk.
+///
+/// Writes all markdown files to the output directory, creating
+/// subdirectories as needed, then builds the search index and writes
+/// metadata.json. Returns the counts of documented items.
+fn save_doc(doc_output: DocOutput) -> Result<ItemCounts> {
+    debug!("Saving documentation to: {:?}", doc_output.path);
+
+    if !doc_output.path.exists() {
+        fs::create_dir_all(&doc_output.path).with_context(|| {
             format!(
                 "failed to create output directory '{}'",
                 doc_output.path.display()
@@ -345,48 +1836,137 @@ fn save_doc(doc_output: DocOutput) -> Result<()> {
         debug!("Generated markdown: {}", full_path.display());
     }
 
+    let pages: HashSet<PathBuf> = doc_output
+        .files
+        .keys()
+        .chain(doc_output.metadata.item_map.values())
+        .map(|page| doc_output.path.join(page))
+        .collect();
+    remove_stale_pages(&doc_output.path, &pages)?;
+
+    SearchIndex::write(&doc_output.path, &doc_output.metadata)?;
+    debug!("Generated search index");
+
+    // metadata.json is written last, so that it only exists for complete output.
+    let metadata_path = doc_output.path.join("metadata.json");
+    let metadata_json = serde_json::to_string_pretty(&doc_output.metadata)
+        .with_context(|| "failed to serialize metadata to JSON")?;
+    fs::write(&metadata_path, metadata_json)
+        .with_context(|| format!("failed to write file '{}'", metadata_path.display()))?;
+    debug!("Generated metadata: {}", metadata_path.display());
+
     let lib_name = doc_output
         .path
         .file_name()
         .and_then(|n| n.to_str())
         .unwrap_or("unknown");
 
-    let total_files = doc_output.files.len();
-    let item_count = total_files.saturating_sub(3);
+    let counts = ItemCounts::of(&doc_output.metadata);
 
     info!(
         "Built documentation for {} ({} items)",
-        lib_name, item_count
+        lib_name, counts.item_count
     );
     info!("Run `cargo txt list {}` to see all items", lib_name);
 
     info!("Successfully saved documentation");
+    Ok(counts)
+}
+
+/// Remove the markdown pages of a previous build that are not part of the
+/// new documentation, such as the pages of items that no longer exist.
+fn remove_stale_pages(output_dir: &Path, pages: &HashSet<PathBuf>) -> Result<()> {
+    let mut pending = vec![output_dir.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        let entries = fs::read_dir(&dir)
+            .with_context(|| format!("failed to read directory '{}'", dir.display()))?;
+        for entry in entries {
+            let path = entry?.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if path.extension().is_some_and(|extension| extension == "md")
+                && !pages.contains(&path)
+            {
+                fs::remove_file(&path)
+                    .with_context(|| format!("failed to remove file '{}'", path.display()))?;
+                debug!("Removed stale markdown: {}", path.display());
+            }
+        }
+    }
+
     Ok(())
 }
 
+/// Add the features and the target triple the documentation was generated
+/// for below the heading of index.md.
+///
+/// Items gated behind disabled features are missing from the documentation,
+/// so the features are always listed, as "none" when no feature is enabled.
+fn format_index_md(content: &str, fingerprint: &BuildFingerprint) -> String {
+    let features = if fingerprint.features.is_empty() {
+        "none".to_string()
+    } else {
+        fingerprint
+            .features
+            .iter()
+            .map(|feature| format!("`{}`", feature))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let mut build_info = format!("**Features**: {}\n", features);
+    if let Some(target) = &fingerprint.target {
+        build_info.push_str(&format!("\n**Target**: `{}`\n", target));
+    }
+
+    match content.split_once('\n') {
+        Some((heading, rest)) if heading.starts_with("# ") => {
+            format!(
+                "{}\n\n{}\n{}",
+                heading,
+                build_info,
+                rest.trim_start_matches('\n')
+            )
+        }
+        _ => format!("{}\n{}", build_info, content),
+    }
+}
+
 /// Format all.md content with crate name as H1 heading and prefixed items.
 ///
 /// This function post-processes the raw markdown content from all.html to:
 /// - Add crate name as H1 heading at the top
 /// - Convert existing H1 "# List of all items" to a paragraph
 /// - Prefix all list items with crate name
+/// - Follow list items with the summary of the item, if any
 /// - Append usage instructions at the end
 ///
+/// The printed paths start with the documentation directory name, so the
+/// items of a versioned library (e.g., `syn@1.0.109::Item`) resolve to that
+/// version rather than the default one.
+///
 /// * `crate_name` - The name of the crate (e.g., "serde")
+/// * `dir_name` - The documentation directory name (e.g., "serde" or "syn@1.0.109")
 /// * `content` - The raw markdown content from all.html
+/// * `items` - The metadata of the items, keyed by full path
 ///
 /// # Examples
 ///
 /// ```
 /// let raw = "# List of all items\n\n### Structs\n\n- Error\n";
-/// let formatted = format_all_md("serde", raw);
+/// let formatted = format_all_md("serde", "serde", raw, &HashMap::new());
 /// // formatted starts with "# serde\n\nList of all items\n\n### Structs\n\n- serde::Error\n"
 /// ```
-fn format_all_md(crate_name: &str, content: &str) -> String {
+fn format_all_md(
+    crate_name: &str,
+    dir_name: &str,
+    content: &str,
+    items: &HashMap<String, ItemInfo>,
+) -> String {
     let mut result = Vec::new();
     let mut lines = content.lines();
 
-    result.push(format!("# {}", crate_name));
+    result.push(format!("# {}", dir_name));
     result.push(String::new());
 
     let Some(first_line) = lines.next() else {
@@ -409,10 +1989,15 @@ fn format_all_md(crate_name: &str, content: &str) -> String {
         } else {
             match line.strip_prefix("- ") {
                 Some(item) => {
-                    result.push(format!("- {}::{}", crate_name, item));
+                    let key = format!("{}::{}", crate_name, item);
+                    let path = format!("{}::{}", dir_name, item);
+                    match items.get(&key).and_then(|info| info.summary.as_ref()) {
+                        Some(summary) => result.push(format!("- {} — {}", path, summary)),
+                        None => result.push(format!("- {}", path)),
+                    }
 
                     if in_section {
-                        first_items.push(format!("{}::{}", crate_name, item));
+                        first_items.push(path);
                         in_section = false;
                     }
                 }
@@ -441,7 +2026,7 @@ fn format_all_md(crate_name: &str, content: &str) -> String {
     }
 
     if first_items.is_empty() {
-        result.push(format!("cargo txt show {}::SomeItem", crate_name));
+        result.push(format!("cargo txt show {}::SomeItem", dir_name));
     }
 
     result.push("```".to_string());
@@ -455,45 +2040,46 @@ mod tests {
 
     #[test]
     fn validate_crate_name_with_regular_dependency() {
-        let cargo_metadata = cargo::Metadata {
-            packages: vec![cargo::Package {
-                dependencies: vec![
-                    cargo::Dependency {
-                        name: "serde".to_string(),
-                        kind: None,
-                    },
-                    cargo::Dependency {
-                        name: "anyhow".to_string(),
-                        kind: None,
-                    },
-                ],
-            }],
-            target_directory: "/target".to_string(),
-        };
+        let cargo_metadata = cargo::Metadata::workspace(vec![cargo::Package::local(
+            "app",
+            &[("serde", None), ("anyhow", None)],
+        )]);
 
-        let result = validate_crate_name("serde", &cargo_metadata);
+        let result = validate_crate_name("serde", &cargo_metadata, None);
         assert!(result.is_ok(), "Regular dependency should pass validation");
     }
 
     #[test]
-    fn validate_crate_name_with_dev_dependency() {
-        let cargo_metadata = cargo::Metadata {
-            packages: vec![cargo::Package {
-                dependencies: vec![
-                    cargo::Dependency {
-                        name: "serde_path_to_error".to_string(),
-                        kind: Some("dev".to_string()),
-                    },
-                    cargo::Dependency {
-                        name: "tempfile".to_string(),
-                        kind: Some("dev".to_string()),
-                    },
-                ],
-            }],
-            target_directory: "/target".to_string(),
-        };
+    fn rust_docs_crates_defer_to_packages() {
+        let mut cargo_metadata =
+            cargo::Metadata::workspace(vec![cargo::Package::local("app", &[("test", None)])]);
+        assert!(is_rust_docs_crate("std", &cargo_metadata));
+        assert!(is_rust_docs_crate("test", &cargo_metadata));
+        assert!(!is_rust_docs_crate("serde", &cargo_metadata));
+
+        cargo_metadata
+            .packages
+            .push(cargo::Package::registry("test", "0.1.0"));
+        assert!(
+            !is_rust_docs_crate("test", &cargo_metadata),
+            "A dependency named like a standard library crate is built from cargo doc"
+        );
+
+        let cargo_metadata = cargo::Metadata::workspace(vec![cargo::Package::local("core", &[])]);
+        assert!(!is_rust_docs_crate("core", &cargo_metadata));
+    }
 
-        let result = validate_crate_name("serde_path_to_error", &cargo_metadata);
+    #[test]
+    fn validate_crate_name_with_dev_dependency() {
+        let cargo_metadata = cargo::Metadata::workspace(vec![cargo::Package::local(
+            "app",
+            &[
+                ("serde_path_to_error", Some("dev")),
+                ("tempfile", Some("dev")),
+            ],
+        )]);
+
+        let result = validate_crate_name("serde_path_to_error", &cargo_metadata, None);
         assert!(result.is_err(), "Dev-dependency should fail validation");
 
         let error_msg = result.unwrap_err().to_string();
@@ -509,17 +2095,10 @@ mod tests {
 
     #[test]
     fn validate_crate_name_with_missing_dependency() {
-        let cargo_metadata = cargo::Metadata {
-            packages: vec![cargo::Package {
-                dependencies: vec![cargo::Dependency {
-                    name: "serde".to_string(),
-                    kind: None,
-                }],
-            }],
-            target_directory: "/target".to_string(),
-        };
+        let cargo_metadata =
+            cargo::Metadata::workspace(vec![cargo::Package::local("app", &[("serde", None)])]);
 
-        let result = validate_crate_name("nonexistent", &cargo_metadata);
+        let result = validate_crate_name("nonexistent", &cargo_metadata, None);
         assert!(result.is_err(), "Missing dependency should fail validation");
 
         let error_msg = result.unwrap_err().to_string();
@@ -535,23 +2114,12 @@ mod tests {
 
     #[test]
     fn validate_crate_name_with_build_dependency() {
-        let cargo_metadata = cargo::Metadata {
-            packages: vec![cargo::Package {
-                dependencies: vec![
-                    cargo::Dependency {
-                        name: "build-dep".to_string(),
-                        kind: Some("build".to_string()),
-                    },
-                    cargo::Dependency {
-                        name: "serde".to_string(),
-                        kind: None,
-                    },
-                ],
-            }],
-            target_directory: "/target".to_string(),
-        };
+        let cargo_metadata = cargo::Metadata::workspace(vec![cargo::Package::local(
+            "app",
+            &[("build-dep", Some("build")), ("serde", None)],
+        )]);
 
-        let result = validate_crate_name("build-dep", &cargo_metadata);
+        let result = validate_crate_name("build-dep", &cargo_metadata, None);
         assert!(result.is_err(), "Build dependency should fail validation");
 
         let error_msg = result.unwrap_err().to_string();
@@ -563,17 +2131,12 @@ mod tests {
 
     #[test]
     fn validate_crate_name_with_version_specifier() {
-        let cargo_metadata = cargo::Metadata {
-            packages: vec![cargo::Package {
-                dependencies: vec![cargo::Dependency {
-                    name: "tracing-subscriber".to_string(),
-                    kind: None,
-                }],
-            }],
-            target_directory: "/target".to_string(),
-        };
+        let cargo_metadata = cargo::Metadata::workspace(vec![cargo::Package::local(
+            "app",
+            &[("tracing-subscriber", None)],
+        )]);
 
-        let result = validate_crate_name("tracing-subscriber@0.3.23", &cargo_metadata);
+        let result = validate_crate_name("tracing-subscriber@0.3.23", &cargo_metadata, None);
         assert!(
             result.is_ok(),
             "Versioned crate name should pass validation when base name is a dependency"
@@ -582,17 +2145,10 @@ mod tests {
 
     #[test]
     fn validate_crate_name_versioned_with_missing_dependency() {
-        let cargo_metadata = cargo::Metadata {
-            packages: vec![cargo::Package {
-                dependencies: vec![cargo::Dependency {
-                    name: "serde".to_string(),
-                    kind: None,
-                }],
-            }],
-            target_directory: "/target".to_string(),
-        };
+        let cargo_metadata =
+            cargo::Metadata::workspace(vec![cargo::Package::local("app", &[("serde", None)])]);
 
-        let result = validate_crate_name("nonexistent@1.0.0", &cargo_metadata);
+        let result = validate_crate_name("nonexistent@1.0.0", &cargo_metadata, None);
         assert!(
             result.is_err(),
             "Missing versioned dependency should fail validation"
@@ -605,11 +2161,762 @@ mod tests {
         );
     }
 
+    fn workspace_metadata() -> cargo::Metadata {
+        cargo::Metadata::workspace(vec![
+            cargo::Package::local("cli", &[("clap", None), ("tempfile", Some("dev"))]),
+            cargo::Package::local("server", &[("tokio", None), ("tempfile", None)]),
+        ])
+    }
+
+    #[test]
+    fn validate_crate_name_with_dependency_of_other_workspace_member() {
+        let cargo_metadata = workspace_metadata();
+
+        let result = validate_crate_name("tokio", &cargo_metadata, None);
+        assert!(
+            result.is_ok(),
+            "Dependency of any workspace member should pass validation"
+        );
+
+        let result = validate_crate_name("tempfile", &cargo_metadata, None);
+        assert!(
+            result.is_ok(),
+            "Regular dependency of one member should win over a dev-dependency of another"
+        );
+    }
+
+    #[test]
+    fn validate_crate_name_with_package_filter() {
+        let cargo_metadata = workspace_metadata();
+
+        let result = validate_crate_name("tokio", &cargo_metadata, Some("cli"));
+        assert!(result.is_err());
+        let error_msg = result.unwrap_err().to_string();
+        assert!(error_msg.contains("not an installed dependency"));
+        assert!(error_msg.contains("clap (cli), tempfile (cli)"));
+
+        let result = validate_crate_name("tempfile", &cargo_metadata, Some("cli"));
+        assert!(result.is_err());
+        let error_msg = result.unwrap_err().to_string();
+        assert!(error_msg.contains("dev dependency of 'cli'"));
+    }
+
+    #[test]
+    fn validate_crate_name_with_transitive_dependency() {
+        let mut cargo_metadata = workspace_metadata();
+        let hyper = cargo::Package::registry("hyper", "1.0.0");
+        let server = cargo_metadata.packages[1].id.clone();
+        cargo_metadata.add_resolved_dependency(&server, &hyper.id, None);
+        cargo_metadata.packages.push(hyper);
+
+        let result = validate_crate_name("hyper", &cargo_metadata, None);
+        assert!(
+            result.is_ok(),
+            "Transitive dependency should pass validation"
+        );
+
+        let result = validate_crate_name("hyper", &cargo_metadata, Some("cli"));
+        assert!(
+            result.is_err(),
+            "Transitive dependency of another member should fail validation"
+        );
+    }
+
+    #[test]
+    fn validate_crate_name_with_workspace_member() {
+        let cargo_metadata = workspace_metadata();
+
+        let result = validate_crate_name("server", &cargo_metadata, None);
+        assert!(result.is_ok(), "Workspace member should pass validation");
+
+        let result = validate_crate_name("server", &cargo_metadata, Some("cli"));
+        assert!(
+            result.is_ok(),
+            "Workspace member should pass validation regardless of --package"
+        );
+    }
+
+    #[test]
+    fn validate_crate_name_reports_dependency_owners() {
+        let cargo_metadata = workspace_metadata();
+
+        let result = validate_crate_name("serde", &cargo_metadata, None);
+        assert!(result.is_err());
+        let error_msg = result.unwrap_err().to_string();
+        assert!(error_msg.contains("clap (cli), tempfile (cli, server), tokio (server)"));
+        assert!(error_msg.contains("Workspace members: cli, server"));
+    }
+
+    fn resolved_workspace_metadata() -> cargo::Metadata {
+        let mut cargo_metadata = workspace_metadata();
+        cargo_metadata.packages[0].targets = vec![cargo::Target {
+            name: "cli".to_string(),
+            kind: vec!["bin".to_string()],
+        }];
+        cargo_metadata.packages[1].targets = vec![cargo::Target {
+            name: "server".to_string(),
+            kind: vec!["lib".to_string()],
+        }];
+
+        let clap = cargo::Package::registry("clap", "4.5.0");
+        let tokio = cargo::Package::registry("tokio", "1.40.0");
+        let bytes = cargo::Package::registry("bytes", "1.7.0");
+        let old_bytes = cargo::Package::registry("bytes", "0.5.6");
+        let cli = cargo_metadata.packages[0].id.clone();
+        let server = cargo_metadata.packages[1].id.clone();
+        cargo_metadata.add_resolved_dependency(&cli, &clap.id, None);
+        cargo_metadata.add_resolved_dependency(&cli, &old_bytes.id, None);
+        cargo_metadata.add_resolved_dependency(&server, &tokio.id, None);
+        cargo_metadata.add_resolved_dependency(&tokio.id, &bytes.id, None);
+        cargo_metadata
+            .packages
+            .extend([clap, tokio, bytes, old_bytes]);
+        cargo_metadata
+    }
+
+    fn specs(targets: &[DocTarget]) -> Vec<&str> {
+        targets.iter().map(|t| t.spec.as_str()).collect()
+    }
+
+    #[test]
+    fn select_doc_targets_workspace_scope() {
+        let cargo_metadata = resolved_workspace_metadata();
+
+        let targets = select_doc_targets(
+            &cargo_metadata,
+            None,
+            BuildScope::Workspace,
+            &BuildFingerprint::default(),
+        )
+        .unwrap();
+        assert_eq!(specs(&targets), vec!["server", "bytes", "clap", "tokio"]);
+        assert_eq!(targets[0].lib_name, "server");
+    }
+
+    #[test]
+    fn select_doc_targets_direct_only() {
+        let cargo_metadata = resolved_workspace_metadata();
+
+        let targets = select_doc_targets(
+            &cargo_metadata,
+            None,
+            BuildScope::DirectOnly,
+            &BuildFingerprint::default(),
+        )
+        .unwrap();
+        assert_eq!(specs(&targets), vec!["bytes", "clap", "tokio"]);
+
+        let targets = select_doc_targets(
+            &cargo_metadata,
+            Some("server"),
+            BuildScope::DirectOnly,
+            &BuildFingerprint::default(),
+        )
+        .unwrap();
+        assert_eq!(specs(&targets), vec!["tokio"]);
+    }
+
+    #[test]
+    fn select_doc_targets_transitive_uses_versioned_specs() {
+        let cargo_metadata = resolved_workspace_metadata();
+
+        let targets = select_doc_targets(
+            &cargo_metadata,
+            None,
+            BuildScope::Transitive,
+            &BuildFingerprint::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            specs(&targets),
+            vec!["server", "bytes@1.7.0", "bytes@0.5.6", "clap", "tokio"]
+        );
+    }
+
+    #[test]
+    fn select_doc_targets_keeps_direct_version_unversioned() {
+        // cli depends on bytes 0.5.6, tokio on the newer bytes 1.7.0.
+        let cargo_metadata = resolved_workspace_metadata();
+
+        let targets = select_doc_targets(
+            &cargo_metadata,
+            None,
+            BuildScope::Transitive,
+            &BuildFingerprint::default(),
+        )
+        .unwrap();
+        let dir_names: Vec<String> = targets.iter().map(DocTarget::dir_name).collect();
+        assert_eq!(
+            dir_names,
+            vec!["server", "bytes@1.7.0", "bytes", "clap", "tokio"]
+        );
+        assert_eq!(targets[2].fingerprint.version, "0.5.6");
+
+        let stale: Vec<&DocTarget> = targets.iter().collect();
+        let (combined, separate) = split_doc_runs(&stale);
+        assert_eq!(
+            combined.iter().map(|t| t.spec.as_str()).collect::<Vec<_>>(),
+            vec!["server", "bytes@0.5.6", "clap", "tokio"]
+        );
+        assert_eq!(
+            separate.iter().map(|t| t.spec.as_str()).collect::<Vec<_>>(),
+            vec!["bytes@1.7.0"]
+        );
+    }
+
+    #[test]
+    fn insert_members_keys_members_by_path() {
+        let mut member_map = HashMap::new();
+        insert_members(
+            &mut member_map,
+            "probe::Shape",
+            vec![
+                "variant.Circle".to_string(),
+                "variant.Circle.field.radius".to_string(),
+                "method.from".to_string(),
+                "tymethod.from".to_string(),
+                "variant.Circle.fields".to_string(),
+            ],
+        );
+        assert_eq!(
+            member_map,
+            HashMap::from([
+                (
+                    "probe::Shape::Circle".to_string(),
+                    "variant.Circle".to_string()
+                ),
+                (
+                    "probe::Shape::Circle::radius".to_string(),
+                    "variant.Circle.field.radius".to_string()
+                ),
+                ("probe::Shape::from".to_string(), "method.from".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn read_module_reexports_of_nested_modules() {
+        let cargo_doc_dir = tempfile::tempdir().unwrap();
+        let reexports_page = |target: &str| {
+            format!(
+                r#"<h2 id="reexports">Re-exports</h2><dl class="item-table reexports"><dt><code>pub use <a title="mod {}">module</a>::*;</code></dt></dl>"#,
+                target
+            )
+        };
+        fs::write(
+            cargo_doc_dir.path().join("index.html"),
+            reexports_page("mylib::a"),
+        )
+        .unwrap();
+        fs::create_dir_all(cargo_doc_dir.path().join("a").join("b")).unwrap();
+        fs::write(
+            cargo_doc_dir.path().join("a").join("b").join("index.html"),
+            reexports_page("mylib::c"),
+        )
+        .unwrap();
+
+        let mut reexports = read_module_reexports(cargo_doc_dir.path(), "mylib").unwrap();
+        reexports.sort_by(|a, b| a.module.cmp(&b.module));
+        let modules: Vec<(&str, &str)> = reexports
+            .iter()
+            .map(|r| (r.module.as_str(), r.target.as_str()))
+            .collect();
+        assert_eq!(
+            modules,
+            vec![("mylib", "mylib::a"), ("mylib::a::b", "mylib::c")]
+        );
+    }
+
+    #[test]
+    fn read_trait_implementors_of_trait_pages() {
+        let target_dir = tempfile::tempdir().unwrap();
+        let doc_dir = target_dir.path().join("doc");
+        let script_dir = doc_dir.join("trait.impl").join("mylib").join("ser");
+        fs::create_dir_all(&script_dir).unwrap();
+        fs::write(script_dir.join("trait.Serialize.js"), "implementors").unwrap();
+
+        let cargo_doc_dir = doc_dir.join("mylib");
+        assert_eq!(
+            read_trait_implementors(&cargo_doc_dir, "mylib", "ser/trait.Serialize.html"),
+            Some("implementors".to_string())
+        );
+        assert_eq!(
+            read_trait_implementors(&cargo_doc_dir, "mylib", "ser/struct.Serialize.html"),
+            None
+        );
+        assert_eq!(
+            read_trait_implementors(&cargo_doc_dir, "mylib", "trait.Missing.html"),
+            None
+        );
+    }
+
+    #[test]
+    fn crate_report_records() {
+        let counts = ItemCounts {
+            item_count: 2,
+            member_count: 5,
+            alias_count: 1,
+            kinds: BTreeMap::from([(ItemKind::Struct, 1), (ItemKind::Fn, 1)]),
+        };
+        let report = |result: Result<BuildOutcome>| {
+            serde_json::to_value(CrateReport::new(
+                "probe-crate",
+                "probe_crate",
+                "0.1.0",
+                &result,
+            ))
+            .unwrap()
+        };
+
+        assert_eq!(
+            report(Ok(BuildOutcome::Built(
+                counts.clone(),
+                vec!["unresolved link to `Missing` (src/lib.rs:1:10)".to_string()]
+            ))),
+            serde_json::json!({
+                "crate_name": "probe-crate",
+                "lib_name": "probe_crate",
+                "version": "0.1.0",
+                "status": "built",
+                "item_count": 2,
+                "member_count": 5,
+                "alias_count": 1,
+                "kinds": { "struct": 1, "fn": 1 },
+                "warnings": ["unresolved link to `Missing` (src/lib.rs:1:10)"],
+            })
+        );
+        assert_eq!(
+            report(Ok(BuildOutcome::UpToDate(counts))),
+            serde_json::json!({
+                "crate_name": "probe-crate",
+                "lib_name": "probe_crate",
+                "version": "0.1.0",
+                "status": "up-to-date",
+                "item_count": 2,
+                "member_count": 5,
+                "alias_count": 1,
+                "kinds": { "struct": 1, "fn": 1 },
+                "warnings": [],
+            })
+        );
+        assert_eq!(
+            report(Err(
+                anyhow!("cargo doc failed").context("failed to build 'probe-crate'")
+            )),
+            serde_json::json!({
+                "crate_name": "probe-crate",
+                "lib_name": "probe_crate",
+                "version": "0.1.0",
+                "status": "failed",
+                "warnings": [],
+                "error": "failed to build 'probe-crate': cargo doc failed",
+            })
+        );
+    }
+
+    #[test]
+    fn content_hash_is_stable() {
+        assert_eq!(content_hash(""), "cbf29ce484222325");
+        assert_eq!(content_hash("a"), "af63dc4c8601ec8c");
+        assert_ne!(
+            content_hash("<main>a</main>"),
+            content_hash("<main>b</main>")
+        );
+    }
+
+    #[test]
+    fn crate_doc_metadata_without_fingerprint() {
+        let json = r#"{"crate_name":"serde","lib_name":"serde","item_map":{}}"#;
+        let metadata: CrateDocMetadata = serde_json::from_str(json).unwrap();
+        assert_eq!(metadata.schema_version, 0);
+        assert_eq!(metadata.fingerprint, BuildFingerprint::default());
+        assert!(metadata.page_hashes.is_empty());
+        assert!(metadata.items.is_empty());
+    }
+
+    fn write_doc_metadata(docmd_dir: &Path, dir_name: &str, fingerprint: BuildFingerprint) {
+        CrateDocMetadata {
+            schema_version: SCHEMA_VERSION,
+            crate_name: "serde".to_string(),
+            lib_name: "serde".to_string(),
+            item_map: HashMap::from([("serde::Error".to_string(), "struct.Error.md".to_string())]),
+            fingerprint,
+            ..Default::default()
+        }
+        .write_lib(docmd_dir, dir_name);
+    }
+
+    #[test]
+    fn find_doc_dir_selects_versions() {
+        let docmd_dir = tempfile::tempdir().unwrap();
+        let docmd = docmd_dir.path();
+        let write = |dir_name: &str, version: &str| {
+            write_doc_metadata(
+                docmd,
+                dir_name,
+                BuildFingerprint {
+                    version: version.to_string(),
+                    ..Default::default()
+                },
+            )
+        };
+
+        assert_eq!(find_doc_dir(docmd, "syn", None), None);
+
+        write("syn@1.0.109", "1.0.109");
+        write("syn@1.0.99", "1.0.99");
+        assert_eq!(
+            find_doc_dir(docmd, "syn", None),
+            Some(docmd.join("syn@1.0.109")),
+            "Without default version, the newest version is used"
+        );
+
+        write("syn", "2.0.87");
+        assert_eq!(find_doc_dir(docmd, "syn", None), Some(docmd.join("syn")));
+        assert_eq!(
+            find_doc_dir(docmd, "syn", Some("2")),
+            Some(docmd.join("syn"))
+        );
+        assert_eq!(
+            find_doc_dir(docmd, "syn", Some("1")),
+            Some(docmd.join("syn@1.0.109"))
+        );
+        assert_eq!(
+            find_doc_dir(docmd, "syn", Some("1.0.99")),
+            Some(docmd.join("syn@1.0.99"))
+        );
+        assert_eq!(find_doc_dir(docmd, "syn", Some("3")), None);
+        assert_eq!(find_doc_dir(docmd, "sy", None), None);
+
+        assert_eq!(doc_dir_name("syn", None), "syn");
+        assert_eq!(doc_dir_name("syn", Some("1.0.109")), "syn@1.0.109");
+    }
+
+    #[test]
+    fn rust_docs_fingerprint_uses_rustc_version() {
+        let base = BuildFingerprint {
+            rustc_version: "rustc 1.95.0 (59807616e 2026-04-14)".to_string(),
+            generator: "0.1.0".to_string(),
+            ..Default::default()
+        };
+        let fingerprint = rust_docs_fingerprint(&base);
+        assert_eq!(fingerprint.version, "1.95.0");
+        assert_eq!(fingerprint.generator, "0.1.0");
+        assert!(fingerprint.features.is_empty());
+    }
+
+    #[test]
+    fn up_to_date_counts_requires_current_schema_version() {
+        let docmd_dir = tempfile::tempdir().unwrap();
+        let output_dir = docmd_dir.path().join("serde");
+        write_doc_metadata(docmd_dir.path(), "serde", BuildFingerprint::default());
+
+        let metadata_path = output_dir.join("metadata.json");
+        let mut metadata: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&metadata_path).unwrap()).unwrap();
+        metadata["schema_version"] = serde_json::json!(SCHEMA_VERSION - 1);
+        fs::write(&metadata_path, metadata.to_string()).unwrap();
+
+        assert_eq!(
+            up_to_date_counts(
+                docmd_dir.path(),
+                "serde",
+                false,
+                &BuildFingerprint::default()
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn up_to_date_counts_compares_fingerprints() {
+        let docmd_dir = tempfile::tempdir().unwrap();
+        let fingerprint = BuildFingerprint {
+            version: "1.0.228".to_string(),
+            features: vec!["std".to_string()],
+            rustc_version: "rustc 1.92.0".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            up_to_date_counts(docmd_dir.path(), "serde", false, &fingerprint),
+            None,
+            "Missing documentation is never up to date"
+        );
+
+        write_doc_metadata(docmd_dir.path(), "serde", fingerprint.clone());
+        assert_eq!(
+            up_to_date_counts(docmd_dir.path(), "serde", false, &fingerprint),
+            Some(ItemCounts {
+                item_count: 1,
+                member_count: 0,
+                alias_count: 0,
+                kinds: BTreeMap::from([(ItemKind::Struct, 1)]),
+            })
+        );
+        assert_eq!(
+            up_to_date_counts(docmd_dir.path(), "serde", true, &fingerprint),
+            None,
+            "Local packages are always rebuilt"
+        );
+
+        let upgraded = BuildFingerprint {
+            version: "1.0.229".to_string(),
+            ..fingerprint.clone()
+        };
+        assert_eq!(
+            up_to_date_counts(docmd_dir.path(), "serde", false, &upgraded),
+            None
+        );
+
+        let more_features = BuildFingerprint {
+            features: vec!["derive".to_string(), "std".to_string()],
+            ..fingerprint
+        };
+        assert_eq!(
+            up_to_date_counts(docmd_dir.path(), "serde", false, &more_features),
+            None
+        );
+    }
+
+    fn fake_cargo_doc_output(doc_dir: &Path, item_html: &str) -> CargoDocOutput {
+        let page = |body: &str| format!("<html><body><main>{}</main></body></html>", body);
+        fs::create_dir_all(doc_dir).unwrap();
+        fs::write(doc_dir.join("struct.Foo.html"), page(item_html)).unwrap();
+
+        CargoDocOutput {
+            path: doc_dir.to_path_buf(),
+            output_path: doc_dir
+                .parent()
+                .unwrap()
+                .with_file_name("docmd")
+                .join("mylib"),
+            files: HashMap::from([
+                ("index.html".to_string(), page("<h1>Crate mylib</h1>")),
+                ("all.html".to_string(), page("<h1>List of all items</h1>")),
+            ]),
+            metadata: CrateDocMetadata {
+                crate_name: "mylib".to_string(),
+                lib_name: "mylib".to_string(),
+                item_map: HashMap::from([("Foo".to_string(), "struct.Foo.html".to_string())]),
+                fingerprint: BuildFingerprint {
+                    generator: "0.1.0".to_string(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn save_doc_removes_pages_of_removed_items() {
+        let target_dir = tempfile::tempdir().unwrap();
+        let doc_dir = target_dir.path().join("doc").join("mylib");
+        let output_dir = target_dir.path().join("docmd").join("mylib");
+        let bar_md = output_dir.join("inner").join("struct.Bar.md");
+
+        let mut cargo_doc_output = fake_cargo_doc_output(&doc_dir, "<p>foo</p>");
+        fs::create_dir_all(doc_dir.join("inner")).unwrap();
+        fs::write(
+            doc_dir.join("inner").join("struct.Bar.html"),
+            "<html><body><main><p>bar</p></main></body></html>",
+        )
+        .unwrap();
+        cargo_doc_output.metadata.item_map.insert(
+            "inner::Bar".to_string(),
+            "inner/struct.Bar.html".to_string(),
+        );
+        let doc_output = process_cargo_doc_output(cargo_doc_output).unwrap();
+        assert_eq!(save_doc(doc_output).unwrap().item_count, 2);
+        assert!(bar_md.exists());
+
+        fs::remove_file(doc_dir.join("inner").join("struct.Bar.html")).unwrap();
+        let doc_output =
+            process_cargo_doc_output(fake_cargo_doc_output(&doc_dir, "<p>foo</p>")).unwrap();
+        assert_eq!(save_doc(doc_output).unwrap().item_count, 1);
+        assert!(
+            !bar_md.exists(),
+            "Page of the removed item should be deleted"
+        );
+        for kept in ["index.md", "all.md", "struct.Foo.md", "metadata.json"] {
+            assert!(output_dir.join(kept).exists(), "{} should be kept", kept);
+        }
+    }
+
+    #[test]
+    fn process_cargo_doc_output_reconverts_changed_pages_only() {
+        let target_dir = tempfile::tempdir().unwrap();
+        let doc_dir = target_dir.path().join("doc").join("mylib");
+        let output_dir = target_dir.path().join("docmd").join("mylib");
+        let foo_md = output_dir.join("struct.Foo.md");
+
+        let doc_output =
+            process_cargo_doc_output(fake_cargo_doc_output(&doc_dir, "<p>first</p>")).unwrap();
+        assert_eq!(doc_output.path, output_dir);
+        assert!(fs::read_to_string(&foo_md).unwrap().contains("first"));
+        assert_eq!(save_doc(doc_output).unwrap().item_count, 1);
+
+        // Mark the page so a reconversion would be noticed.
+        fs::write(&foo_md, "unchanged").unwrap();
+        let doc_output =
+            process_cargo_doc_output(fake_cargo_doc_output(&doc_dir, "<p>first</p>")).unwrap();
+        assert_eq!(
+            fs::read_to_string(&foo_md).unwrap(),
+            "unchanged",
+            "Unchanged page should not be converted again"
+        );
+        assert_eq!(
+            doc_output.metadata.items["mylib::Foo"].kind,
+            Some(ItemKind::Struct),
+            "Unchanged page should keep its item metadata"
+        );
+        assert_eq!(save_doc(doc_output).unwrap().item_count, 1);
+
+        process_cargo_doc_output(fake_cargo_doc_output(&doc_dir, "<p>second</p>")).unwrap();
+        assert!(fs::read_to_string(&foo_md).unwrap().contains("second"));
+    }
+
+    #[test]
+    fn process_cargo_doc_output_reconverts_pages_when_links_change() {
+        let target_dir = tempfile::tempdir().unwrap();
+        let doc_dir = target_dir.path().join("doc").join("mylib");
+        let output_dir = target_dir.path().join("docmd").join("mylib");
+        let foo_md = output_dir.join("struct.Foo.md");
+
+        let doc_output =
+            process_cargo_doc_output(fake_cargo_doc_output(&doc_dir, "<p>first</p>")).unwrap();
+        save_doc(doc_output).unwrap();
+
+        // Documenting another crate turns its links into local pages.
+        fs::write(&foo_md, "unchanged").unwrap();
+        let other_dir = target_dir.path().join("doc").join("other");
+        fs::create_dir_all(&other_dir).unwrap();
+        fs::write(other_dir.join("index.html"), "<html></html>").unwrap();
+        let doc_output =
+            process_cargo_doc_output(fake_cargo_doc_output(&doc_dir, "<p>first</p>")).unwrap();
+        assert!(
+            fs::read_to_string(&foo_md).unwrap().contains("first"),
+            "Unchanged page should be converted again for the new crate"
+        );
+        save_doc(doc_output).unwrap();
+
+        fs::write(&foo_md, "unchanged").unwrap();
+        process_cargo_doc_output(fake_cargo_doc_output(&doc_dir, "<p>first</p>")).unwrap();
+        assert_eq!(fs::read_to_string(&foo_md).unwrap(), "unchanged");
+    }
+
+    #[test]
+    fn link_hash_covers_crates_and_maps() {
+        let target_dir = tempfile::tempdir().unwrap();
+        let doc_dir = target_dir.path().join("doc").join("mylib");
+        fs::create_dir_all(&doc_dir).unwrap();
+        let aliases = HashMap::from([("mylib::Foo".to_string(), "mylib::inner::Foo".to_string())]);
+        let members = HashMap::from([(
+            "mylib::inner::Foo::new".to_string(),
+            "method.new".to_string(),
+        )]);
+
+        let hash = link_hash(&doc_dir, &aliases, &members);
+        assert_eq!(link_hash(&doc_dir, &aliases, &members), hash);
+        assert_ne!(link_hash(&doc_dir, &HashMap::new(), &members), hash);
+        assert_ne!(link_hash(&doc_dir, &aliases, &HashMap::new()), hash);
+
+        let other_dir = target_dir.path().join("doc").join("other");
+        fs::create_dir_all(&other_dir).unwrap();
+        assert_eq!(
+            link_hash(&doc_dir, &aliases, &members),
+            hash,
+            "Directories without docs are not documented crates"
+        );
+        fs::write(other_dir.join("index.html"), "<html></html>").unwrap();
+        assert_ne!(link_hash(&doc_dir, &aliases, &members), hash);
+    }
+
+    #[test]
+    fn convert_item_pages_keeps_item_order() {
+        let cargo_doc_dir = tempfile::tempdir().unwrap();
+        let output_dir = tempfile::tempdir().unwrap();
+
+        let names: Vec<String> = (0..50).map(|i| format!("Item{}", i)).collect();
+        let html_paths: Vec<String> = names
+            .iter()
+            .map(|name| format!("module/struct.{}.html", name))
+            .collect();
+        fs::create_dir_all(cargo_doc_dir.path().join("module")).unwrap();
+        for (name, html_path) in names.iter().zip(&html_paths) {
+            fs::write(
+                cargo_doc_dir.path().join(html_path),
+                format!("<main><h1>{}</h1></main>", name),
+            )
+            .unwrap();
+        }
+
+        let items: Vec<(&String, &String)> = names.iter().zip(&html_paths).collect();
+        let pages = convert_item_pages(
+            &items,
+            "mylib",
+            &[],
+            cargo_doc_dir.path(),
+            output_dir.path(),
+            (&HashMap::new(), &HashMap::new()),
+        )
+        .unwrap();
+
+        assert_eq!(pages.len(), 50);
+        for (name, page) in names.iter().zip(&pages) {
+            assert_eq!(page.md_path, format!("module/struct.{}.md", name));
+            assert!(page.converted);
+            let markdown = fs::read_to_string(output_dir.path().join(&page.md_path)).unwrap();
+            assert!(markdown.contains(name.as_str()));
+        }
+    }
+
+    #[test]
+    fn convert_item_pages_reports_missing_page() {
+        let cargo_doc_dir = tempfile::tempdir().unwrap();
+        let output_dir = tempfile::tempdir().unwrap();
+
+        let name = "Missing".to_string();
+        let html_path = "struct.Missing.html".to_string();
+        let result = convert_item_pages(
+            &[(&name, &html_path)],
+            "mylib",
+            &[],
+            cargo_doc_dir.path(),
+            output_dir.path(),
+            (&HashMap::new(), &HashMap::new()),
+        );
+
+        let error_msg = result.err().unwrap().to_string();
+        assert!(error_msg.contains("failed to read HTML file"));
+    }
+
+    #[test]
+    fn format_index_md_lists_features_and_target() {
+        let fingerprint = BuildFingerprint {
+            features: vec!["default".to_string(), "derive".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            format_index_md("# Crate serde\n\n## Description\n", &fingerprint),
+            "# Crate serde\n\n**Features**: `default`, `derive`\n\n## Description\n"
+        );
+
+        let fingerprint = BuildFingerprint {
+            target: Some("wasm32-unknown-unknown".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            format_index_md("# Crate probe\n", &fingerprint),
+            "# Crate probe\n\n**Features**: none\n\n**Target**: `wasm32-unknown-unknown`\n\n"
+        );
+    }
+
     #[test]
     fn format_all_md_comprehensive() {
         let content = "# List of all items\n\n### Structs\n\n- Error\n- Config\n\n### Traits\n\n- Serialize\n- Deserialize\n\n### Enums\n\n- Value";
 
-        let result = format_all_md("serde", content);
+        let result = format_all_md("serde", "serde", content, &HashMap::new());
 
         assert_eq!(&result[..7], "# serde");
 
@@ -637,10 +2944,27 @@ mod tests {
         assert!(!result.contains("cargo txt show serde::SomeEnum"));
     }
 
+    #[test]
+    fn format_all_md_appends_summaries() {
+        let content = "# List of all items\n\n### Structs\n\n- Error\n- Config";
+        let items = HashMap::from([(
+            "serde::Error".to_string(),
+            ItemInfo {
+                summary: Some("The error type.".to_string()),
+                ..Default::default()
+            },
+        )]);
+        let result = format_all_md("serde", "serde", content, &items);
+
+        assert!(result.contains("\n- serde::Error — The error type.\n"));
+        assert!(result.contains("\n- serde::Config\n"));
+        assert!(result.contains("cargo txt show serde::Error\n"));
+    }
+
     #[test]
     fn format_all_md_uses_crate_name_as_is() {
         let content = "# List of all items\n\n### Structs\n\n- Error\n- Config";
-        let result = format_all_md("rustdoc_types", content);
+        let result = format_all_md("rustdoc_types", "rustdoc_types", content, &HashMap::new());
 
         assert_eq!(&result[..15], "# rustdoc_types");
 
@@ -648,10 +2972,30 @@ mod tests {
         assert!(result.contains("- rustdoc_types::Config"));
     }
 
+    #[test]
+    fn format_all_md_prefixes_versioned_paths() {
+        let content = "# List of all items\n\n### Structs\n\n- Item";
+        let items = HashMap::from([(
+            "syn::Item".to_string(),
+            ItemInfo {
+                summary: Some("Things that can appear directly inside of a module.".to_string()),
+                ..Default::default()
+            },
+        )]);
+        let result = format_all_md("syn", "syn@1.0.109", content, &items);
+
+        assert_eq!(&result[..14], "# syn@1.0.109\n");
+        assert!(result.contains(
+            "\n- syn@1.0.109::Item — Things that can appear directly inside of a module.\n"
+        ));
+        assert!(result.contains("cargo txt show syn@1.0.109::Item\n"));
+        assert!(!result.contains(" syn::Item"));
+    }
+
     #[test]
     fn format_all_md_preserves_non_list_lines() {
         let content = "# List of all items\n\n### Structs\n\nSome text\n\n- Error";
-        let result = format_all_md("serde", content);
+        let result = format_all_md("serde", "serde", content, &HashMap::new());
 
         assert!(result.contains("### Structs"));
         assert!(result.contains("\nSome text\n"));
@@ -661,7 +3005,7 @@ mod tests {
     #[test]
     fn format_all_md_usage_instructions_fallback() {
         let content = "# List of all items\n\nNo items here";
-        let result = format_all_md("my_crate", content);
+        let result = format_all_md("my_crate", "my_crate", content, &HashMap::new());
 
         assert!(result.contains("cargo txt show my_crate::SomeItem"));
 
@@ -672,7 +3016,7 @@ mod tests {
     #[test]
     fn format_all_md_usage_instructions_limit_to_3_sections() {
         let content = "# List of all items\n\n### Structs\n\n- Error\n- Config\n\n### Traits\n\n- Serialize\n- Deserialize\n\n### Enums\n\n- Value\n\n### Constants\n\n- VERSION";
-        let result = format_all_md("serde", content);
+        let result = format_all_md("serde", "serde", content, &HashMap::new());
 
         assert!(result.contains("cargo txt show serde::Error"));
         assert!(result.contains("cargo txt show serde::Serialize"));
@@ -1,44 +1,285 @@
 //! List command implementation.
 //!
 //! This module provides the list command which displays the master index
-//! of all items in a crate (all.md) to stdout. The list command only accepts
-//! crate names and rejects paths with `::` to provide clear separation from
-//! the show command.
+//! of all items in a crate (all.md) to stdout. Given a module path, such as
+//! `serde::de`, only the direct children of the module are listed, and the
+//! module hierarchy can be printed as a tree. Items can be filtered by kind,
+//! name pattern and module. Modules and kinds are derived from the item paths
+//! and files recorded in the metadata, re-exported paths included.
 
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
-use anyhow::{Context, Result, bail};
+use anyhow::{Context, Result, anyhow, bail};
+use clap::ValueEnum;
+use regex_automata::meta::Regex;
+use regex_automata::util::syntax;
+use serde::{Deserialize, Serialize};
 use serde_json;
 use tracing::{debug, trace};
 
 use crate::cargo;
-use crate::commands::build::CrateDocMetadata;
+use crate::commands::build::{CrateDocMetadata, find_doc_dir};
+use crate::commands::search::qualified_path;
+use crate::commands::{OutputFormat, write_json};
 
-/// List and display all items in a crate.
-///
-/// This function accepts a library name, checks if metadata.json exists,
-/// resolves the all.md file path, and prints its contents to stdout.
-pub fn list(lib_name: &str) -> Result<()> {
-    debug!("List command: lib_name={}", lib_name);
+/// Kind of a listed item, in the order rustdoc lists them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ItemKind {
+    #[value(alias = "mod")]
+    Module,
+    Primitive,
+    Macro,
+    Struct,
+    Enum,
+    Union,
+    Trait,
+    TraitAlias,
+    #[value(alias = "attribute")]
+    Attr,
+    Derive,
+    #[value(alias = "function")]
+    Fn,
+    #[value(alias = "type-alias")]
+    Type,
+    Static,
+    #[value(alias = "const")]
+    Constant,
+    Keyword,
+}
+
+impl ItemKind {
+    /// Return the kind of an item from its markdown file name, such as
+    /// `Struct` for `de/struct.IgnoredAny.md`.
+    pub fn from_file(file: &str) -> Option<ItemKind> {
+        let name = file.rsplit('/').next().unwrap_or(file);
+        let kind = match name.split_once('.')?.0 {
+            "primitive" => ItemKind::Primitive,
+            "macro" => ItemKind::Macro,
+            "struct" => ItemKind::Struct,
+            "enum" => ItemKind::Enum,
+            "union" => ItemKind::Union,
+            "trait" => ItemKind::Trait,
+            "traitalias" => ItemKind::TraitAlias,
+            "attr" => ItemKind::Attr,
+            "derive" => ItemKind::Derive,
+            "fn" => ItemKind::Fn,
+            "type" => ItemKind::Type,
+            "static" => ItemKind::Static,
+            "constant" => ItemKind::Constant,
+            "keyword" => ItemKind::Keyword,
+            _ => return None,
+        };
+        Some(kind)
+    }
+
+    /// Return the list heading of the kind.
+    fn title(self) -> &'static str {
+        match self {
+            ItemKind::Module => "Modules",
+            ItemKind::Primitive => "Primitives",
+            ItemKind::Macro => "Macros",
+            ItemKind::Struct => "Structs",
+            ItemKind::Enum => "Enums",
+            ItemKind::Union => "Unions",
+            ItemKind::Trait => "Traits",
+            ItemKind::TraitAlias => "Trait Aliases",
+            ItemKind::Attr => "Attribute Macros",
+            ItemKind::Derive => "Derive Macros",
+            ItemKind::Fn => "Functions",
+            ItemKind::Type => "Type Aliases",
+            ItemKind::Static => "Statics",
+            ItemKind::Constant => "Constants",
+            ItemKind::Keyword => "Keywords",
+        }
+    }
+}
+
+/// Filters of the list command.
+#[derive(Debug, Default)]
+pub struct ListFilter {
+    /// Only list items of these kinds
+    pub kinds: Vec<ItemKind>,
+    /// Only list items whose name matches this glob, or regular expression
+    /// with `regex`
+    pub pattern: Option<String>,
+    /// Interpret `pattern` as a regular expression instead of a glob
+    pub regex: bool,
+    /// Only list items below this module (e.g., "io" or "tokio::io")
+    pub module: Option<String>,
+}
+
+impl ListFilter {
+    /// Return whether no filter is set.
+    fn is_empty(&self) -> bool {
+        self.kinds.is_empty() && self.pattern.is_none() && self.module.is_none()
+    }
+}
+
+/// A compiled `ListFilter`.
+struct Matcher<'a> {
+    /// Kinds to keep, or all kinds if empty
+    kinds: &'a [ItemKind],
+    /// Pattern matched against item names
+    pattern: Option<Regex>,
+    /// Path prefix of the items to keep, ending with `::`
+    module_prefix: Option<String>,
+}
 
+impl<'a> Matcher<'a> {
+    /// Compile a filter for the items of `lib_name`.
+    fn new(filter: &'a ListFilter, lib_name: &str) -> Result<Self> {
+        let pattern = match &filter.pattern {
+            Some(pattern) => {
+                let expression = if filter.regex {
+                    pattern.clone()
+                } else {
+                    glob_to_regex(pattern)
+                };
+                let regex = Regex::builder()
+                    .syntax(syntax::Config::new().case_insensitive(true))
+                    .build(&expression)
+                    .with_context(|| format!("invalid filter pattern '{}'", pattern))?;
+                Some(regex)
+            }
+            None => None,
+        };
+
+        let module_prefix = filter.module.as_deref().map(|module| {
+            let module = module.trim_end_matches("::");
+            if module == lib_name || module.starts_with(&format!("{}::", lib_name)) {
+                format!("{}::", module)
+            } else {
+                format!("{}::{}::", lib_name, module)
+            }
+        });
+
+        Ok(Matcher {
+            kinds: &filter.kinds,
+            pattern,
+            module_prefix,
+        })
+    }
+
+    /// Return whether an item or module is kept.
+    ///
+    /// The pattern is matched against the name of the item, the last segment
+    /// of its path.
+    fn matches(&self, path: &str, kind: Option<ItemKind>) -> bool {
+        let name = path.rsplit("::").next().unwrap_or(path);
+        (self.kinds.is_empty() || kind.is_some_and(|kind| self.kinds.contains(&kind)))
+            && self
+                .pattern
+                .as_ref()
+                .is_none_or(|pattern| pattern.is_match(name))
+            && self
+                .module_prefix
+                .as_ref()
+                .is_none_or(|prefix| path.starts_with(prefix))
+    }
+}
+
+/// Convert a glob, where `*` matches any characters and `?` a single
+/// character, to an anchored regular expression.
+fn glob_to_regex(glob: &str) -> String {
+    let mut expression = String::from("^");
+    for c in glob.chars() {
+        match c {
+            '*' => expression.push_str(".*"),
+            '?' => expression.push('.'),
+            c if "\\.+()|[]{}^$#&-~".contains(c) => {
+                expression.push('\\');
+                expression.push(c);
+            }
+            c => expression.push(c),
+        }
+    }
+    expression.push('$');
+    expression
+}
+
+/// A module of a crate, derived from the paths of its items.
+#[derive(Debug, Default, PartialEq)]
+struct Module {
+    /// Full paths of the direct submodules
+    submodules: BTreeSet<String>,
+    /// Full paths and kinds of the direct child items, sorted by path
+    items: Vec<(String, Option<ItemKind>)>,
+}
+
+/// List and display the items of a crate or of one of its modules.
+///
+/// This function accepts a library name or a module path, checks if
+/// metadata.json exists and prints to stdout either all.md for a library, the
+/// direct children of a module grouped by kind, or with `tree` the module
+/// hierarchy with item counts. With a library name and a filter, every
+/// matching item of the crate is listed, grouped by kind. With
+/// `OutputFormat::Json`, the listed modules and items are printed as JSON
+/// records, every item of the crate for a library name. A version after the
+/// library name, as in `syn@2::parse`, selects the documentation of that
+/// version of the crate. The markdown output of a versioned library prefixes
+/// its paths with the version, as in `syn@2.0.87::parse`.
+pub fn list(
+    path: &str,
+    tree: bool,
+    filter: &ListFilter,
+    format: OutputFormat,
+    out: &mut dyn Write,
+) -> Result<()> {
     let metadata = cargo::metadata()?;
     let docmd_dir = PathBuf::from(&metadata.target_directory).join("docmd");
+    list_in(&docmd_dir, path, tree, filter, format, out)
+}
 
-    let metadata_path = docmd_dir.join(lib_name).join("metadata.json");
-    if !metadata_path.exists() {
-        let available_crates: Vec<&str> = metadata.packages[0]
-            .dependencies
-            .iter()
-            .map(|dep| dep.name.as_str())
-            .collect();
+/// List a library or module of the documentation built under `docmd_dir`.
+fn list_in(
+    docmd_dir: &Path,
+    path: &str,
+    tree: bool,
+    filter: &ListFilter,
+    format: OutputFormat,
+    out: &mut dyn Write,
+) -> Result<()> {
+    debug!(
+        "List command: path={}, tree={}, filter={:?}, format={:?}",
+        path, tree, filter, format
+    );
+
+    let (spec, module) = match path.split_once("::") {
+        Some((spec, module)) => (spec, Some(module)),
+        None => (path, None),
+    };
+    let (lib_name, version) = match spec.split_once('@') {
+        Some((lib_name, version)) => (lib_name, Some(version)),
+        None => (spec, None),
+    };
+    if lib_name.is_empty() || version == Some("") || path.ends_with("::") {
         bail!(
-            "Documentation for '{}' is not built yet. Run 'cargo txt build <crate>' for one of the following crates: {}",
-            lib_name,
-            available_crates.join(", ")
+            "invalid module path '{}'. Expected format: <lib_name> or <lib_name>::<module> (e.g., 'serde' or 'serde::de').",
+            path
         );
     }
+    let unversioned_path = match module {
+        Some(module) => format!("{}::{}", lib_name, module),
+        None => lib_name.to_string(),
+    };
+    let path = unversioned_path.as_str();
+
+    let Some(lib_dir) = find_doc_dir(docmd_dir, lib_name, version) else {
+        let metadata = cargo::metadata()?;
+        let owners = metadata.dependency_owners(None)?;
+        bail!(
+            "Documentation for '{}' is not built yet. Run 'cargo txt build <crate>' for one of the following crates: {}",
+            spec,
+            cargo::format_dependency_owners(&owners)
+        );
+    };
+    debug!("Resolved documentation directory: {:?}", lib_dir);
 
+    let metadata_path = lib_dir.join("metadata.json");
     let metadata_content = fs::read_to_string(&metadata_path)
         .with_context(|| format!("failed to read metadata file '{}'", metadata_path.display()))?;
     let crate_metadata: CrateDocMetadata =
@@ -49,22 +290,755 @@ pub fn list(lib_name: &str) -> Result<()> {
         crate_metadata.crate_name, crate_metadata.lib_name
     );
 
-    let all_md_path = docmd_dir.join(lib_name).join("all.md");
-    debug!("Resolved all.md path: {:?}", all_md_path);
+    if path == lib_name && !tree && filter.is_empty() && format == OutputFormat::Markdown {
+        let all_md_path = lib_dir.join("all.md");
+        debug!("Resolved all.md path: {:?}", all_md_path);
 
-    let markdown_content = fs::read_to_string(&all_md_path)
-        .with_context(|| format!("failed to read markdown file '{}'", all_md_path.display()))?;
-    trace!("Read markdown file ({} bytes)", markdown_content.len());
+        let markdown_content = fs::read_to_string(&all_md_path)
+            .with_context(|| format!("failed to read markdown file '{}'", all_md_path.display()))?;
+        trace!("Read markdown file ({} bytes)", markdown_content.len());
 
-    println!("{}", markdown_content);
+        writeln!(out, "{}", markdown_content)?;
+        return Ok(());
+    }
+
+    let matcher = Matcher::new(filter, lib_name)?;
+    // A crate listing spans every module, where re-exported paths would list
+    // the same items twice.
+    let crate_listing = path == lib_name && !tree;
+    let modules = module_tree(&crate_metadata, !crate_listing);
+    if !modules.contains_key(path) {
+        if crate_metadata.item_map.contains_key(path) || crate_metadata.alias_map.contains_key(path)
+        {
+            bail!(
+                "'{}' is an item, not a module. Try: `cargo txt show {}` to view it.",
+                path,
+                qualified_path(&lib_dir, path)
+            );
+        }
+        bail!(
+            "could not find module '{}'. Try: `cargo txt list {} --tree` to see all modules.",
+            path,
+            qualified_path(&lib_dir, lib_name)
+        );
+    }
+
+    let no_match = || {
+        anyhow!(
+            "no items of '{}' match the filters. Try: `cargo txt list {} --tree` to see all modules.",
+            path,
+            qualified_path(&lib_dir, lib_name)
+        )
+    };
+
+    if tree {
+        let tree = module_tree_entries(&modules, path, &matcher);
+        if !filter.is_empty() && tree.iter().all(|(_, _, count)| *count == 0) {
+            return Err(no_match());
+        }
+        return match format {
+            OutputFormat::Markdown => {
+                writeln!(out, "{}", format_tree(&lib_dir, path, &tree))?;
+                Ok(())
+            }
+            OutputFormat::Json => {
+                let entries = tree
+                    .iter()
+                    .map(|(module, _, count)| ListEntry {
+                        item_count: Some(*count),
+                        ..ListEntry::new(&crate_metadata, module, Some(ItemKind::Module))
+                    })
+                    .collect();
+                write_json(out, &ListReport { path, entries })
+            }
+        };
+    }
+
+    let entries = if crate_listing {
+        crate_entries(&modules, path, &matcher)
+    } else {
+        module_entries(&modules, path, &matcher)
+    };
+    if entries.is_empty() {
+        return Err(no_match());
+    }
+    match format {
+        OutputFormat::Markdown => writeln!(
+            out,
+            "{}",
+            format_listing(&crate_metadata, &lib_dir, path, &entries)
+        )?,
+        OutputFormat::Json => {
+            let entries = entries
+                .iter()
+                .map(|(entry, kind)| ListEntry::new(&crate_metadata, entry, *kind))
+                .collect();
+            write_json(out, &ListReport { path, entries })?;
+        }
+    }
 
     Ok(())
 }
 
+/// Listing printed by `list --format json`.
+#[derive(Debug, Serialize)]
+struct ListReport<'a> {
+    /// The listed library or module path
+    path: &'a str,
+    /// The listed modules and items
+    entries: Vec<ListEntry<'a>>,
+}
+
+/// A module or item of a listing.
+#[derive(Debug, Serialize)]
+struct ListEntry<'a> {
+    /// Full path of the module or item
+    path: &'a str,
+    /// Kind of the item, `None` when unknown
+    kind: Option<ItemKind>,
+    /// Markdown file of the item, relative to `docmd/<lib_name>`. Only the
+    /// crate root has a module page.
+    file: Option<&'a str>,
+    /// Canonical path of a re-exported item
+    #[serde(skip_serializing_if = "Option::is_none")]
+    canonical: Option<&'a str>,
+    /// First sentence of the item documentation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<&'a str>,
+    /// Number of direct child items that match the filters, for the modules
+    /// of a tree
+    #[serde(skip_serializing_if = "Option::is_none")]
+    item_count: Option<usize>,
+}
+
+impl<'a> ListEntry<'a> {
+    /// Create the entry of a path, looking up its file in the metadata.
+    fn new(crate_metadata: &'a CrateDocMetadata, path: &'a str, kind: Option<ItemKind>) -> Self {
+        let canonical = crate_metadata.alias_map.get(path).map(String::as_str);
+        let file = if path == crate_metadata.lib_name {
+            Some("index.md")
+        } else {
+            crate_metadata
+                .item_map
+                .get(canonical.unwrap_or(path))
+                .map(String::as_str)
+        };
+        ListEntry {
+            path,
+            kind,
+            file,
+            canonical,
+            summary: item_summary(crate_metadata, path),
+            item_count: None,
+        }
+    }
+}
+
+/// Return the summary of an item, following re-exports to the canonical item.
+fn item_summary<'a>(crate_metadata: &'a CrateDocMetadata, path: &str) -> Option<&'a str> {
+    let canonical = crate_metadata
+        .alias_map
+        .get(path)
+        .map_or(path, String::as_str);
+    crate_metadata.items.get(canonical)?.summary.as_deref()
+}
+
+/// Derive the modules of a crate from the item paths of its metadata.
+///
+/// Every parent path of an item is a module. With `include_aliases`,
+/// re-exported paths from the alias map are listed too, with the kind of the
+/// item they resolve to.
+fn module_tree(
+    crate_metadata: &CrateDocMetadata,
+    include_aliases: bool,
+) -> BTreeMap<String, Module> {
+    let mut modules: BTreeMap<String, Module> = BTreeMap::new();
+    modules.entry(crate_metadata.lib_name.clone()).or_default();
+
+    let aliases = crate_metadata
+        .alias_map
+        .iter()
+        .filter(|_| include_aliases)
+        .filter_map(|(alias, canonical)| {
+            crate_metadata
+                .item_map
+                .get(canonical)
+                .map(|file| (alias, file))
+        });
+    for (path, file) in crate_metadata.item_map.iter().chain(aliases) {
+        let Some((parent, _)) = path.rsplit_once("::") else {
+            continue;
+        };
+        modules
+            .entry(parent.to_string())
+            .or_default()
+            .items
+            .push((path.clone(), ItemKind::from_file(file)));
+
+        let mut child = parent;
+        while let Some((ancestor, _)) = child.rsplit_once("::") {
+            modules
+                .entry(ancestor.to_string())
+                .or_default()
+                .submodules
+                .insert(child.to_string());
+            child = ancestor;
+        }
+    }
+
+    for module in modules.values_mut() {
+        module.items.sort();
+    }
+    modules
+}
+
+/// Return the direct submodules and items of a module that match.
+fn module_entries<'a>(
+    modules: &'a BTreeMap<String, Module>,
+    path: &str,
+    matcher: &Matcher,
+) -> Vec<(&'a str, Option<ItemKind>)> {
+    let module = &modules[path];
+    let submodules = module
+        .submodules
+        .iter()
+        .map(|submodule| (submodule.as_str(), Some(ItemKind::Module)));
+    let items = module
+        .items
+        .iter()
+        .map(|(path, kind)| (path.as_str(), *kind));
+    submodules
+        .chain(items)
+        .filter(|(path, kind)| matcher.matches(path, *kind))
+        .collect()
+}
+
+/// Return every module and item of a crate that match, sorted by path.
+fn crate_entries<'a>(
+    modules: &'a BTreeMap<String, Module>,
+    lib_name: &str,
+    matcher: &Matcher,
+) -> Vec<(&'a str, Option<ItemKind>)> {
+    let submodules = modules
+        .keys()
+        .filter(|path| *path != lib_name)
+        .map(|path| (path.as_str(), Some(ItemKind::Module)));
+    let items = modules
+        .values()
+        .flat_map(|module| module.items.iter())
+        .map(|(path, kind)| (path.as_str(), *kind));
+    let mut entries: Vec<(&str, Option<ItemKind>)> = submodules
+        .chain(items)
+        .filter(|(path, kind)| matcher.matches(path, *kind))
+        .collect();
+    entries.sort();
+    entries
+}
+
+/// Format entries under `title`, grouped by kind and followed by their summaries.
+///
+/// Paths are printed as `show` resolves them in the library of `lib_dir`.
+fn format_listing(
+    crate_metadata: &CrateDocMetadata,
+    lib_dir: &Path,
+    title: &str,
+    entries: &[(&str, Option<ItemKind>)],
+) -> String {
+    let mut grouped: BTreeMap<Option<ItemKind>, Vec<&str>> = BTreeMap::new();
+    for (path, kind) in entries {
+        grouped.entry(*kind).or_default().push(path);
+    }
+
+    let mut output = format!("# {}\n\n", qualified_path(lib_dir, title));
+    // Items of unknown kinds sort first, but are listed last.
+    let other = grouped.remove(&None);
+    let groups = grouped
+        .into_iter()
+        .map(|(kind, paths)| (kind.map_or("Other Items", ItemKind::title), paths))
+        .chain(other.map(|paths| ("Other Items", paths)));
+    for (title, mut paths) in groups {
+        paths.sort();
+        output.push_str(&format!("### {}\n\n", title));
+        for path in paths {
+            let shown = qualified_path(lib_dir, path);
+            match item_summary(crate_metadata, path) {
+                Some(summary) => output.push_str(&format!("- {} — {}\n", shown, summary)),
+                None => output.push_str(&format!("- {}\n", shown)),
+            }
+        }
+        output.push('\n');
+    }
+    output
+}
+
+/// Return the modules of the hierarchy below a module, depth first, each
+/// with its depth and the number of its direct child items that match.
+fn module_tree_entries<'a>(
+    modules: &'a BTreeMap<String, Module>,
+    path: &'a str,
+    matcher: &Matcher,
+) -> Vec<(&'a str, usize, usize)> {
+    let mut entries = Vec::new();
+    let mut pending = vec![(path, 0)];
+    while let Some((path, depth)) = pending.pop() {
+        let module = &modules[path];
+        let count = module
+            .items
+            .iter()
+            .filter(|(path, kind)| matcher.matches(path, *kind))
+            .count();
+        entries.push((path, depth, count));
+        pending.extend(
+            module
+                .submodules
+                .iter()
+                .rev()
+                .map(|submodule| (submodule.as_str(), depth + 1)),
+        );
+    }
+    entries
+}
+
+/// Format a module hierarchy as a nested list, with item counts.
+///
+/// Paths are printed as `list` resolves them in the library of `lib_dir`.
+fn format_tree(lib_dir: &Path, title: &str, entries: &[(&str, usize, usize)]) -> String {
+    let mut output = format!("# {}\n\n", qualified_path(lib_dir, title));
+    for (path, depth, count) in entries {
+        output.push_str(&format!(
+            "{}- {} ({} {})\n",
+            "  ".repeat(*depth),
+            qualified_path(lib_dir, path),
+            count,
+            if *count == 1 { "item" } else { "items" }
+        ));
+    }
+    output
+}
+
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
     use std::fs;
 
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::commands::build::{BuildFingerprint, temp_docmd};
+    use crate::item_info::ItemInfo;
+
+    fn probe_metadata() -> CrateDocMetadata {
+        let map = |entries: &[(&str, &str)]| -> HashMap<String, String> {
+            entries
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect()
+        };
+        CrateDocMetadata {
+            crate_name: "probe".to_string(),
+            lib_name: "probe".to_string(),
+            item_map: map(&[
+                ("probe::Widget", "struct.Widget.md"),
+                ("probe::Kind", "trait.Kind.md"),
+                ("probe::task::spawn", "task/fn.spawn.md"),
+                ("probe::task::nested::Deep", "task/nested/struct.Deep.md"),
+            ]),
+            alias_map: map(&[
+                ("probe::spawn", "probe::task::spawn"),
+                ("probe::util::Gadget", "probe::Widget"),
+            ]),
+            items: HashMap::from([(
+                "probe::Widget".to_string(),
+                ItemInfo {
+                    kind: Some(ItemKind::Struct),
+                    summary: Some("A widget.".to_string()),
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        }
+    }
+
+    fn filter(kinds: &[ItemKind], pattern: Option<&str>, module: Option<&str>) -> ListFilter {
+        ListFilter {
+            kinds: kinds.to_vec(),
+            pattern: pattern.map(str::to_string),
+            regex: false,
+            module: module.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn module_tree_from_item_paths() {
+        let modules = module_tree(&probe_metadata(), true);
+        assert_eq!(
+            modules.keys().collect::<Vec<_>>(),
+            vec!["probe", "probe::task", "probe::task::nested", "probe::util"]
+        );
+        assert_eq!(
+            modules["probe"],
+            Module {
+                submodules: BTreeSet::from(["probe::task".to_string(), "probe::util".to_string()]),
+                items: vec![
+                    ("probe::Kind".to_string(), Some(ItemKind::Trait)),
+                    ("probe::Widget".to_string(), Some(ItemKind::Struct)),
+                    ("probe::spawn".to_string(), Some(ItemKind::Fn)),
+                ],
+            }
+        );
+
+        let modules = module_tree(&probe_metadata(), false);
+        assert_eq!(
+            modules.keys().collect::<Vec<_>>(),
+            vec!["probe", "probe::task", "probe::task::nested"]
+        );
+    }
+
+    #[test]
+    fn format_module_groups_children_by_kind() {
+        let metadata = probe_metadata();
+        let modules = module_tree(&metadata, true);
+        let no_filter = ListFilter::default();
+        let matcher = Matcher::new(&no_filter, "probe").unwrap();
+        assert_eq!(
+            format_listing(
+                &metadata,
+                Path::new("probe"),
+                "probe",
+                &module_entries(&modules, "probe", &matcher)
+            ),
+            concat!(
+                "# probe\n\n",
+                "### Modules\n\n- probe::task\n- probe::util\n\n",
+                "### Structs\n\n- probe::Widget — A widget.\n\n",
+                "### Traits\n\n- probe::Kind\n\n",
+                "### Functions\n\n- probe::spawn\n\n",
+            )
+        );
+        let nested = "probe::task::nested";
+        assert_eq!(
+            format_listing(
+                &metadata,
+                Path::new("probe"),
+                nested,
+                &module_entries(&modules, nested, &matcher)
+            ),
+            "# probe::task::nested\n\n### Structs\n\n- probe::task::nested::Deep\n\n"
+        );
+
+        let structs = filter(&[ItemKind::Struct], None, None);
+        let matcher = Matcher::new(&structs, "probe").unwrap();
+        assert_eq!(
+            module_entries(&modules, "probe", &matcher),
+            vec![("probe::Widget", Some(ItemKind::Struct))]
+        );
+        assert!(module_entries(&modules, "probe::task", &matcher).is_empty());
+    }
+
+    #[test]
+    fn crate_entries_filter_items() {
+        let metadata = probe_metadata();
+        let modules = module_tree(&metadata, false);
+
+        let pattern = filter(&[], Some("*e*"), None);
+        let matcher = Matcher::new(&pattern, "probe").unwrap();
+        assert_eq!(
+            format_listing(
+                &metadata,
+                Path::new("probe"),
+                "probe",
+                &crate_entries(&modules, "probe", &matcher)
+            ),
+            concat!(
+                "# probe\n\n",
+                "### Modules\n\n- probe::task::nested\n\n",
+                concat!(
+                    "### Structs\n\n",
+                    "- probe::Widget — A widget.\n",
+                    "- probe::task::nested::Deep\n\n",
+                ),
+            )
+        );
+
+        let module = filter(&[ItemKind::Fn, ItemKind::Struct], None, Some("task"));
+        let matcher = Matcher::new(&module, "probe").unwrap();
+        assert_eq!(
+            crate_entries(&modules, "probe", &matcher),
+            vec![
+                ("probe::task::nested::Deep", Some(ItemKind::Struct)),
+                ("probe::task::spawn", Some(ItemKind::Fn)),
+            ]
+        );
+    }
+
+    #[test]
+    fn format_tree_counts_items() {
+        let modules = module_tree(&probe_metadata(), true);
+        let no_filter = ListFilter::default();
+        let matcher = Matcher::new(&no_filter, "probe").unwrap();
+        assert_eq!(
+            format_tree(
+                Path::new("probe"),
+                "probe",
+                &module_tree_entries(&modules, "probe", &matcher)
+            ),
+            concat!(
+                "# probe\n\n",
+                "- probe (3 items)\n",
+                "  - probe::task (1 item)\n",
+                "    - probe::task::nested (1 item)\n",
+                "  - probe::util (1 item)\n",
+            )
+        );
+
+        let traits = filter(&[ItemKind::Trait], None, None);
+        let matcher = Matcher::new(&traits, "probe").unwrap();
+        assert_eq!(
+            module_tree_entries(&modules, "probe::task", &matcher),
+            vec![("probe::task", 0, 0), ("probe::task::nested", 1, 0)]
+        );
+    }
+
+    #[test]
+    fn list_entry_records() {
+        let metadata = probe_metadata();
+        let entry =
+            |path, kind| serde_json::to_value(ListEntry::new(&metadata, path, kind)).unwrap();
+        assert_eq!(
+            entry("probe::Widget", Some(ItemKind::Struct)),
+            serde_json::json!({
+                "path": "probe::Widget",
+                "kind": "struct",
+                "file": "struct.Widget.md",
+                "summary": "A widget.",
+            })
+        );
+        assert_eq!(
+            entry("probe::util::Gadget", Some(ItemKind::Struct)),
+            serde_json::json!({
+                "path": "probe::util::Gadget",
+                "kind": "struct",
+                "file": "struct.Widget.md",
+                "canonical": "probe::Widget",
+                "summary": "A widget.",
+            })
+        );
+        assert_eq!(
+            entry("probe::task", Some(ItemKind::Module)),
+            serde_json::json!({ "path": "probe::task", "kind": "module", "file": null })
+        );
+        assert_eq!(entry("probe", Some(ItemKind::Module))["file"], "index.md");
+    }
+
+    /// Write `probe_metadata` as a built library of a temporary `docmd`.
+    fn probe_docmd() -> tempfile::TempDir {
+        temp_docmd(&[("probe", &probe_metadata())])
+    }
+
+    fn list_json(docmd_dir: &Path, path: &str, tree: bool, filter: &ListFilter) -> Value {
+        let mut out = Vec::new();
+        list_in(docmd_dir, path, tree, filter, OutputFormat::Json, &mut out).unwrap();
+        serde_json::from_slice(&out).unwrap()
+    }
+
+    #[test]
+    fn list_json_reports() {
+        let docmd_dir = probe_docmd();
+        let docmd_dir = docmd_dir.path();
+
+        let report = list_json(docmd_dir, "probe::task", false, &ListFilter::default());
+        assert_eq!(
+            report,
+            json!({
+                "path": "probe::task",
+                "entries": [
+                    { "path": "probe::task::nested", "kind": "module", "file": null },
+                    {
+                        "path": "probe::task::spawn",
+                        "kind": "fn",
+                        "file": "task/fn.spawn.md",
+                    },
+                ],
+            })
+        );
+
+        let report = list_json(
+            docmd_dir,
+            "probe",
+            false,
+            &filter(&[ItemKind::Struct], None, None),
+        );
+        assert_eq!(report["path"], "probe");
+        assert_eq!(
+            report["entries"],
+            json!([
+                {
+                    "path": "probe::Widget",
+                    "kind": "struct",
+                    "file": "struct.Widget.md",
+                    "summary": "A widget.",
+                },
+                {
+                    "path": "probe::task::nested::Deep",
+                    "kind": "struct",
+                    "file": "task/nested/struct.Deep.md",
+                },
+            ])
+        );
+
+        let report = list_json(docmd_dir, "probe", true, &ListFilter::default());
+        let counts: Vec<(&str, u64)> = report["entries"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entry| {
+                assert_eq!(entry["kind"], "module");
+                (
+                    entry["path"].as_str().unwrap(),
+                    entry["item_count"].as_u64().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            counts,
+            vec![
+                ("probe", 3),
+                ("probe::task", 1),
+                ("probe::task::nested", 1),
+                ("probe::util", 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn list_versioned_paths_resolve_to_the_listed_version() {
+        let versioned = |version: &str| CrateDocMetadata {
+            fingerprint: BuildFingerprint {
+                version: version.to_string(),
+                ..Default::default()
+            },
+            ..probe_metadata()
+        };
+        let docmd_dir = temp_docmd(&[
+            ("probe", &versioned("2.0.0")),
+            ("probe@1.0.5", &versioned("1.0.5")),
+        ]);
+        let docmd_dir = docmd_dir.path();
+
+        let list_markdown = |path: &str, tree: bool| {
+            let mut out = Vec::new();
+            let filter = filter(&[ItemKind::Struct], None, None);
+            list_in(
+                docmd_dir,
+                path,
+                tree,
+                &filter,
+                OutputFormat::Markdown,
+                &mut out,
+            )
+            .unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        let listing = list_markdown("probe@1", false) + &list_markdown("probe@1::task", true);
+        let printed: Vec<&str> = listing
+            .lines()
+            .filter_map(|line| line.trim_start().strip_prefix("- "))
+            .map(|entry| entry.split(' ').next().unwrap())
+            .collect();
+        assert!(printed.contains(&"probe@1.0.5::Widget"));
+        assert!(printed.contains(&"probe@1.0.5::task::nested"));
+
+        let metadata = probe_metadata();
+        for path in printed {
+            let (spec, rest) = path.split_once("::").unwrap();
+            let (lib_name, version) = spec.split_once('@').unwrap();
+            assert_eq!(
+                find_doc_dir(docmd_dir, lib_name, Some(version)),
+                Some(docmd_dir.join("probe@1.0.5")),
+                "{} resolves to another version",
+                path
+            );
+            let unversioned = format!("{}::{}", lib_name, rest);
+            assert!(
+                metadata.item_map.contains_key(&unversioned)
+                    || metadata
+                        .item_map
+                        .keys()
+                        .any(|item| item.starts_with(&format!("{}::", unversioned))),
+                "{} is not an item or module",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn list_json_errors() {
+        let docmd_dir = probe_docmd();
+        let mut out = Vec::new();
+
+        let error = list_in(
+            docmd_dir.path(),
+            "probe::Widget",
+            false,
+            &ListFilter::default(),
+            OutputFormat::Json,
+            &mut out,
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("is an item, not a module"));
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn matcher_patterns() {
+        let glob = filter(&[], Some("*stream?"), None);
+        let matcher = Matcher::new(&glob, "tokio").unwrap();
+        assert!(matcher.matches("tokio::io::ReadStreamX", None));
+        assert!(!matcher.matches("tokio::io::ReadStream", None));
+        assert!(!matcher.matches("tokio::stream::Other", None));
+
+        let regex = ListFilter {
+            pattern: Some("^Async(Read|Write)$".to_string()),
+            regex: true,
+            module: Some("tokio::io".to_string()),
+            ..Default::default()
+        };
+        let matcher = Matcher::new(&regex, "tokio").unwrap();
+        assert!(matcher.matches("tokio::io::AsyncRead", Some(ItemKind::Trait)));
+        assert!(!matcher.matches("tokio::io::AsyncReadExt", Some(ItemKind::Trait)));
+        assert!(!matcher.matches("tokio::net::AsyncRead", Some(ItemKind::Trait)));
+
+        let invalid = ListFilter {
+            pattern: Some("(".to_string()),
+            regex: true,
+            ..Default::default()
+        };
+        assert!(Matcher::new(&invalid, "tokio").is_err());
+    }
+
+    #[test]
+    fn glob_to_regex_escapes_metacharacters() {
+        assert_eq!(glob_to_regex("*Stream*"), "^.*Stream.*$");
+        assert_eq!(glob_to_regex("a.b?"), "^a\\.b.$");
+    }
+
+    #[test]
+    fn item_kind_from_file() {
+        assert_eq!(
+            ItemKind::from_file("struct.Widget.md"),
+            Some(ItemKind::Struct)
+        );
+        assert_eq!(
+            ItemKind::from_file("de/value/struct.Error.md"),
+            Some(ItemKind::Struct)
+        );
+        assert_eq!(
+            ItemKind::from_file("macro.forward_to_deserialize_any.md"),
+            Some(ItemKind::Macro)
+        );
+        assert_eq!(ItemKind::from_file("index.md"), None);
+    }
+
     #[test]
     fn list_succeeds_when_metadata_exists() {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -0,0 +1,759 @@
+//! MCP server implementation.
+//!
+//! This module provides the mcp command which serves the Model Context
+//! Protocol over stdio: one JSON-RPC message per line on stdin, one response
+//! per line on stdout, and logs on stderr. The `build_crate_docs`,
+//! `list_items`, `show_item` and `search_docs` tools run the build, list,
+//! show and search commands with JSON output, and every markdown file of the
+//! built documentation is a resource with a `docmd://<lib>/<file>` URI.
+
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use tracing::{debug, info, trace};
+
+use crate::cargo::{self, DocOptions};
+use crate::commands::OutputFormat;
+use crate::commands::build::{self, Backend};
+use crate::commands::list::{self, ItemKind, ListFilter};
+use crate::commands::search;
+use crate::commands::show::{self, ShowSection};
+
+/// Protocol versions the server speaks, newest first.
+const PROTOCOL_VERSIONS: [&str; 3] = ["2025-06-18", "2025-03-26", "2024-11-05"];
+
+/// Maximum number of resources returned by a `resources/list` request.
+const RESOURCE_PAGE_SIZE: usize = 500;
+
+/// URI scheme of the markdown files under `docmd`.
+const RESOURCE_SCHEME: &str = "docmd://";
+
+/// JSON-RPC error codes.
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+const RESOURCE_NOT_FOUND: i64 = -32002;
+
+/// An error sent back to the client as a JSON-RPC error response.
+#[derive(Debug, PartialEq)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        RpcError {
+            code,
+            message: message.into(),
+        }
+    }
+
+    /// Create an internal error from a failure of the server.
+    fn internal(error: anyhow::Error) -> Self {
+        RpcError::new(INTERNAL_ERROR, format!("{:#}", error))
+    }
+}
+
+/// Arguments of the `build_crate_docs` tool.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BuildArgs {
+    #[serde(rename = "crate")]
+    crate_name: String,
+    #[serde(default)]
+    features: Vec<String>,
+    #[serde(default)]
+    all_features: bool,
+    #[serde(default)]
+    no_default_features: bool,
+    #[serde(default)]
+    target: Option<String>,
+    #[serde(default)]
+    docsrs: bool,
+    #[serde(default)]
+    backend: Backend,
+}
+
+/// Arguments of the `list_items` tool.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ListArgs {
+    path: String,
+    #[serde(default)]
+    tree: bool,
+    #[serde(default)]
+    kinds: Vec<ItemKind>,
+    #[serde(default)]
+    filter: Option<String>,
+    #[serde(default)]
+    regex: bool,
+    #[serde(default)]
+    module: Option<String>,
+}
+
+/// Arguments of the `show_item` tool.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ShowArgs {
+    item: String,
+    #[serde(default)]
+    sections: Vec<ShowSection>,
+    #[serde(default)]
+    signature_only: bool,
+}
+
+/// Arguments of the `search_docs` tool.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SearchArgs {
+    query: String,
+    #[serde(default)]
+    lib: Option<String>,
+    #[serde(default = "default_search_limit")]
+    limit: usize,
+}
+
+fn default_search_limit() -> usize {
+    20
+}
+
+/// Serve the Model Context Protocol on stdin and stdout.
+///
+/// The server runs until stdin is closed. Requests are handled one at a
+/// time, in the current directory, like the other commands.
+pub fn mcp() -> Result<()> {
+    info!("Serving MCP on stdio");
+    serve(io::stdin().lock(), &mut io::stdout())
+}
+
+/// Answer the JSON-RPC messages of `input` on `output`.
+fn serve(input: impl BufRead, output: &mut impl Write) -> Result<()> {
+    for line in input.lines() {
+        let line = line.context("failed to read MCP message")?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        trace!("Received: {}", line);
+
+        if let Some(response) = handle_message(&line) {
+            let response =
+                serde_json::to_string(&response).context("failed to serialize MCP response")?;
+            trace!("Sending: {}", response);
+            writeln!(output, "{}", response)?;
+            output.flush()?;
+        }
+    }
+
+    debug!("Input closed, stopping the MCP server");
+    Ok(())
+}
+
+/// Handle a JSON-RPC message and return its response.
+///
+/// Notifications, and responses to requests the server never sends, have
+/// no response.
+fn handle_message(line: &str) -> Option<Value> {
+    let message: Value = match serde_json::from_str(line) {
+        Ok(message) => message,
+        Err(e) => {
+            return Some(error_response(
+                Value::Null,
+                RpcError::new(PARSE_ERROR, format!("invalid JSON: {}", e)),
+            ));
+        }
+    };
+
+    let id = message.get("id").cloned();
+    let Some(method) = message.get("method").and_then(Value::as_str) else {
+        if message.get("result").is_some() || message.get("error").is_some() {
+            return None;
+        }
+        return Some(error_response(
+            id.unwrap_or(Value::Null),
+            RpcError::new(INVALID_REQUEST, "expected a JSON-RPC request"),
+        ));
+    };
+    let Some(id) = id else {
+        debug!("Received notification '{}'", method);
+        return None;
+    };
+
+    let params = message.get("params").cloned().unwrap_or_else(|| json!({}));
+    debug!("Received request '{}'", method);
+    Some(match handle_request(method, params) {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(e) => error_response(id, e),
+    })
+}
+
+/// Return the JSON-RPC error response to the request `id`.
+fn error_response(id: Value, error: RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": error.code, "message": error.message },
+    })
+}
+
+/// Dispatch a request to its handler.
+fn handle_request(method: &str, params: Value) -> Result<Value, RpcError> {
+    match method {
+        "initialize" => Ok(initialize(&params)),
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(json!({ "tools": tools() })),
+        "tools/call" => call_tool(params),
+        "resources/list" => list_resources(&params),
+        "resources/templates/list" => Ok(json!({ "resourceTemplates": [] })),
+        "resources/read" => read_resource(&params),
+        _ => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("method '{}' not found", method),
+        )),
+    }
+}
+
+/// Deserialize the parameters of a request, or the arguments of a tool.
+fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    serde_json::from_value(params)
+        .map_err(|e| RpcError::new(INVALID_PARAMS, format!("invalid parameters: {}", e)))
+}
+
+/// Answer the `initialize` request.
+///
+/// The protocol version requested by the client is accepted when the server
+/// speaks it, otherwise the newest version is proposed.
+fn initialize(params: &Value) -> Value {
+    let requested = params.get("protocolVersion").and_then(Value::as_str);
+    let protocol_version = requested
+        .filter(|version| PROTOCOL_VERSIONS.contains(version))
+        .unwrap_or(PROTOCOL_VERSIONS[0]);
+
+    json!({
+        "protocolVersion": protocol_version,
+        "capabilities": {
+            "tools": { "listChanged": false },
+            "resources": { "listChanged": false, "subscribe": false },
+        },
+        "serverInfo": {
+            "name": "cargo-txt",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "instructions": concat!(
+            "Rust crate documentation as markdown. Build the documentation of a ",
+            "dependency with build_crate_docs, then find items with list_items or ",
+            "search_docs and read them with show_item. Item paths use library ",
+            "names, with underscores (e.g., 'rustdoc_types::Item')."
+        ),
+    })
+}
+
+/// Return the tools of the server with the JSON schemas of their arguments.
+fn tools() -> Value {
+    json!([
+        {
+            "name": "build_crate_docs",
+            "description": "Generate the markdown documentation of a crate: a dependency, a workspace member or std, core, alloc, proc_macro, test. Returns the item counts and rustdoc warnings.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "crate": { "type": "string", "description": "Crate name, optionally with a version (e.g., 'serde' or 'syn@1')" },
+                    "features": { "type": "array", "items": { "type": "string" }, "description": "Features of the crate to document" },
+                    "all_features": { "type": "boolean", "description": "Document every feature of the crate" },
+                    "no_default_features": { "type": "boolean", "description": "Do not enable the default feature of the crate" },
+                    "target": { "type": "string", "description": "Target triple to document for (e.g., 'x86_64-pc-windows-msvc')" },
+                    "docsrs": { "type": "boolean", "description": "Build like docs.rs, with the crate's docs.rs settings (requires nightly)" },
+                    "backend": { "type": "string", "enum": ["html", "json"], "description": "Documentation source to convert (default: html)" },
+                },
+                "required": ["crate"],
+            },
+        },
+        {
+            "name": "list_items",
+            "description": "List the items of a built library or module, with their kinds and summaries, or the module tree.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "path": { "type": "string", "description": "Library name or module path (e.g., 'serde', 'serde::de' or 'syn@1')" },
+                    "tree": { "type": "boolean", "description": "Return the module hierarchy with the number of items of each module" },
+                    "kinds": { "type": "array", "items": { "type": "string", "enum": item_kind_names() }, "description": "Only list items of these kinds" },
+                    "filter": { "type": "string", "description": "Only list items whose name matches this glob (e.g., '*Stream*')" },
+                    "regex": { "type": "boolean", "description": "Interpret filter as a regular expression" },
+                    "module": { "type": "string", "description": "Only list items below this module (e.g., 'io' or 'tokio::io')" },
+                },
+                "required": ["path"],
+            },
+        },
+        {
+            "name": "show_item",
+            "description": "Show the markdown documentation of a crate, item or member, such as a method, variant or field.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "item": { "type": "string", "description": "Item identifier (e.g., 'serde', 'serde::Deserialize' or 'serde::Serializer::serialize_str')" },
+                    "sections": { "type": "array", "items": { "type": "string", "enum": ["description", "methods", "impls", "examples"] }, "description": "Only return these sections of the page" },
+                    "signature_only": { "type": "boolean", "description": "Only return signatures" },
+                },
+                "required": ["item"],
+            },
+        },
+        {
+            "name": "search_docs",
+            "description": "Search item paths and documentation across the built libraries.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string", "description": "Words or identifier to search for (e.g., 'backpressure' or 'AsyncRead')" },
+                    "lib": { "type": "string", "description": "Only search this library (e.g., 'tokio' or 'syn@1')" },
+                    "limit": { "type": "integer", "minimum": 1, "description": "Maximum number of results (default: 20)" },
+                },
+                "required": ["query"],
+            },
+        },
+    ])
+}
+
+/// Return the names of the item kinds accepted by `list_items`.
+fn item_kind_names() -> Vec<Value> {
+    use clap::ValueEnum;
+
+    ItemKind::value_variants()
+        .iter()
+        .filter_map(|kind| serde_json::to_value(kind).ok())
+        .collect()
+}
+
+/// Answer the `tools/call` request.
+///
+/// A failing command is reported as a tool result with `isError`, so the
+/// model can read the error message. Unknown tools and invalid arguments are
+/// protocol errors.
+fn call_tool(params: Value) -> Result<Value, RpcError> {
+    #[derive(Deserialize)]
+    struct CallParams {
+        name: String,
+        #[serde(default)]
+        arguments: Option<Value>,
+    }
+
+    let call: CallParams = parse_params(params)?;
+    let arguments = call.arguments.unwrap_or_else(|| json!({}));
+    debug!("Calling tool '{}' with {}", call.name, arguments);
+
+    let mut out = Vec::new();
+    let result = match call.name.as_str() {
+        "build_crate_docs" => build_crate_docs(parse_params(arguments)?, &mut out),
+        "list_items" => list_items(parse_params(arguments)?, &mut out),
+        "show_item" => show_item(parse_params(arguments)?, &mut out),
+        "search_docs" => search_docs(parse_params(arguments)?, &mut out),
+        name => {
+            return Err(RpcError::new(
+                INVALID_PARAMS,
+                format!("unknown tool '{}'", name),
+            ));
+        }
+    };
+
+    Ok(tool_result(&call.name, result, &out))
+}
+
+/// Turn the output of a command into a tool result.
+///
+/// The JSON report of the command is the structured content of the result.
+/// Its text content is the same JSON, except for `show_item` where it is the
+/// markdown of the item.
+fn tool_result(tool: &str, result: Result<()>, out: &[u8]) -> Value {
+    if let Err(e) = result {
+        return json!({
+            "content": [{ "type": "text", "text": format!("{:#}", e) }],
+            "isError": true,
+        });
+    }
+
+    let text = String::from_utf8_lossy(out).trim_end().to_string();
+    let structured: Option<Value> = serde_json::from_str(&text).ok();
+    let text = match structured
+        .as_ref()
+        .and_then(|report| report.get("markdown"))
+    {
+        Some(Value::String(markdown)) if tool == "show_item" => markdown.clone(),
+        _ => text,
+    };
+
+    let mut result = json!({
+        "content": [{ "type": "text", "text": text }],
+        "isError": false,
+    });
+    if let Some(structured) = structured {
+        result["structuredContent"] = structured;
+    }
+    result
+}
+
+fn build_crate_docs(args: BuildArgs, out: &mut dyn Write) -> Result<()> {
+    let doc_options = DocOptions {
+        features: args.features,
+        all_features: args.all_features,
+        no_default_features: args.no_default_features,
+        target: args.target,
+        docsrs: args.docsrs,
+        ..Default::default()
+    };
+    build::build(
+        &args.crate_name,
+        None,
+        args.backend,
+        &[],
+        &doc_options,
+        OutputFormat::Json,
+        out,
+    )
+}
+
+fn list_items(args: ListArgs, out: &mut dyn Write) -> Result<()> {
+    let filter = ListFilter {
+        kinds: args.kinds,
+        pattern: args.filter,
+        regex: args.regex,
+        module: args.module,
+    };
+    list::list(&args.path, args.tree, &filter, OutputFormat::Json, out)
+}
+
+fn show_item(args: ShowArgs, out: &mut dyn Write) -> Result<()> {
+    show::show(
+        &args.item,
+        &args.sections,
+        args.signature_only,
+        OutputFormat::Json,
+        out,
+    )
+}
+
+fn search_docs(args: SearchArgs, out: &mut dyn Write) -> Result<()> {
+    search::search(
+        &args.query,
+        args.lib.as_deref(),
+        args.limit,
+        OutputFormat::Json,
+        out,
+    )
+}
+
+/// Return the `docmd` directory of the current project.
+fn docmd_dir() -> Result<PathBuf> {
+    let metadata = cargo::metadata()?;
+    Ok(PathBuf::from(&metadata.target_directory).join("docmd"))
+}
+
+/// Answer the `resources/list` request.
+///
+/// Every library contributes its overview, `index.md`, its list of items,
+/// `all.md`, and the page of every item, named after the item path. The
+/// cursor of the next page is the offset of its first resource.
+fn list_resources(params: &Value) -> Result<Value, RpcError> {
+    let offset = match params.get("cursor").and_then(Value::as_str) {
+        Some(cursor) => cursor
+            .parse::<usize>()
+            .map_err(|_| RpcError::new(INVALID_PARAMS, format!("invalid cursor '{}'", cursor)))?,
+        None => 0,
+    };
+
+    let resources = docmd_dir()
+        .and_then(|docmd_dir| doc_resources(&docmd_dir))
+        .map_err(RpcError::internal)?;
+    let page: Vec<&Value> = resources
+        .iter()
+        .skip(offset)
+        .take(RESOURCE_PAGE_SIZE)
+        .collect();
+
+    let mut result = json!({ "resources": page });
+    if offset + RESOURCE_PAGE_SIZE < resources.len() {
+        result["nextCursor"] = json!((offset + RESOURCE_PAGE_SIZE).to_string());
+    }
+    Ok(result)
+}
+
+/// Return the resources of every built library under `docmd_dir`.
+fn doc_resources(docmd_dir: &Path) -> Result<Vec<Value>> {
+    let mut resources = Vec::new();
+    for lib_dir in search::built_lib_dirs(docmd_dir)? {
+        let Some(dir_name) = lib_dir.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        let crate_metadata = search::read_crate_metadata(&lib_dir)?;
+
+        resources.push(resource(
+            dir_name,
+            "index.md",
+            dir_name,
+            Some("Crate overview"),
+        ));
+        resources.push(resource(
+            dir_name,
+            "all.md",
+            &format!("{} (all items)", dir_name),
+            Some("List of all items"),
+        ));
+
+        let mut items: Vec<(&String, &String)> = crate_metadata.item_map.iter().collect();
+        items.sort();
+        for (path, file) in items {
+            let summary = crate_metadata
+                .items
+                .get(path)
+                .and_then(|info| info.summary.as_deref());
+            resources.push(resource(dir_name, file, path, summary));
+        }
+    }
+    Ok(resources)
+}
+
+/// Describe the markdown file `file` of the library directory `dir_name`.
+fn resource(dir_name: &str, file: &str, name: &str, description: Option<&str>) -> Value {
+    let mut resource = json!({
+        "uri": resource_uri(dir_name, file),
+        "name": name,
+        "mimeType": "text/markdown",
+    });
+    if let Some(description) = description {
+        resource["description"] = json!(description);
+    }
+    resource
+}
+
+/// Return the URI of a markdown file (e.g., "docmd://serde/trait.Serialize.md").
+///
+/// The `@` of versioned library directories is percent-encoded, so that it
+/// is not read as the user information of the URI.
+fn resource_uri(dir_name: &str, file: &str) -> String {
+    format!(
+        "{}{}/{}",
+        RESOURCE_SCHEME,
+        dir_name.replace('@', "%40"),
+        file
+    )
+}
+
+/// Return the path of the markdown file of a resource URI under `docmd_dir`.
+///
+/// Only markdown files inside a library directory are served, URIs that
+/// escape `docmd_dir` are rejected.
+fn resource_path(docmd_dir: &Path, uri: &str) -> Option<PathBuf> {
+    let relative = uri.strip_prefix(RESOURCE_SCHEME)?.replace("%40", "@");
+    let relative = Path::new(&relative);
+
+    let mut components = relative.components();
+    let all_normal = components.all(|component| matches!(component, Component::Normal(_)));
+    if !all_normal || relative.components().count() < 2 {
+        return None;
+    }
+    if relative.extension().and_then(|ext| ext.to_str()) != Some("md") {
+        return None;
+    }
+    Some(docmd_dir.join(relative))
+}
+
+/// Answer the `resources/read` request.
+fn read_resource(params: &Value) -> Result<Value, RpcError> {
+    let Some(uri) = params.get("uri").and_then(Value::as_str) else {
+        return Err(RpcError::new(INVALID_PARAMS, "missing resource uri"));
+    };
+
+    let docmd_dir = docmd_dir().map_err(RpcError::internal)?;
+    let not_found = || RpcError::new(RESOURCE_NOT_FOUND, format!("resource '{}' not found", uri));
+    let path = resource_path(&docmd_dir, uri).ok_or_else(not_found)?;
+    let text = fs::read_to_string(&path).map_err(|_| not_found())?;
+
+    Ok(json!({
+        "contents": [{ "uri": uri, "mimeType": "text/markdown", "text": text }],
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(method: &str, params: Value) -> Value {
+        let line = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        handle_message(&line.to_string()).unwrap()
+    }
+
+    #[test]
+    fn initialize_negotiates_protocol_version() {
+        let response = request("initialize", json!({ "protocolVersion": "2024-11-05" }));
+        assert_eq!(response["id"], 1);
+        assert_eq!(response["result"]["protocolVersion"], "2024-11-05");
+        assert_eq!(response["result"]["serverInfo"]["name"], "cargo-txt");
+        assert!(response["result"]["capabilities"]["tools"].is_object());
+        assert!(response["result"]["capabilities"]["resources"].is_object());
+
+        let response = request("initialize", json!({ "protocolVersion": "1999-01-01" }));
+        assert_eq!(response["result"]["protocolVersion"], PROTOCOL_VERSIONS[0]);
+    }
+
+    #[test]
+    fn tools_list_describes_every_tool() {
+        let response = request("tools/list", json!({}));
+        let tools = response["result"]["tools"].as_array().unwrap();
+        let names: Vec<&str> = tools
+            .iter()
+            .map(|tool| tool["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            ["build_crate_docs", "list_items", "show_item", "search_docs"]
+        );
+        for tool in tools {
+            assert_eq!(tool["inputSchema"]["type"], "object");
+        }
+        assert!(
+            tools[1]["inputSchema"]["properties"]["kinds"]["items"]["enum"]
+                .as_array()
+                .unwrap()
+                .contains(&json!("trait-alias"))
+        );
+    }
+
+    #[test]
+    fn invalid_messages_get_errors() {
+        let response = handle_message("{not json").unwrap();
+        assert_eq!(response["error"]["code"], PARSE_ERROR);
+        assert_eq!(response["id"], Value::Null);
+
+        let response = handle_message(r#"{"jsonrpc": "2.0", "id": 7}"#).unwrap();
+        assert_eq!(response["error"]["code"], INVALID_REQUEST);
+
+        let response = request("prompts/list", json!({}));
+        assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+
+        let response = request("tools/call", json!({ "name": "rm" }));
+        assert_eq!(response["error"]["code"], INVALID_PARAMS);
+
+        let response = request(
+            "tools/call",
+            json!({ "name": "show_item", "arguments": { "path": "serde" } }),
+        );
+        assert_eq!(response["error"]["code"], INVALID_PARAMS);
+    }
+
+    #[test]
+    fn notifications_and_responses_are_not_answered() {
+        assert_eq!(
+            handle_message(r#"{"jsonrpc": "2.0", "method": "notifications/initialized"}"#),
+            None
+        );
+        assert_eq!(
+            handle_message(r#"{"jsonrpc": "2.0", "id": 3, "result": {}}"#),
+            None
+        );
+    }
+
+    #[test]
+    fn serve_answers_one_line_per_request() {
+        let input = [
+            r#"{"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}}"#,
+            r#"{"jsonrpc": "2.0", "method": "notifications/initialized"}"#,
+            "",
+            r#"{"jsonrpc": "2.0", "id": 2, "method": "ping"}"#,
+        ]
+        .join("\n");
+        let mut output = Vec::new();
+        serve(input.as_bytes(), &mut output).unwrap();
+
+        let responses: Vec<Value> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(responses.len(), 2);
+        assert_eq!(
+            responses[1],
+            json!({ "jsonrpc": "2.0", "id": 2, "result": {} })
+        );
+    }
+
+    #[test]
+    fn tool_results_carry_structured_content() {
+        let report = json!({ "path": "probe::Widget", "markdown": "# Struct Widget" });
+        let result = tool_result("show_item", Ok(()), report.to_string().as_bytes());
+        assert_eq!(result["content"][0]["text"], "# Struct Widget");
+        assert_eq!(result["structuredContent"], report);
+        assert_eq!(result["isError"], false);
+
+        let result = tool_result("list_items", Ok(()), report.to_string().as_bytes());
+        assert_eq!(result["content"][0]["text"], report.to_string());
+
+        let error = anyhow::anyhow!("could not find module 'probe::nope'");
+        let result = tool_result("list_items", Err(error), b"");
+        assert_eq!(result["isError"], true);
+        assert_eq!(
+            result["content"][0]["text"],
+            "could not find module 'probe::nope'"
+        );
+    }
+
+    #[test]
+    fn resource_uris_stay_inside_docmd() {
+        let docmd = Path::new("/ws/target/docmd");
+
+        let uri = resource_uri("syn@1.0.109", "struct.Ident.md");
+        assert_eq!(uri, "docmd://syn%401.0.109/struct.Ident.md");
+        assert_eq!(
+            resource_path(docmd, &uri),
+            Some(docmd.join("syn@1.0.109").join("struct.Ident.md"))
+        );
+        assert_eq!(
+            resource_path(docmd, "docmd://tokio/task/fn.spawn.md"),
+            Some(docmd.join("tokio/task/fn.spawn.md"))
+        );
+
+        assert_eq!(resource_path(docmd, "docmd://tokio/../../secret.md"), None);
+        assert_eq!(resource_path(docmd, "docmd:///etc/passwd.md"), None);
+        assert_eq!(resource_path(docmd, "docmd://tokio/metadata.json"), None);
+        assert_eq!(resource_path(docmd, "docmd://index.md"), None);
+        assert_eq!(resource_path(docmd, "file:///tokio/index.md"), None);
+    }
+
+    #[test]
+    fn doc_resources_of_built_libraries() {
+        let docmd_dir = tempfile::tempdir().unwrap();
+        let lib_dir = docmd_dir.path().join("probe");
+        fs::create_dir_all(&lib_dir).unwrap();
+        fs::write(
+            lib_dir.join("metadata.json"),
+            r#"{
+                "crate_name": "probe",
+                "lib_name": "probe",
+                "item_map": { "probe::Widget": "struct.Widget.md" },
+                "items": { "probe::Widget": { "kind": "struct", "summary": "A widget." } }
+            }"#,
+        )
+        .unwrap();
+
+        let resources = doc_resources(docmd_dir.path()).unwrap();
+        let uris: Vec<&str> = resources
+            .iter()
+            .map(|resource| resource["uri"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            uris,
+            [
+                "docmd://probe/index.md",
+                "docmd://probe/all.md",
+                "docmd://probe/struct.Widget.md"
+            ]
+        );
+        assert_eq!(resources[2]["name"], "probe::Widget");
+        assert_eq!(resources[2]["description"], "A widget.");
+        assert_eq!(resources[2]["mimeType"], "text/markdown");
+    }
+}
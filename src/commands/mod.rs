@@ -3,10 +3,89 @@
 //! This module contains all subcommand implementations, each in its own module.
 //! Commands are organized by functionality and can be called directly from main.
 
-pub use build::build;
+use std::io::{self, Write};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+pub use build::{build, build_all};
 pub use list::list;
+pub use mcp::mcp;
+pub use search::search;
 pub use show::show;
 
 pub mod build;
 pub mod list;
+pub mod mcp;
+pub mod search;
 pub mod show;
+
+/// Output format of the build, list and show commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Markdown and log messages meant to be read
+    #[default]
+    Markdown,
+    /// A single JSON document on stdout, logs go to stderr
+    Json,
+}
+
+/// Treat a failure to write to a closed pipe as success.
+///
+/// The reader of the output, such as `head`, has exited and nothing more
+/// needs to be written.
+pub fn ignore_broken_pipe(result: Result<()>) -> Result<()> {
+    match result {
+        Err(e)
+            if e.chain().any(|cause| {
+                cause
+                    .downcast_ref::<io::Error>()
+                    .is_some_and(|e| e.kind() == io::ErrorKind::BrokenPipe)
+            }) =>
+        {
+            Ok(())
+        }
+        result => result,
+    }
+}
+
+/// Write a record as pretty JSON.
+fn write_json(out: &mut dyn Write, record: &impl Serialize) -> Result<()> {
+    let json =
+        serde_json::to_string_pretty(record).context("failed to serialize output to JSON")?;
+    writeln!(out, "{}", json)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A writer whose reader has exited.
+    struct ClosedPipe;
+
+    impl Write for ClosedPipe {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::ErrorKind::BrokenPipe.into())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn broken_pipe_is_not_an_error() {
+        let result = write_json(&mut ClosedPipe, &serde_json::json!({ "path": "serde" }));
+        assert!(result.is_err());
+        assert!(ignore_broken_pipe(result).is_ok());
+
+        let result = writeln!(ClosedPipe, "serde::de").context("failed to print listing");
+        assert!(ignore_broken_pipe(result).is_ok());
+
+        let error = ignore_broken_pipe(Err(anyhow::anyhow!("no items match 'Foo'.")));
+        assert_eq!(error.unwrap_err().to_string(), "no items match 'Foo'.");
+        let denied = Err(io::Error::from(io::ErrorKind::PermissionDenied).into());
+        assert!(ignore_broken_pipe(denied).is_err());
+    }
+}
@@ -0,0 +1,247 @@
+//! Search command implementation.
+//!
+//! This module provides the search command which looks up items across the
+//! built documentation under `docmd/*`, by item path and by full text, and
+//! prints ranked item paths with short snippets that can be passed to show.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use serde::Serialize;
+use tracing::{debug, trace};
+
+use crate::cargo;
+use crate::commands::build::{CrateDocMetadata, find_doc_dir};
+use crate::commands::{OutputFormat, write_json};
+use crate::search_index::{self, SearchHit, SearchIndex};
+
+/// Results printed by `search --format json`.
+#[derive(Debug, Serialize)]
+struct SearchReport {
+    /// The best matching items, best first
+    results: Vec<SearchResult>,
+    /// Number of matching items, including the ones beyond the limit
+    total: usize,
+}
+
+/// A matching item.
+#[derive(Debug, Serialize)]
+struct SearchResult {
+    /// Full path of the item, to pass to show, with the version of the
+    /// library for versioned libraries (e.g., "syn@1.0.109::Item")
+    path: String,
+    /// Line of the documentation that matches the query, or the summary of
+    /// the item
+    snippet: String,
+}
+
+/// Search the built documentation and print the best matching items.
+///
+/// All built libraries are searched unless `lib_name` restricts the search
+/// to a single one, optionally with a version such as `syn@1`. At most
+/// `limit` results are printed, as a JSON report with `OutputFormat::Json`.
+pub fn search(
+    query: &str,
+    lib_name: Option<&str>,
+    limit: usize,
+    format: OutputFormat,
+    out: &mut dyn Write,
+) -> Result<()> {
+    debug!("Search command: query={}, lib_name={:?}", query, lib_name);
+
+    if query.trim().is_empty() {
+        bail!("search query cannot be empty");
+    }
+
+    let metadata = cargo::metadata()?;
+    let docmd_dir = PathBuf::from(&metadata.target_directory).join("docmd");
+
+    let lib_dirs = match lib_name {
+        Some(spec) => {
+            let (name, version) = match spec.split_once('@') {
+                Some((name, version)) => (name, Some(version)),
+                None => (spec, None),
+            };
+            let Some(lib_dir) = find_doc_dir(&docmd_dir, name, version) else {
+                let owners = metadata.dependency_owners(None)?;
+                bail!(
+                    "Documentation for '{}' is not built yet. Run 'cargo txt build <crate>' for one of the following crates: {}",
+                    spec,
+                    cargo::format_dependency_owners(&owners)
+                );
+            };
+            vec![lib_dir]
+        }
+        None => built_lib_dirs(&docmd_dir)?,
+    };
+
+    if lib_dirs.is_empty() {
+        bail!("No documentation is built yet. Run 'cargo txt build <crate>' first.");
+    }
+
+    let mut hits: Vec<(PathBuf, SearchHit)> = Vec::new();
+    for lib_dir in lib_dirs {
+        let crate_metadata = read_crate_metadata(&lib_dir)?;
+        let index = SearchIndex::load(&lib_dir, &crate_metadata)?;
+        trace!(
+            "Searching {} ({} items)",
+            crate_metadata.lib_name,
+            index.items.len()
+        );
+        hits.extend(
+            index
+                .search(query)
+                .into_iter()
+                .map(|hit| (lib_dir.clone(), hit)),
+        );
+    }
+
+    hits.sort_by(|(_, a), (_, b)| {
+        b.score
+            .total_cmp(&a.score)
+            .then(a.path.len().cmp(&b.path.len()))
+            .then(a.path.cmp(&b.path))
+    });
+
+    if hits.is_empty() {
+        bail!("No items match '{}'.", query);
+    }
+
+    let total = hits.len();
+    let results: Vec<SearchResult> = hits
+        .into_iter()
+        .take(limit)
+        .map(|(lib_dir, hit)| SearchResult {
+            snippet: fs::read_to_string(lib_dir.join(&hit.file))
+                .ok()
+                .and_then(|markdown| search_index::snippet(&markdown, query))
+                .unwrap_or(hit.summary),
+            path: qualified_path(&lib_dir, &hit.path),
+        })
+        .collect();
+
+    if format == OutputFormat::Json {
+        return write_json(out, &SearchReport { results, total });
+    }
+
+    for result in &results {
+        writeln!(out, "{}", result.path)?;
+        if !result.snippet.is_empty() {
+            writeln!(out, "    {}", result.snippet)?;
+        }
+    }
+
+    if total > limit {
+        writeln!(
+            out,
+            "\n{} more results. Use --limit to show more.",
+            total - limit
+        )?;
+    }
+    writeln!(out, "\nRun `cargo txt show <ITEM_PATH>` to view an item.")?;
+
+    Ok(())
+}
+
+/// Return the item path that show resolves in the library of `lib_dir`.
+///
+/// Items of a versioned library directory (e.g., `docmd/syn@1.0.109`) are
+/// prefixed with the directory name, since their bare path resolves to the
+/// default version.
+pub fn qualified_path(lib_dir: &Path, path: &str) -> String {
+    let Some(dir_name) = lib_dir.file_name().and_then(|name| name.to_str()) else {
+        return path.to_string();
+    };
+    if !dir_name.contains('@') {
+        return path.to_string();
+    }
+    match path.split_once("::") {
+        Some((_, rest)) => format!("{}::{}", dir_name, rest),
+        None => dir_name.to_string(),
+    }
+}
+
+/// Return the directories of every built library under `docmd_dir`.
+pub fn built_lib_dirs(docmd_dir: &Path) -> Result<Vec<PathBuf>> {
+    if !docmd_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut lib_dirs = Vec::new();
+    let entries = fs::read_dir(docmd_dir)
+        .with_context(|| format!("failed to read directory '{}'", docmd_dir.display()))?;
+    for entry in entries {
+        let path = entry?.path();
+        if path.join("metadata.json").exists() {
+            lib_dirs.push(path);
+        }
+    }
+    lib_dirs.sort();
+
+    Ok(lib_dirs)
+}
+
+/// Read the metadata.json of a built library.
+pub fn read_crate_metadata(lib_dir: &Path) -> Result<CrateDocMetadata> {
+    let metadata_path = lib_dir.join("metadata.json");
+    let metadata_content = fs::read_to_string(&metadata_path)
+        .with_context(|| format!("failed to read metadata file '{}'", metadata_path.display()))?;
+    serde_json::from_str(&metadata_content).with_context(|| "failed to parse metadata.json")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn built_lib_dirs_lists_only_built_libraries() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let docmd_dir = temp_dir.path();
+
+        fs::create_dir_all(docmd_dir.join("serde")).unwrap();
+        fs::write(docmd_dir.join("serde").join("metadata.json"), "{}").unwrap();
+        fs::create_dir_all(docmd_dir.join("anyhow")).unwrap();
+        fs::write(docmd_dir.join("anyhow").join("metadata.json"), "{}").unwrap();
+        fs::create_dir_all(docmd_dir.join("partial")).unwrap();
+
+        let lib_dirs = built_lib_dirs(docmd_dir).unwrap();
+        assert_eq!(
+            lib_dirs,
+            vec![docmd_dir.join("anyhow"), docmd_dir.join("serde")]
+        );
+    }
+
+    #[test]
+    fn qualified_path_of_versioned_libraries() {
+        let docmd_dir = Path::new("/ws/target/docmd");
+
+        assert_eq!(
+            qualified_path(&docmd_dir.join("syn"), "syn::Item"),
+            "syn::Item"
+        );
+        assert_eq!(
+            qualified_path(&docmd_dir.join("syn@1.0.109"), "syn::Item"),
+            "syn@1.0.109::Item"
+        );
+        assert_eq!(
+            qualified_path(
+                &docmd_dir.join("syn@1.0.109"),
+                "syn::punctuated::Punctuated"
+            ),
+            "syn@1.0.109::punctuated::Punctuated"
+        );
+        assert_eq!(
+            qualified_path(&docmd_dir.join("syn@1.0.109"), "syn"),
+            "syn@1.0.109"
+        );
+    }
+
+    #[test]
+    fn built_lib_dirs_without_docmd() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let lib_dirs = built_lib_dirs(&temp_dir.path().join("docmd")).unwrap();
+        assert!(lib_dirs.is_empty());
+    }
+}
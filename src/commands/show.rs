@@ -1,32 +1,184 @@
 //! Show command implementation.
 //!
 //! This module provides the show command which displays crate documentation
-//! to stdout. Users can view the crate overview (index.md) or specific items
-//! by providing an item identifier.
+//! to stdout. Users can view the crate overview (index.md), specific items or
+//! members of items, such as methods, enum variants and struct fields, by
+//! providing an item identifier. When the identifier does not match an item
+//! exactly, suffix, case-insensitive and edit-distance matches are tried. The
+//! output can be limited to some sections of the page, or to signatures.
 
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
-use crate::commands::build::CrateDocMetadata;
+use crate::commands::build::{CrateDocMetadata, find_doc_dir};
 use anyhow::{Context, Result, bail, ensure};
+use serde::{Deserialize, Serialize};
 use serde_json;
 use tracing::{debug, trace};
 
 use crate::cargo;
+use crate::commands::list::ItemKind;
+use crate::commands::{OutputFormat, write_json};
+use crate::sections::{self, MemberKind, Section};
 
-/// Parsed item identifier containing library name and optional item.
+/// Part of an item page printed by `show --section`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ShowSection {
+    /// The item documentation
+    Description,
+    /// Required, provided and inherent methods, and associated items
+    Methods,
+    /// Trait implementations and implementors
+    Impls,
+    /// Code examples, under the heading they appear in
+    Examples,
+}
+
+impl ShowSection {
+    /// Return whether a `##` section heading belongs to this section.
+    fn matches(self, title: &str) -> bool {
+        match self {
+            ShowSection::Description => title == Section::Description.title(),
+            ShowSection::Methods => {
+                [
+                    Section::RequiredAssociatedTypes,
+                    Section::ProvidedAssociatedTypes,
+                    Section::RequiredAssociatedConstants,
+                    Section::ProvidedAssociatedConstants,
+                    Section::RequiredMethods,
+                    Section::ProvidedMethods,
+                    Section::InherentMethods,
+                ]
+                .iter()
+                .any(|section| title == section.title())
+                    || title.starts_with("Methods from ")
+            }
+            ShowSection::Impls => {
+                title == Section::TraitImplementations.title()
+                    || title == Section::AutoTraitImplementations.title()
+                    || title == Section::BlanketImplementations.title()
+                    || title == Section::Implementors.title()
+                    || title == "Implementations on Foreign Types"
+                    || title == "Auto implementors"
+            }
+            ShowSection::Examples => false,
+        }
+    }
+
+    /// Name of the section as accepted by `--section`.
+    fn name(self) -> &'static str {
+        match self {
+            ShowSection::Description => "description",
+            ShowSection::Methods => "methods",
+            ShowSection::Impls => "impls",
+            ShowSection::Examples => "examples",
+        }
+    }
+}
+
+/// Parsed item identifier containing library name, optional version and
+/// optional item.
 #[derive(Debug)]
 struct ItemIdentifier {
     lib_name: String,
+    /// Full or partial version of the crate, as in `syn@1::Item`
+    version: Option<String>,
     item: Option<String>,
 }
 
+/// A resolved item identifier.
+#[derive(Debug)]
+struct ResolvedItem {
+    /// Full canonical path of the shown item or member
+    path: String,
+    /// Markdown file of the item, or of the item declaring the member
+    markdown_path: PathBuf,
+    /// Member to show, for paths such as `Builder::worker_threads`
+    member: Option<Member>,
+    /// Notice printed with the output when another item than the requested
+    /// one is shown
+    notice: Option<String>,
+}
+
+/// A member of an item page.
+#[derive(Debug)]
+struct Member {
+    /// Path of the member relative to the item, e.g. `worker_threads` or
+    /// `Circle::radius` for a field of an enum variant
+    path: String,
+    /// Kind of the member, when known from the metadata
+    kind: Option<MemberKind>,
+}
+
+/// Kind of a shown item or member.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+enum ShownKind {
+    Item(ItemKind),
+    Member(MemberKind),
+}
+
+/// Item printed by `show --format json`.
+#[derive(Debug, Serialize)]
+struct ShowReport<'a> {
+    /// Full canonical path of the item or member
+    path: &'a str,
+    /// Why another item than the requested one is shown
+    #[serde(skip_serializing_if = "Option::is_none")]
+    notice: Option<&'a str>,
+    /// Kind of the item or member, `None` when unknown
+    kind: Option<ShownKind>,
+    /// Declaration of the item, or signature of the member
+    signature: Option<String>,
+    /// Titles of the `##` sections of `markdown`
+    sections: Vec<&'a str>,
+    /// The markdown that is printed without `--format json`
+    markdown: &'a str,
+}
+
 /// Show and display crate documentation.
 ///
 /// This function parses the item identifier, resolves the appropriate markdown file,
-/// and prints its contents to stdout.
-pub fn show(item_identifier: &str) -> Result<()> {
-    debug!("Show command: item_identifier={}", item_identifier);
+/// and prints its contents to stdout. For members, only the member's block is
+/// printed. `sections` and `signature_only` further limit the output. With
+/// `OutputFormat::Json`, the output is printed as a JSON record along with the
+/// path, kind and signature of the item. When another item than the requested
+/// one is shown, a notice precedes the markdown, or is a field of the record.
+pub fn show(
+    item_identifier: &str,
+    sections: &[ShowSection],
+    signature_only: bool,
+    format: OutputFormat,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let metadata = cargo::metadata()?;
+    let docmd_dir = PathBuf::from(&metadata.target_directory).join("docmd");
+    show_in(
+        &docmd_dir,
+        item_identifier,
+        sections,
+        signature_only,
+        format,
+        out,
+    )
+}
+
+/// Show an item of the documentation built under `docmd_dir`.
+fn show_in(
+    docmd_dir: &Path,
+    item_identifier: &str,
+    sections: &[ShowSection],
+    signature_only: bool,
+    format: OutputFormat,
+    out: &mut dyn Write,
+) -> Result<()> {
+    debug!(
+        "Show command: item_identifier={}, sections={:?}, signature_only={}, format={:?}",
+        item_identifier, sections, signature_only, format
+    );
 
     let parsed = parse_item_identifier(item_identifier)?;
     trace!(
@@ -34,54 +186,178 @@ pub fn show(item_identifier: &str) -> Result<()> {
         parsed.lib_name, parsed.item
     );
 
-    let markdown_path = resolve_markdown_path(&parsed).with_context(|| {
-        let metadata = cargo::metadata();
-        match metadata {
-            Ok(m) => {
-                let available_crates: Vec<&str> = m.packages[0]
-                    .dependencies
-                    .iter()
-                    .map(|dep| dep.name.as_str())
-                    .collect();
-                format!(
-                    "Can't show '{}'. You should build one of the following crates first: {}",
-                    item_identifier,
-                    available_crates.join(", ")
-                )
-            }
-            Err(_) => format!(
-                "Can't show '{}'. You may need to build the crate documentation first",
-                item_identifier
-            ),
-        }
-    })?;
-    debug!("Resolved markdown path: {:?}", markdown_path);
+    let resolved = resolve_markdown_path(docmd_dir, &parsed, item_identifier)?;
+    debug!("Resolved item: {:?}", resolved);
 
-    let markdown_content = fs::read_to_string(&markdown_path)
+    let markdown_path = &resolved.markdown_path;
+    let markdown_content = fs::read_to_string(markdown_path)
         .with_context(|| format!("failed to read markdown file '{}'", markdown_path.display()))?;
     trace!("Read markdown file ({} bytes)", markdown_content.len());
 
-    println!("{}", markdown_content);
+    let output = select_output(
+        &markdown_content,
+        resolved.member.as_ref(),
+        sections,
+        signature_only,
+    )
+    .with_context(|| format!("failed to show '{}'", item_identifier))?;
+
+    match format {
+        OutputFormat::Markdown => {
+            if let Some(notice) = &resolved.notice {
+                writeln!(out, "> {}\n", notice)?;
+            }
+            writeln!(out, "{}", output)?
+        }
+        OutputFormat::Json => {
+            let kind = match &resolved.member {
+                Some(member) => member.kind.map(ShownKind::Member),
+                None => shown_item_kind(markdown_path).map(ShownKind::Item),
+            };
+            write_json(
+                out,
+                &ShowReport {
+                    path: &resolved.path,
+                    notice: resolved.notice.as_deref(),
+                    kind,
+                    signature: signature(&markdown_content, resolved.member.as_ref()),
+                    sections: sections::section_titles(&output),
+                    markdown: &output,
+                },
+            )?;
+        }
+    }
 
     Ok(())
 }
 
-/// Parse an item identifier into library name and optional item.
+/// Return the kind of the item of a markdown page. The crate overview,
+/// `index.md`, is a module.
+fn shown_item_kind(markdown_path: &Path) -> Option<ItemKind> {
+    let file_name = markdown_path.file_name()?.to_str()?;
+    if file_name == "index.md" {
+        return Some(ItemKind::Module);
+    }
+    ItemKind::from_file(file_name)
+}
+
+/// Return the declaration of the item of a page without its code fences, or
+/// the signature heading of a member. Members of trait implementations are
+/// preceded by their `impl` heading, which is skipped.
+fn signature(markdown: &str, member: Option<&Member>) -> Option<String> {
+    match member {
+        Some(member) => {
+            let block = sections::extract_member(markdown, &member.path, member.kind)?;
+            block
+                .lines()
+                .filter(|line| line.starts_with('#'))
+                .map(|heading| heading.trim_start_matches('#').trim().trim_matches('`'))
+                .find(|heading| !heading.starts_with("impl"))
+                .map(str::to_string)
+        }
+        None => {
+            let declaration = sections::declaration(markdown)?;
+            let lines: Vec<&str> = declaration.trim_end().lines().collect();
+            Some(lines.get(1..lines.len().saturating_sub(1))?.join("\n"))
+        }
+    }
+}
+
+/// Select the part of an item page to print.
 ///
-/// Extracts the library name (first component before `::`) and the remaining
-/// item identifier (if any) from the full item identifier string.
+/// For a member, only its block is kept. With `sections`, only the title and
+/// the requested sections are kept, in page order, followed by the examples
+/// if requested. With `signature_only`, sections and members are reduced to
+/// their signatures, and a whole page to its declaration.
+fn select_output(
+    markdown: &str,
+    member: Option<&Member>,
+    sections: &[ShowSection],
+    signature_only: bool,
+) -> Result<String> {
+    let title = sections::page_title(markdown).map(|title| format!("{}\n\n", title));
+
+    let markdown = match member {
+        Some(member) => match sections::extract_member(markdown, &member.path, member.kind) {
+            Some(block) => block,
+            None => bail!("no member named '{}' found", member.path),
+        },
+        None => markdown.to_string(),
+    };
+
+    if sections.is_empty() {
+        if !signature_only {
+            return Ok(markdown);
+        }
+        if let Some(member) = member {
+            return Ok(match member.kind {
+                Some(MemberKind::Variant | MemberKind::Field) => markdown
+                    .lines()
+                    .next()
+                    .map(|line| format!("{}\n", line))
+                    .unwrap_or_default(),
+                _ => sections::signatures(&markdown),
+            });
+        }
+        let Some(declaration) = sections::declaration(&markdown) else {
+            bail!("the page has no declaration");
+        };
+        return Ok(format!("{}{}", title.unwrap_or_default(), declaration));
+    }
+
+    let mut selected = String::new();
+    let found = sections::extract_sections(&markdown, |heading| {
+        sections.iter().any(|section| section.matches(heading))
+    });
+    for section in found {
+        if signature_only {
+            selected.push_str(&sections::signatures(&section));
+        } else {
+            selected.push_str(&section);
+        }
+    }
+    if sections.contains(&ShowSection::Examples) {
+        selected.push_str(&sections::examples(&markdown));
+    }
+
+    if selected.is_empty() {
+        let names: Vec<&str> = sections.iter().map(|section| section.name()).collect();
+        bail!("the page has no {} section", names.join(" or "));
+    }
+
+    match (member, title) {
+        (None, Some(title)) => Ok(format!("{}{}", title, selected)),
+        _ => Ok(selected),
+    }
+}
+
+/// Parse an item identifier into library name, optional version and
+/// optional item.
+///
+/// Extracts the library name (first component before `::`, without a
+/// `@version` suffix) and the remaining item identifier (if any) from the
+/// full item identifier string.
 fn parse_item_identifier(item_identifier: &str) -> Result<ItemIdentifier> {
     let mut parts = item_identifier.split("::");
 
-    let lib_name = match parts.next().filter(|s| !s.is_empty()) {
+    let spec = match parts.next().filter(|s| !s.is_empty()) {
         Some(n) => n,
         None => bail!(
             "invalid item identifier '{}'. Expected format: <lib_name> or <lib_name>::<item> (e.g., 'serde' or 'serde::Error').",
             item_identifier
         ),
     };
+    let (lib_name, version) = match spec.split_once('@') {
+        Some((lib_name, version)) => (lib_name, Some(version)),
+        None => (spec, None),
+    };
 
     ensure!(!lib_name.is_empty(), "library name cannot be empty");
+    ensure!(
+        version != Some(""),
+        "version cannot be empty in '{}'",
+        item_identifier
+    );
 
     let item: Vec<&str> = parts.collect();
     let item = if item.is_empty() {
@@ -90,10 +366,14 @@ fn parse_item_identifier(item_identifier: &str) -> Result<ItemIdentifier> {
         Some(item.join("::"))
     };
 
-    trace!("Parsed item path: lib_name={}, item={:?}", lib_name, item);
+    trace!(
+        "Parsed item path: lib_name={}, version={:?}, item={:?}",
+        lib_name, version, item
+    );
 
     Ok(ItemIdentifier {
         lib_name: lib_name.to_string(),
+        version: version.map(str::to_string),
         item,
     })
 }
@@ -102,17 +382,47 @@ fn parse_item_identifier(item_identifier: &str) -> Result<ItemIdentifier> {
 ///
 /// If no item is specified, returns the path to index.md (crate overview).
 /// If an item is specified, looks up the item in metadata.json and
-/// returns the corresponding markdown file path.
-fn resolve_markdown_path(parsed: &ItemIdentifier) -> Result<PathBuf> {
-    let metadata = cargo::metadata()?;
-    let docmd_dir = PathBuf::from(&metadata.target_directory).join("docmd");
-    let lib_docmd_dir = docmd_dir.join(&parsed.lib_name);
+/// returns the corresponding markdown file path. Re-exported paths are
+/// first replaced by their canonical path. Members indexed in the
+/// metadata, such as `tokio::runtime::Builder::worker_threads`, resolve to
+/// the page of their item. Other paths whose parent is an item are looked up
+/// by signature on the parent's page, and reported with the indexed members
+/// of the parent that have a similar name when missing. Other items that are
+/// not found exactly are looked up with `find_item_candidates`: a single
+/// candidate is shown with a notice, several candidates are reported as a
+/// ranked "did you mean" list.
+fn resolve_markdown_path(
+    docmd_dir: &Path,
+    parsed: &ItemIdentifier,
+    item_identifier: &str,
+) -> Result<ResolvedItem> {
+    let Some(lib_docmd_dir) = find_doc_dir(docmd_dir, &parsed.lib_name, parsed.version.as_deref())
+    else {
+        let metadata = cargo::metadata()?;
+        match metadata.dependency_owners(None) {
+            Ok(owners) => bail!(
+                "Can't show '{}'. You should build one of the following crates first: {}",
+                item_identifier,
+                cargo::format_dependency_owners(&owners)
+            ),
+            Err(_) => bail!(
+                "Can't show '{}'. You may need to build the crate documentation first",
+                item_identifier
+            ),
+        }
+    };
+    debug!("Resolved documentation directory: {:?}", lib_docmd_dir);
 
     let parsed_item = match &parsed.item {
         None => {
             let index_md = lib_docmd_dir.join("index.md");
             trace!("No item specified, returning index.md: {:?}", index_md);
-            return Ok(index_md);
+            return Ok(ResolvedItem {
+                path: parsed.lib_name.clone(),
+                markdown_path: index_md,
+                member: None,
+                notice: None,
+            });
         }
         Some(item) => item,
     };
@@ -130,16 +440,119 @@ fn resolve_markdown_path(parsed: &ItemIdentifier) -> Result<PathBuf> {
         crate_metadata.item_map.len()
     );
 
-    let full_item_path = format!("{}::{}", parsed.lib_name, parsed_item);
+    let full_item_path = canonical_path(
+        &crate_metadata,
+        &format!("{}::{}", parsed.lib_name, parsed_item),
+    );
     trace!("Looking up item path: {}", full_item_path);
 
-    let relative_md_path = match crate_metadata.item_map.get(&full_item_path) {
-        Some(p) => p,
-        None => bail!(
-            "could not resolve item path '{}'. The item may not exist. Try: `cargo txt list {}` to see all available items.",
+    if !crate_metadata.item_map.contains_key(&full_item_path)
+        && let Some(anchor) = crate_metadata.member_map.get(&full_item_path)
+        && let Some((kind, path)) = MemberKind::from_anchor(anchor)
+        && let Some(parent) = full_item_path
+            .strip_suffix(&path)
+            .and_then(|parent| parent.strip_suffix("::"))
+        && let Some(parent_md_path) = crate_metadata.item_map.get(parent)
+    {
+        trace!("Resolved member '{}' of '{}' ({})", path, parent, anchor);
+        return Ok(ResolvedItem {
+            path: full_item_path.clone(),
+            markdown_path: lib_docmd_dir.join(parent_md_path),
+            member: Some(Member {
+                path,
+                kind: Some(kind),
+            }),
+            notice: None,
+        });
+    }
+
+    if !crate_metadata.item_map.contains_key(&full_item_path)
+        && let Some((parent, member)) = full_item_path.rsplit_once("::")
+        && let Some(parent_md_path) = crate_metadata.item_map.get(parent)
+    {
+        let markdown_path = lib_docmd_dir.join(parent_md_path);
+        let markdown = fs::read_to_string(&markdown_path).with_context(|| {
+            format!("failed to read markdown file '{}'", markdown_path.display())
+        })?;
+        if sections::extract_member(&markdown, member, None).is_none() {
+            let candidates = find_member_candidates(&crate_metadata.member_map, parent, member);
+            ensure!(
+                !candidates.is_empty(),
+                "could not find member '{}' of '{}'. Try: `cargo txt show {} --section methods --signature-only` to see its methods.",
+                member,
+                parent,
+                parent
+            );
+            bail!(
+                "could not find member '{}' of '{}'. Did you mean:\n{}",
+                member,
+                parent,
+                candidates
+                    .iter()
+                    .take(MAX_SUGGESTIONS)
+                    .map(|candidate| format!("  {}", candidate))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            );
+        }
+        trace!("Resolved member '{}' of '{}'", member, parent);
+        return Ok(ResolvedItem {
+            path: full_item_path.clone(),
+            markdown_path,
+            member: Some(Member {
+                path: member.to_string(),
+                kind: None,
+            }),
+            notice: None,
+        });
+    }
+
+    let module_prefix = format!("{}::", full_item_path);
+    let is_module = crate_metadata
+        .item_map
+        .keys()
+        .chain(crate_metadata.alias_map.keys())
+        .any(|path| path.starts_with(&module_prefix));
+    if !crate_metadata.item_map.contains_key(&full_item_path) && is_module {
+        bail!(
+            "'{}' is a module. Try: `cargo txt list {}` to see its items.",
             full_item_path,
-            crate_metadata.lib_name
-        ),
+            full_item_path
+        );
+    }
+
+    let (item_path, relative_md_path, notice) = match crate_metadata.item_map.get(&full_item_path) {
+        Some(p) => (full_item_path, p, None),
+        None => {
+            let candidates =
+                find_item_candidates(&crate_metadata.item_map, &parsed.lib_name, parsed_item);
+            match candidates.as_slice() {
+                [] => bail!(
+                    "could not resolve item path '{}'. The item may not exist. Try: `cargo txt list {}` to see all available items.",
+                    full_item_path,
+                    crate_metadata.lib_name
+                ),
+                [candidate] => {
+                    let notice = format!("'{}' not found, showing '{}'", full_item_path, candidate);
+                    debug!("{}", notice);
+                    (
+                        candidate.clone(),
+                        &crate_metadata.item_map[candidate],
+                        Some(notice),
+                    )
+                }
+                _ => bail!(
+                    "could not resolve item path '{}'. Did you mean:\n{}",
+                    full_item_path,
+                    candidates
+                        .iter()
+                        .take(MAX_SUGGESTIONS)
+                        .map(|candidate| format!("  {}", candidate))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                ),
+            }
+        }
     };
 
     trace!("Found markdown path: {}", relative_md_path);
@@ -148,12 +561,184 @@ fn resolve_markdown_path(parsed: &ItemIdentifier) -> Result<PathBuf> {
 
     debug!("Resolved markdown path: {:?}", markdown_path);
 
-    Ok(markdown_path)
+    Ok(ResolvedItem {
+        path: item_path,
+        markdown_path,
+        member: None,
+        notice,
+    })
+}
+
+/// Return the canonical path of an item path.
+///
+/// Paths that re-export an item, such as `tokio::spawn`, are replaced by the
+/// item path they resolve to in the metadata's alias map. Paths below a
+/// re-exported item or module, such as members, are rewritten from their
+/// longest re-exported prefix.
+fn canonical_path(crate_metadata: &CrateDocMetadata, path: &str) -> String {
+    if crate_metadata.item_map.contains_key(path) {
+        return path.to_string();
+    }
+
+    let mut prefix = path;
+    loop {
+        if let Some(canonical) = crate_metadata.alias_map.get(prefix) {
+            let canonical = format!("{}{}", canonical, &path[prefix.len()..]);
+            trace!("Resolved alias '{}' to '{}'", path, canonical);
+            return canonical;
+        }
+        match prefix.rsplit_once("::") {
+            Some((parent, _)) => prefix = parent,
+            None => return path.to_string(),
+        }
+    }
+}
+
+/// Maximum number of candidates listed in a "did you mean" error.
+const MAX_SUGGESTIONS: usize = 10;
+
+/// Find item paths that likely match an item that was not found exactly.
+///
+/// The following strategies are tried in order, and the candidates of the
+/// first one that matches anything are returned:
+///
+/// 1. Suffix match: `Error` matches `serde::de::Error`
+/// 2. Case-insensitive match, on the full path or as a suffix
+/// 3. Edit-distance match on the path or on its last segment
+///
+/// Candidates are ranked best first: shorter paths win for suffix matches,
+/// and smaller edit distances win for edit-distance matches.
+fn find_item_candidates(
+    item_map: &HashMap<String, String>,
+    lib_name: &str,
+    item: &str,
+) -> Vec<String> {
+    let prefix = format!("{}::", lib_name);
+    let paths: Vec<(&str, &str)> = item_map
+        .keys()
+        .filter_map(|path| path.strip_prefix(&prefix).map(|rel| (path.as_str(), rel)))
+        .collect();
+
+    let rank = |mut matches: Vec<(usize, &str)>| -> Vec<String> {
+        matches.sort_by(|a, b| {
+            a.0.cmp(&b.0)
+                .then(a.1.len().cmp(&b.1.len()))
+                .then(a.1.cmp(b.1))
+        });
+        matches
+            .into_iter()
+            .map(|(_, path)| path.to_string())
+            .collect()
+    };
+
+    let suffix = format!("::{}", item);
+    let suffix_matches: Vec<(usize, &str)> = paths
+        .iter()
+        .filter(|(_, rel)| rel.ends_with(&suffix))
+        .map(|(path, _)| (0, *path))
+        .collect();
+    if !suffix_matches.is_empty() {
+        trace!("Suffix matches for '{}': {:?}", item, suffix_matches);
+        return rank(suffix_matches);
+    }
+
+    let item_lower = item.to_lowercase();
+    let suffix_lower = suffix.to_lowercase();
+    let case_matches: Vec<(usize, &str)> = paths
+        .iter()
+        .filter_map(|(path, rel)| {
+            let rel_lower = rel.to_lowercase();
+            if rel_lower == item_lower {
+                Some((0, *path))
+            } else if rel_lower.ends_with(&suffix_lower) {
+                Some((1, *path))
+            } else {
+                None
+            }
+        })
+        .collect();
+    if !case_matches.is_empty() {
+        trace!(
+            "Case-insensitive matches for '{}': {:?}",
+            item, case_matches
+        );
+        return rank(case_matches);
+    }
+
+    let last_lower = item_lower.rsplit("::").next().unwrap_or_default();
+    let max_distance = (last_lower.chars().count() / 4).max(1);
+    let distance_matches: Vec<(usize, &str)> = paths
+        .iter()
+        .filter_map(|(path, rel)| {
+            let rel_lower = rel.to_lowercase();
+            let rel_last = rel_lower.rsplit("::").next().unwrap_or_default();
+            let distance =
+                edit_distance(&item_lower, &rel_lower).min(edit_distance(last_lower, rel_last));
+            (distance <= max_distance).then_some((distance, *path))
+        })
+        .collect();
+    trace!(
+        "Edit-distance matches for '{}': {:?}",
+        item, distance_matches
+    );
+    rank(distance_matches)
+}
+
+/// Find the members of `parent` whose name is close to `member`, such as
+/// `worker_threads` for `worker_thread`.
+///
+/// Members are looked up in the member map and ranked by case-insensitive
+/// edit distance, with the same limit as `find_item_candidates`.
+fn find_member_candidates(
+    member_map: &HashMap<String, String>,
+    parent: &str,
+    member: &str,
+) -> Vec<String> {
+    let prefix = format!("{}::", parent);
+    let member_lower = member.to_lowercase();
+    let max_distance = (member_lower.chars().count() / 4).max(1);
+
+    let mut matches: Vec<(usize, &str)> = member_map
+        .keys()
+        .filter_map(|path| {
+            let rel = path.strip_prefix(&prefix)?;
+            let distance = edit_distance(&member_lower, &rel.to_lowercase());
+            (distance <= max_distance).then_some((distance, path.as_str()))
+        })
+        .collect();
+    matches.sort();
+    trace!("Member matches for '{}': {:?}", member, matches);
+
+    matches
+        .into_iter()
+        .map(|(_, path)| path.to_string())
+        .collect()
+}
+
+/// Compute the Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, ca) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
 }
 
 #[cfg(test)]
 mod tests {
+    use serde_json::{Value, json};
+
     use super::*;
+    use crate::commands::build::temp_docmd;
 
     #[test]
     fn parse_simple_crate_name() {
@@ -183,6 +768,22 @@ mod tests {
         assert_eq!(result.item, Some("de::value::Error".to_string()));
     }
 
+    #[test]
+    fn parse_item_path_with_version() {
+        let result = parse_item_identifier("syn@1::Item").unwrap();
+        assert_eq!(result.lib_name, "syn");
+        assert_eq!(result.version.as_deref(), Some("1"));
+        assert_eq!(result.item, Some("Item".to_string()));
+
+        let result = parse_item_identifier("syn@2.0.87").unwrap();
+        assert_eq!(result.lib_name, "syn");
+        assert_eq!(result.version.as_deref(), Some("2.0.87"));
+        assert_eq!(result.item, None);
+
+        assert!(parse_item_identifier("syn@::Item").is_err());
+        assert!(parse_item_identifier("@1::Item").is_err());
+    }
+
     #[test]
     fn parse_item_identifier_invalid_leading_separator() {
         let result = parse_item_identifier("::invalid");
@@ -218,6 +819,7 @@ mod tests {
     fn error_message_preserves_user_input_format() {
         let parsed = ItemIdentifier {
             lib_name: "rustdoc-types".to_string(),
+            version: None,
             item: Some("Abi".to_string()),
         };
 
@@ -228,6 +830,7 @@ mod tests {
 
         let parsed_underscores = ItemIdentifier {
             lib_name: "rustdoc_types".to_string(),
+            version: None,
             item: Some("Abi".to_string()),
         };
         assert_eq!(parsed_underscores.lib_name, "rustdoc_types");
@@ -238,4 +841,479 @@ mod tests {
         );
         assert_eq!(user_item_path_underscores, "rustdoc_types::Abi");
     }
+
+    fn serde_item_map() -> HashMap<String, String> {
+        [
+            ("serde::de::Error", "de/trait.Error.md"),
+            ("serde::ser::Error", "ser/trait.Error.md"),
+            ("serde::de::value::Error", "de/value/struct.Error.md"),
+            ("serde::de::Visitor", "de/trait.Visitor.md"),
+            ("serde::Deserializer", "trait.Deserializer.md"),
+            ("serde::Serializer", "trait.Serializer.md"),
+            (
+                "serde::de::IntoDeserializer",
+                "de/trait.IntoDeserializer.md",
+            ),
+        ]
+        .into_iter()
+        .map(|(path, file)| (path.to_string(), file.to_string()))
+        .collect()
+    }
+
+    #[test]
+    fn find_item_candidates_unique_suffix() {
+        let item_map = serde_item_map();
+        assert_eq!(
+            find_item_candidates(&item_map, "serde", "Visitor"),
+            vec!["serde::de::Visitor"]
+        );
+        assert_eq!(
+            find_item_candidates(&item_map, "serde", "value::Error"),
+            vec!["serde::de::value::Error"]
+        );
+    }
+
+    #[test]
+    fn find_item_candidates_ranks_ambiguous_suffix() {
+        let item_map = serde_item_map();
+        assert_eq!(
+            find_item_candidates(&item_map, "serde", "Error"),
+            vec![
+                "serde::de::Error",
+                "serde::ser::Error",
+                "serde::de::value::Error"
+            ]
+        );
+    }
+
+    #[test]
+    fn find_item_candidates_case_insensitive() {
+        let item_map = serde_item_map();
+        assert_eq!(
+            find_item_candidates(&item_map, "serde", "deserializer"),
+            vec!["serde::Deserializer"]
+        );
+        assert_eq!(
+            find_item_candidates(&item_map, "serde", "visitor"),
+            vec!["serde::de::Visitor"]
+        );
+    }
+
+    #[test]
+    fn find_item_candidates_edit_distance() {
+        let item_map = serde_item_map();
+        assert_eq!(
+            find_item_candidates(&item_map, "serde", "Serialiser"),
+            vec!["serde::Serializer"]
+        );
+        assert_eq!(
+            find_item_candidates(&item_map, "serde", "de::Vistor"),
+            vec!["serde::de::Visitor"]
+        );
+        assert!(find_item_candidates(&item_map, "serde", "Tokio").is_empty());
+    }
+
+    #[test]
+    fn find_member_candidates_by_edit_distance() {
+        let member_map: HashMap<String, String> = [
+            (
+                "tokio::runtime::Builder::worker_threads",
+                "method.worker_threads",
+            ),
+            (
+                "tokio::runtime::Builder::max_blocking_threads",
+                "method.max_blocking_threads",
+            ),
+            ("tokio::runtime::Builder::enable_all", "method.enable_all"),
+            (
+                "tokio::runtime::Runtime::worker_threads",
+                "method.worker_threads",
+            ),
+        ]
+        .iter()
+        .map(|(path, anchor)| (path.to_string(), anchor.to_string()))
+        .collect();
+
+        assert_eq!(
+            find_member_candidates(&member_map, "tokio::runtime::Builder", "worker_thread"),
+            vec!["tokio::runtime::Builder::worker_threads"]
+        );
+        assert_eq!(
+            find_member_candidates(&member_map, "tokio::runtime::Builder", "Enable_Al"),
+            vec!["tokio::runtime::Builder::enable_all"]
+        );
+        assert!(find_member_candidates(&member_map, "tokio::runtime::Builder", "spawn").is_empty());
+    }
+
+    #[test]
+    fn show_suggests_misspelled_members() {
+        let docmd_dir = tokio_docmd();
+        let mut out = Vec::new();
+
+        let error = show_in(
+            docmd_dir.path(),
+            "tokio::runtime::Builder::worker_thread",
+            &[],
+            false,
+            OutputFormat::Markdown,
+            &mut out,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "could not find member 'worker_thread' of 'tokio::runtime::Builder'. Did you mean:\n  tokio::runtime::Builder::worker_threads"
+        );
+
+        let error = show_in(
+            docmd_dir.path(),
+            "tokio::runtime::Builder::spawn",
+            &[],
+            false,
+            OutputFormat::Markdown,
+            &mut out,
+        )
+        .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("--section methods --signature-only")
+        );
+    }
+
+    #[test]
+    fn edit_distance_basic() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("abc", ""), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("visitor", "vistor"), 1);
+    }
+
+    #[test]
+    fn canonical_path_follows_aliases() {
+        let crate_metadata = CrateDocMetadata {
+            crate_name: "tokio".to_string(),
+            lib_name: "tokio".to_string(),
+            item_map: HashMap::from([
+                (
+                    "tokio::task::spawn".to_string(),
+                    "task/fn.spawn.md".to_string(),
+                ),
+                (
+                    "tokio::runtime::Builder".to_string(),
+                    "runtime/struct.Builder.md".to_string(),
+                ),
+            ]),
+            alias_map: HashMap::from([
+                ("tokio::spawn".to_string(), "tokio::task::spawn".to_string()),
+                (
+                    "tokio::rt::Builder".to_string(),
+                    "tokio::runtime::Builder".to_string(),
+                ),
+            ]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            canonical_path(&crate_metadata, "tokio::spawn"),
+            "tokio::task::spawn"
+        );
+        assert_eq!(
+            canonical_path(&crate_metadata, "tokio::rt::Builder::worker_threads"),
+            "tokio::runtime::Builder::worker_threads"
+        );
+        assert_eq!(
+            canonical_path(&crate_metadata, "tokio::task::spawn"),
+            "tokio::task::spawn"
+        );
+        assert_eq!(
+            canonical_path(&crate_metadata, "tokio::Spawn"),
+            "tokio::Spawn"
+        );
+    }
+
+    const BUILDER_PAGE: &str = "# Struct Builder
+
+## Declaration
+
+```rust,item-decl
+pub struct Builder { /* private fields */ }
+```
+
+## Description
+
+Builds a runtime.
+
+```rust
+let rt = Builder::new_multi_thread().build();
+```
+
+## Inherent Methods
+
+### impl Builder
+
+#### pub fn worker_threads(&mut self, val: usize) -> &mut Self
+
+Sets the number of worker threads.
+
+## Trait Implementations
+
+### impl Debug for Builder
+
+#### fn fmt(&self, f: &mut Formatter<'_>) -> Result
+
+Formats the value.
+";
+
+    /// Write a tokio library with the Builder page to a temporary `docmd`.
+    fn tokio_docmd() -> tempfile::TempDir {
+        let crate_metadata = CrateDocMetadata {
+            crate_name: "tokio".to_string(),
+            lib_name: "tokio".to_string(),
+            item_map: HashMap::from([(
+                "tokio::runtime::Builder".to_string(),
+                "runtime/struct.Builder.md".to_string(),
+            )]),
+            member_map: HashMap::from([(
+                "tokio::runtime::Builder::worker_threads".to_string(),
+                "method.worker_threads".to_string(),
+            )]),
+            alias_map: HashMap::from([(
+                "tokio::rt::Builder".to_string(),
+                "tokio::runtime::Builder".to_string(),
+            )]),
+            ..Default::default()
+        };
+        let docmd_dir = temp_docmd(&[("tokio", &crate_metadata)]);
+        let lib_dir = docmd_dir.path().join("tokio");
+        fs::create_dir_all(lib_dir.join("runtime")).unwrap();
+        fs::write(lib_dir.join("runtime/struct.Builder.md"), BUILDER_PAGE).unwrap();
+        fs::write(lib_dir.join("index.md"), "# Crate tokio\n\n## Modules\n").unwrap();
+        docmd_dir
+    }
+
+    fn show_json(
+        docmd_dir: &Path,
+        item: &str,
+        sections: &[ShowSection],
+        signature_only: bool,
+    ) -> Value {
+        let mut out = Vec::new();
+        show_in(
+            docmd_dir,
+            item,
+            sections,
+            signature_only,
+            OutputFormat::Json,
+            &mut out,
+        )
+        .unwrap();
+        serde_json::from_slice(&out).unwrap()
+    }
+
+    #[test]
+    fn show_json_reports_items() {
+        let docmd_dir = tokio_docmd();
+
+        let report = show_json(docmd_dir.path(), "tokio::rt::Builder", &[], false);
+        assert_eq!(
+            report,
+            json!({
+                "path": "tokio::runtime::Builder",
+                "kind": "struct",
+                "signature": "pub struct Builder { /* private fields */ }",
+                "sections": [
+                    "Declaration",
+                    "Description",
+                    "Inherent Methods",
+                    "Trait Implementations"
+                ],
+                "markdown": BUILDER_PAGE,
+            })
+        );
+
+        let report = show_json(
+            docmd_dir.path(),
+            "tokio::runtime::Builder",
+            &[ShowSection::Methods],
+            true,
+        );
+        assert_eq!(report["sections"], json!(["Inherent Methods"]));
+        assert_eq!(
+            report["markdown"],
+            "# Struct Builder\n\n## Inherent Methods\n\n### impl Builder\n#### pub fn worker_threads(&mut self, val: usize) -> &mut Self\n"
+        );
+
+        let report = show_json(docmd_dir.path(), "tokio", &[], false);
+        assert_eq!(report["path"], "tokio");
+        assert_eq!(report["kind"], "module");
+        assert_eq!(report["signature"], Value::Null);
+        assert_eq!(report["sections"], json!(["Modules"]));
+    }
+
+    #[test]
+    fn show_prints_fallback_notice_in_output() {
+        let docmd_dir = tokio_docmd();
+
+        let mut out = Vec::new();
+        show_in(
+            docmd_dir.path(),
+            "tokio::Builder",
+            &[],
+            false,
+            OutputFormat::Markdown,
+            &mut out,
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            format!(
+                "> 'tokio::Builder' not found, showing 'tokio::runtime::Builder'\n\n{}\n",
+                BUILDER_PAGE
+            )
+        );
+
+        let report = show_json(docmd_dir.path(), "tokio::Builder", &[], false);
+        assert_eq!(report["path"], "tokio::runtime::Builder");
+        assert_eq!(
+            report["notice"],
+            "'tokio::Builder' not found, showing 'tokio::runtime::Builder'"
+        );
+        assert_eq!(report["markdown"], BUILDER_PAGE);
+
+        let report = show_json(docmd_dir.path(), "tokio::runtime::Builder", &[], false);
+        assert!(report.get("notice").is_none());
+    }
+
+    #[test]
+    fn show_json_reports_members() {
+        let docmd_dir = tokio_docmd();
+
+        let report = show_json(
+            docmd_dir.path(),
+            "tokio::runtime::Builder::worker_threads",
+            &[],
+            false,
+        );
+        assert_eq!(
+            report,
+            json!({
+                "path": "tokio::runtime::Builder::worker_threads",
+                "kind": "method",
+                "signature": "pub fn worker_threads(&mut self, val: usize) -> &mut Self",
+                "sections": [],
+                "markdown": "#### pub fn worker_threads(&mut self, val: usize) -> &mut Self\n\nSets the number of worker threads.\n\n",
+            })
+        );
+
+        // Members missing from the member map are looked up on the page.
+        let report = show_json(docmd_dir.path(), "tokio::runtime::Builder::fmt", &[], false);
+        assert_eq!(report["path"], "tokio::runtime::Builder::fmt");
+        assert_eq!(report["kind"], Value::Null);
+        assert_eq!(
+            report["signature"],
+            "fn fmt(&self, f: &mut Formatter<'_>) -> Result"
+        );
+    }
+
+    #[test]
+    fn select_output_whole_page() {
+        let output = select_output(BUILDER_PAGE, None, &[], false).unwrap();
+        assert_eq!(output, BUILDER_PAGE);
+    }
+
+    #[test]
+    fn select_output_signature_only() {
+        let output = select_output(BUILDER_PAGE, None, &[], true).unwrap();
+        assert_eq!(
+            output,
+            "# Struct Builder\n\n```rust,item-decl\npub struct Builder { /* private fields */ }\n```\n\n"
+        );
+    }
+
+    #[test]
+    fn select_output_sections() {
+        let output = select_output(
+            BUILDER_PAGE,
+            None,
+            &[ShowSection::Impls, ShowSection::Description],
+            false,
+        )
+        .unwrap();
+        assert!(output.starts_with("# Struct Builder\n\n## Description\n\nBuilds a runtime."));
+        assert!(output.contains("## Trait Implementations\n\n### impl Debug for Builder"));
+        assert!(!output.contains("## Inherent Methods"));
+    }
+
+    #[test]
+    fn select_output_method_signatures() {
+        let output = select_output(BUILDER_PAGE, None, &[ShowSection::Methods], true).unwrap();
+        assert_eq!(
+            output,
+            concat!(
+                "# Struct Builder\n\n",
+                "## Inherent Methods\n\n",
+                "### impl Builder\n",
+                "#### pub fn worker_threads(&mut self, val: usize) -> &mut Self\n",
+            )
+        );
+    }
+
+    #[test]
+    fn select_output_examples() {
+        let output = select_output(BUILDER_PAGE, None, &[ShowSection::Examples], false).unwrap();
+        assert_eq!(
+            output,
+            "# Struct Builder\n\n## Description\n\n```rust\nlet rt = Builder::new_multi_thread().build();\n```\n\n"
+        );
+    }
+
+    #[test]
+    fn select_output_member() {
+        let member = Member {
+            path: "worker_threads".to_string(),
+            kind: Some(MemberKind::Method),
+        };
+        let output = select_output(BUILDER_PAGE, Some(&member), &[], false).unwrap();
+        assert_eq!(
+            output,
+            "#### pub fn worker_threads(&mut self, val: usize) -> &mut Self\n\nSets the number of worker threads.\n\n"
+        );
+
+        let output = select_output(BUILDER_PAGE, Some(&member), &[], true).unwrap();
+        assert_eq!(
+            output,
+            "#### pub fn worker_threads(&mut self, val: usize) -> &mut Self\n"
+        );
+    }
+
+    #[test]
+    fn select_output_field_signature() {
+        let page = "# Struct Widget\n\n## Fields\n\n### `size: usize`\n\nIts size.\n\n";
+        let member = Member {
+            path: "size".to_string(),
+            kind: Some(MemberKind::Field),
+        };
+        assert_eq!(
+            select_output(page, Some(&member), &[], false).unwrap(),
+            "### `size: usize`\n\nIts size.\n\n"
+        );
+        assert_eq!(
+            select_output(page, Some(&member), &[], true).unwrap(),
+            "### `size: usize`\n"
+        );
+    }
+
+    #[test]
+    fn select_output_missing_section() {
+        let result = select_output(
+            "# Crate serde\n\nOverview.\n",
+            None,
+            &[ShowSection::Methods, ShowSection::Impls],
+            false,
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "the page has no methods or impls section"
+        );
+    }
 }
@@ -7,12 +7,135 @@ use anyhow::{Result, bail};
 use scraper::element_ref::ElementRef;
 use scraper::{Html, Selector};
 
+use crate::item_info::{self, ItemInfo, Stability};
+use crate::reexports::Reexport;
+use crate::sections::{MemberKind, Section};
+
+/// A rustdoc HTML page, used to resolve relative links to item paths and to
+/// render the page sections.
+#[derive(Debug, Clone, Copy)]
+pub struct DocPage<'a> {
+    /// Library name of the crate the page belongs to (e.g., "serde")
+    pub lib_name: &'a str,
+    /// Path of the page relative to the crate's doc directory
+    /// (e.g., "de/trait.Visitor.html")
+    pub path: &'a str,
+    /// Sections left out of the markdown
+    pub skipped_sections: &'a [Section],
+    /// The `trait.impl` script of a trait page, which lists the implementors
+    /// found in other crates of the same doc directory
+    pub implementors: Option<&'a str>,
+}
+
 /// Convert HTML string to markdown by extracting main element content.
 ///
 /// This function parses the HTML, extracts the content within the <main>
-/// element, and converts it to markdown format.
+/// element, and converts it to markdown format. Links are rendered as their
+/// inner content only.
 pub fn convert(html: &str) -> Result<String> {
+    convert_html(html, None)
+}
+
+/// Convert a rustdoc HTML page to markdown, preserving links.
+///
+/// Item pages are rendered with the sections of [`Section`], under stable
+/// headings, except for the sections in `page.skipped_sections`. Links to
+/// rustdoc items, in this crate or in other crates of the same doc
+/// directory, are rewritten to fully qualified item paths such as
+/// `[Deserializer](serde::Deserializer)`, which `cargo txt show` accepts.
+/// External URLs are kept as they are. Links inside code and headings are
+/// rendered as their inner content only.
+pub fn convert_page(html: &str, page: &DocPage) -> Result<String> {
+    convert_html(html, Some(page))
+}
+
+/// Convert a rustdoc item page to markdown, along with the metadata of the
+/// item.
+///
+/// This is `convert_page` and `item_info` with a single parse of the page.
+pub fn convert_item_page(html: &str, page: &DocPage) -> Result<(String, ItemInfo)> {
     let document = Html::parse_document(html);
+    let markdown = convert_document(&document, Some(page))?;
+    Ok((markdown, document_item_info(&document)?))
+}
+
+/// Return the metadata of the item of a rustdoc item page.
+///
+/// The signature is the text of the declaration block and the summary the
+/// first sentence of the item documentation. Deprecation, stability and
+/// required features come from the notices rustdoc renders below the
+/// declaration, where the feature gate of an unstable item is the first code
+/// span. The kind is left to the caller, which knows the page file name.
+pub fn item_info(html: &str) -> Result<ItemInfo> {
+    document_item_info(&Html::parse_document(html))
+}
+
+/// Return the metadata of the item of a parsed rustdoc item page.
+fn document_item_info(document: &Html) -> Result<ItemInfo> {
+    let selector = |css: &str| match Selector::parse(css) {
+        Ok(s) => Ok(s),
+        Err(e) => bail!("failed to parse HTML selector '{}': {}", css, e),
+    };
+    let declaration_selector = selector("#main-content > pre.item-decl")?;
+    let paragraph_selector =
+        selector("#main-content > details.top-doc > .docblock > p, #main-content > .docblock > p")?;
+    let deprecated_selector = selector("#main-content > .item-info > .stab.deprecated")?;
+    let unstable_selector = selector("#main-content > .item-info > .stab.unstable")?;
+    let portability_selector = selector("#main-content > .item-info > .stab.portability")?;
+    let code_selector = selector("code")?;
+    let emoji_selector = selector(".emoji")?;
+
+    let mut info = ItemInfo::default();
+
+    if let Some(declaration) = document.select(&declaration_selector).next() {
+        let signature: String = declaration.text().collect();
+        info.signature = Some(signature.trim().to_string());
+    }
+
+    if let Some(paragraph) = document.select(&paragraph_selector).next() {
+        let mut text = String::new();
+        convert_children(paragraph, &mut text, None);
+        let summary = item_info::first_sentence(&text);
+        if !summary.is_empty() {
+            info.summary = Some(summary);
+        }
+    }
+
+    if let Some(notice) = document.select(&deprecated_selector).next() {
+        let mut text = String::new();
+        convert_children(notice, &mut text, None);
+        let emoji: String = notice
+            .select(&emoji_selector)
+            .flat_map(|emoji| emoji.text())
+            .collect();
+        let text = text.trim().strip_prefix(emoji.as_str()).unwrap_or(&text);
+        info.deprecated = Some(text.split_whitespace().collect::<Vec<&str>>().join(" "));
+    }
+
+    if let Some(notice) = document.select(&unstable_selector).next() {
+        info.stability = Stability::Unstable;
+        info.feature_gates = notice
+            .select(&code_selector)
+            .take(1)
+            .map(|code| code.text().collect::<String>().trim().to_string())
+            .collect();
+    }
+
+    if let Some(notice) = document.select(&portability_selector).next() {
+        info.required_features = Portability::of(notice).features;
+    }
+
+    Ok(info)
+}
+
+/// Convert HTML to markdown, resolving links relative to `page` if any.
+fn convert_html(html: &str, page: Option<&DocPage>) -> Result<String> {
+    convert_document(&Html::parse_document(html), page)
+}
+
+/// Convert a parsed HTML document to markdown, resolving links relative to
+/// `page` if any.
+fn convert_document(document: &Html, page: Option<&DocPage>) -> Result<String> {
     let selector = match Selector::parse("main") {
         Ok(s) => s,
         Err(e) => bail!("failed to parse HTML selector for main element: {}", e),
@@ -26,10 +149,91 @@ pub fn convert(html: &str) -> Result<String> {
     };
 
     let mut markdown = String::new();
-    convert_node(main_element, &mut markdown);
+    convert_node(main_element, &mut markdown, page);
     Ok(markdown)
 }
 
+/// Return the rustdoc anchors of the members documented on an item page.
+///
+/// Methods, associated types, associated constants, enum variants and
+/// fields are found by the `id` of their headings, e.g. `method.push`,
+/// `variant.Circle` or `structfield.size`. Anchors of duplicate members are
+/// left out, see `MemberKind::from_anchor`.
+pub fn member_anchors(html: &str) -> Vec<String> {
+    let mut anchors = Vec::new();
+    for (start, _) in html.match_indices(" id=\"") {
+        let rest = &html[start + 5..];
+        let Some(end) = rest.find('"') else {
+            break;
+        };
+        let anchor = &rest[..end];
+        if MemberKind::from_anchor(anchor).is_some() {
+            anchors.push(anchor.to_string());
+        }
+    }
+    anchors
+}
+
+/// Return the re-exports listed on a module page.
+///
+/// Rustdoc lists the `pub use` declarations of a module that are not inlined
+/// under "Re-exports", with a link to the target titled with its kind and
+/// canonical path (e.g., `fn tokio::task::spawn`). Re-exports of items from
+/// other crates are left out, since their pages are not part of this crate.
+pub fn reexports(html: &str, module: &str) -> Result<Vec<Reexport>> {
+    if !html.contains("id=\"reexports\"") {
+        return Ok(Vec::new());
+    }
+
+    let lib_prefix = match module.split_once("::") {
+        Some((lib_name, _)) => format!("{}::", lib_name),
+        None => format!("{}::", module),
+    };
+    let document = Html::parse_document(html);
+    let entry_selector = match Selector::parse("dl.reexports > dt code") {
+        Ok(s) => s,
+        Err(e) => bail!("failed to parse HTML selector for re-exports: {}", e),
+    };
+    let link_selector = match Selector::parse("a[title]") {
+        Ok(s) => s,
+        Err(e) => bail!("failed to parse HTML selector for re-export links: {}", e),
+    };
+
+    let mut reexports = Vec::new();
+    for code in document.select(&entry_selector) {
+        let text: String = code.text().collect();
+        let Some(declaration) = text
+            .trim()
+            .strip_prefix("pub use ")
+            .and_then(|declaration| declaration.strip_suffix(';'))
+        else {
+            continue;
+        };
+        let Some(target) = code
+            .select(&link_selector)
+            .last()
+            .and_then(|link| link.value().attr("title"))
+            .and_then(|title| title.split_once(' '))
+            .map(|(_, path)| path)
+            .filter(|path| path.starts_with(&lib_prefix))
+        else {
+            continue;
+        };
+
+        let name = match declaration.rsplit_once(" as ") {
+            _ if declaration.ends_with("::*") => None,
+            Some((_, alias)) => Some(alias.trim()),
+            None => declaration.rsplit("::").next(),
+        };
+        reexports.push(Reexport {
+            module: module.to_string(),
+            name: name.map(str::to_string),
+            target: target.to_string(),
+        });
+    }
+    Ok(reexports)
+}
+
 /// Check if a node should be skipped based on its attributes.
 ///
 /// Returns true for rustdoc-specific elements that should not be rendered
@@ -42,14 +246,15 @@ fn should_skip_node(node: ElementRef) -> bool {
         _ => {}
     }
 
-    match elem.attr("id") {
-        Some("copy-path") | Some("implementors") | Some("implementors-list") => return true,
-        _ => {}
+    if elem.attr("id") == Some("copy-path") {
+        return true;
     }
 
     let should_skip_class = match elem.attr("class") {
         Some(class) => {
             class.contains("src")
+                || class.contains("rightside")
+                || class.contains("sub-heading")
                 || class.contains("hideme")
                 || class.contains("anchor")
                 || class.contains("rustdoc-breadcrumbs")
@@ -64,11 +269,95 @@ fn should_skip_node(node: ElementRef) -> bool {
     false
 }
 
+/// Requirements of a portability notice, which docs.rs builds render below
+/// the declaration of items gated by `cfg` (e.g., "Available on crate feature
+/// `std` only.").
+#[derive(Debug, PartialEq)]
+struct Portability {
+    /// Crate features that must all be enabled, empty when the condition
+    /// is a disjunction or a negation
+    features: Vec<String>,
+    /// Whether the condition requires nothing but these features
+    features_only: bool,
+}
+
+impl Portability {
+    /// Parse the condition of a portability notice.
+    ///
+    /// The condition is a list of requirements joined by "and" and commas,
+    /// where "crate feature `x`" and "crate features `x` and `y`" name
+    /// crate features and other requirements name platforms or `cfg`
+    /// options (e.g., "Unix").
+    fn of(notice: ElementRef) -> Portability {
+        let mut text = String::new();
+        convert_children(notice, &mut text, None);
+        let text = text.replace("**", "");
+        let text = text.split_whitespace().collect::<Vec<&str>>().join(" ");
+        let condition = text
+            .trim_start_matches("Available on ")
+            .trim_end_matches('.')
+            .trim_end_matches(" only");
+
+        if condition.contains(" or ") || condition.contains("non-crate") {
+            return Portability {
+                features: Vec::new(),
+                features_only: false,
+            };
+        }
+
+        let mut features = Vec::new();
+        let mut features_only = true;
+        let mut in_features = false;
+        for part in condition.split(" and ").flat_map(|part| part.split(", ")) {
+            let mut part = part.trim();
+            if let Some(rest) = part
+                .strip_prefix("crate features ")
+                .or_else(|| part.strip_prefix("crate feature "))
+            {
+                in_features = true;
+                part = rest;
+            }
+            let name = part
+                .strip_prefix('`')
+                .and_then(|part| part.strip_suffix('`'))
+                .filter(|name| !name.contains('`'));
+            match name {
+                Some(name) if in_features => features.push(name.to_string()),
+                _ => {
+                    in_features = false;
+                    features_only = false;
+                }
+            }
+        }
+
+        Portability {
+            features_only: features_only && !features.is_empty(),
+            features,
+        }
+    }
+}
+
+/// Check whether a node is the portability notice of the item of a page,
+/// rather than of one of its members.
+fn is_item_portability(node: ElementRef) -> bool {
+    let classes = || node.value().classes();
+    let is_notice = classes().any(|c| c == "stab") && classes().any(|c| c == "portability");
+    let item_info = node.parent().and_then(ElementRef::wrap);
+    is_notice
+        && item_info.is_some_and(|info| {
+            info.value().classes().any(|c| c == "item-info")
+                && info
+                    .parent()
+                    .and_then(ElementRef::wrap)
+                    .is_some_and(|main| main.value().id() == Some("main-content"))
+        })
+}
+
 /// Recursively convert HTML nodes to markdown.
 ///
 /// This function walks through the HTML node tree and converts each element
 /// to its markdown equivalent, handling nested elements appropriately.
-fn convert_node(node: ElementRef, output: &mut String) {
+fn convert_node(node: ElementRef, output: &mut String, page: Option<&DocPage>) {
     if should_skip_node(node) {
         return;
     }
@@ -76,38 +365,17 @@ fn convert_node(node: ElementRef, output: &mut String) {
     let name = node.value().name();
 
     match name {
-        "h1" => {
-            output.push_str("# ");
-            convert_children_normalized(node, output);
-            output.push_str("\n\n");
-        }
-        "h2" => {
-            output.push_str("## ");
-            convert_children_normalized(node, output);
-            output.push_str("\n\n");
-        }
-        "h3" => {
-            output.push_str("### ");
-            convert_children_normalized(node, output);
-            output.push_str("\n\n");
-        }
-        "h4" => {
-            output.push_str("#### ");
-            convert_children_normalized(node, output);
-            output.push_str("\n\n");
-        }
-        "h5" => {
-            output.push_str("##### ");
-            convert_children_normalized(node, output);
-            output.push_str("\n\n");
-        }
-        "h6" => {
-            output.push_str("###### ");
-            convert_children_normalized(node, output);
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+            output.push_str(&"#".repeat(heading_level(node)));
+            output.push(' ');
+            match rustdoc_section(node).and_then(|(_, title)| title) {
+                Some(title) => output.push_str(title),
+                None => convert_children_normalized(node, output, page),
+            }
             output.push_str("\n\n");
         }
         "p" => {
-            convert_children_normalized(node, output);
+            convert_children_normalized(node, output, page);
             output.push_str("\n\n");
         }
         "code" => {
@@ -119,67 +387,159 @@ fn convert_node(node: ElementRef, output: &mut String) {
 
             if !is_code_block {
                 output.push('`');
-                convert_children(node, output);
+                convert_children(node, output, page);
                 output.push('`');
             } else {
-                convert_children(node, output);
+                convert_children(node, output, page);
             }
         }
         "pre" => {
-            output.push_str("```\n");
-            convert_children(node, output);
+            if node.value().classes().any(|c| c == "item-decl") {
+                if !section_enabled(page, Section::Declaration) {
+                    return;
+                }
+                output.push_str(&format!("## {}\n\n", Section::Declaration.title()));
+            }
+            output.push_str("```");
+            output.push_str(&code_fence_info(node));
+            output.push('\n');
+            convert_children(node, output, page);
             output.push_str("\n```\n\n");
         }
-        "div" | "section" | "article" | "header" | "footer" | "nav" | "aside" => {
-            convert_children(node, output);
+        "details" if node.value().classes().any(|c| c == "top-doc") => {
+            if !section_enabled(page, Section::Description) {
+                return;
+            }
+            output.push_str(&format!("## {}\n\n", Section::Description.title()));
+            convert_children(node, output, page);
         }
-        "span" => {
-            convert_children(node, output);
+        "div" if is_item_portability(node) => {
+            let portability = Portability::of(node);
+            if portability.features_only {
+                output.push_str(&format!(
+                    "**Required features**: {}\n\n",
+                    portability
+                        .features
+                        .iter()
+                        .map(|feature| format!("`{}`", feature))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            } else {
+                convert_children_normalized(node, output, page);
+                output.push_str("\n\n");
+            }
         }
+        "div" => {
+            // Where clauses of signatures are laid out as blocks.
+            if node.value().classes().any(|c| c == "where") {
+                output.push(' ');
+            }
+            convert_children(node, output, page);
+            if node.value().id() == Some("implementors-list")
+                && let Some(page) = page
+            {
+                convert_external_implementors(node, output, page);
+            }
+            // Text directly inside a div, such as the docs of trait
+            // implementation methods, ends a block.
+            let in_pre = node
+                .ancestors()
+                .filter_map(ElementRef::wrap)
+                .any(|ancestor| ancestor.value().name() == "pre");
+            if !in_pre && !output.is_empty() && !output.ends_with('\n') {
+                output.push_str("\n\n");
+            }
+        }
+        "section" | "article" | "header" | "footer" | "nav" | "aside" => {
+            convert_children(node, output, page);
+        }
+        "span" => match node.value().id() {
+            // Field signatures are spans rather than headings, render them
+            // as headings so that `show` can find them like other members.
+            Some(id) if id.starts_with("structfield.") || id.contains(".field.") => {
+                let level = if id.starts_with("structfield.") { 3 } else { 5 };
+                output.push_str(&"#".repeat(level));
+                output.push(' ');
+                convert_children_normalized(node, output, page);
+                output.push_str("\n\n");
+            }
+            _ => convert_children(node, output, page),
+        },
         "a" => {
-            convert_children(node, output);
+            let target = page.and_then(|page| link_target(node, page));
+            let Some(target) = target else {
+                convert_children(node, output, page);
+                return;
+            };
+
+            let mut text = String::new();
+            convert_children(node, &mut text, page);
+            // Trait method summaries end with a "Read more" link to the
+            // trait member, which is already linked from the implementation.
+            if text.trim() == "Read more" {
+                if !links_to_member(node) {
+                    output.push_str(&text);
+                }
+                return;
+            }
+            if text.trim().is_empty() {
+                output.push_str(&text);
+                return;
+            }
+            output.push('[');
+            output.push_str(&text);
+            output.push_str("](");
+            output.push_str(&target);
+            output.push(')');
         }
         "ul" | "ol" => {
-            convert_list(node, output, name == "ol");
+            convert_list(node, output, name == "ol", page);
             output.push('\n');
         }
         "li" => {
-            convert_list_item(node, output);
+            convert_list_item(node, output, page);
         }
         "dl" => {
-            convert_definition_list(node, output);
+            convert_definition_list(node, output, page);
             output.push('\n');
         }
+        "table" if in_table_cell(node) => {
+            convert_inline_table(node, output, page);
+        }
+        "table" => {
+            convert_table(node, output, page);
+        }
         "dt" => {
             output.push_str("- **");
-            convert_children(node, output);
+            convert_children(node, output, page);
             output.push_str("**");
         }
         "dd" => {
             output.push_str(": ");
-            convert_children(node, output);
+            convert_children(node, output, page);
             output.push('\n');
         }
         "strong" | "b" => {
             output.push_str("**");
-            convert_children(node, output);
+            convert_children(node, output, page);
             output.push_str("**");
         }
         "em" | "i" => {
             output.push('_');
-            convert_children(node, output);
+            convert_children(node, output, page);
             output.push('_');
         }
         "blockquote" => {
             output.push_str("> ");
-            convert_children(node, output);
+            convert_children(node, output, page);
             output.push_str("\n\n");
         }
         "br" => {
             output.push_str("\n\n");
         }
         _ => {
-            convert_children(node, output);
+            convert_children(node, output, page);
         }
     }
 }
@@ -188,17 +548,31 @@ fn convert_node(node: ElementRef, output: &mut String) {
 ///
 /// This is used for block-level elements where whitespace should be collapsed
 /// into single spaces (paragraphs, headings, list items, definition terms/descriptions).
-fn convert_children_normalized(node: ElementRef, output: &mut String) {
+fn convert_children_normalized(node: ElementRef, output: &mut String, page: Option<&DocPage>) {
     let mut buffer = String::new();
-    convert_children(node, &mut buffer);
+    convert_children(node, &mut buffer, page);
     let normalized: Vec<&str> = buffer.split_whitespace().collect();
     let normalized = normalized.join(" ");
     output.push_str(&normalized);
 }
 
 /// Convert children of a node to markdown.
-fn convert_children(node: ElementRef, output: &mut String) {
+///
+/// A skipped section header is left out together with the siblings that
+/// follow it, up to the next `<h2>`.
+fn convert_children(node: ElementRef, output: &mut String, page: Option<&DocPage>) {
+    let mut skipping = false;
     for child in node.children() {
+        if let Some(elem) = ElementRef::wrap(child)
+            && elem.value().name() == "h2"
+        {
+            skipping =
+                rustdoc_section(elem).is_some_and(|(section, _)| !section_enabled(page, section));
+        }
+        if skipping {
+            continue;
+        }
+
         match child.value() {
             scraper::Node::Text(text) => {
                 let mut text_str = text.text.to_string();
@@ -214,7 +588,7 @@ fn convert_children(node: ElementRef, output: &mut String) {
                 let Some(elem_ref) = ElementRef::wrap(child) else {
                     continue;
                 };
-                convert_node(elem_ref, output);
+                convert_node(elem_ref, output, page);
             }
             _ => {}
         }
@@ -222,7 +596,7 @@ fn convert_children(node: ElementRef, output: &mut String) {
 }
 
 /// Convert a list node to markdown.
-fn convert_list(node: ElementRef, output: &mut String, is_ordered: bool) {
+fn convert_list(node: ElementRef, output: &mut String, is_ordered: bool, page: Option<&DocPage>) {
     let mut index = 1;
     for child in node.children() {
         let Some(elem) = child.value().as_element() else {
@@ -238,22 +612,22 @@ fn convert_list(node: ElementRef, output: &mut String, is_ordered: bool) {
             let Some(li_node) = ElementRef::wrap(child) else {
                 continue;
             };
-            convert_list_item(li_node, output);
+            convert_list_item(li_node, output, page);
             output.push('\n');
         }
     }
 }
 
 /// Convert a list item to markdown with normalized whitespace.
-fn convert_list_item(node: ElementRef, output: &mut String) {
-    convert_children_normalized(node, output);
+fn convert_list_item(node: ElementRef, output: &mut String, page: Option<&DocPage>) {
+    convert_children_normalized(node, output, page);
 }
 
 /// Convert a definition list (<dl>) to markdown.
 ///
 /// Renders definition terms as bold list items and descriptions on the same line.
 /// Format: "- **Term**: Description"
-fn convert_definition_list(node: ElementRef, output: &mut String) {
+fn convert_definition_list(node: ElementRef, output: &mut String, page: Option<&DocPage>) {
     let mut current_term: Option<String> = None;
     let mut has_description = false;
 
@@ -274,28 +648,490 @@ fn convert_definition_list(node: ElementRef, output: &mut String) {
 
                 output.push_str("- **");
                 let mut term_text = String::new();
-                convert_children_normalized(dt_node, &mut term_text);
+                convert_children_normalized(dt_node, &mut term_text, page);
                 output.push_str(&term_text);
                 output.push_str("**");
                 current_term = Some(term_text);
                 has_description = false;
             }
-            "dd" => {
-                if current_term.is_some() {
-                    output.push_str(": ");
-                    let Some(dd_node) = ElementRef::wrap(child) else {
+            "dd" if current_term.is_some() => {
+                output.push_str(": ");
+                let Some(dd_node) = ElementRef::wrap(child) else {
+                    continue;
+                };
+                convert_children_normalized(dd_node, output, page);
+                has_description = true;
+            }
+            _ => {}
+        }
+    }
+
+    if current_term.is_some() && has_description {
+        output.push('\n');
+    }
+}
+
+/// Return the markdown heading level of a heading element.
+///
+/// Headings of the crate or item documentation are rendered one level below
+/// the "Description" section they belong to.
+fn heading_level(node: ElementRef) -> usize {
+    let level = node.value().name()[1..].parse::<usize>().unwrap_or(1);
+    let in_description = node
+        .ancestors()
+        .filter_map(ElementRef::wrap)
+        .any(|ancestor| {
+            ancestor.value().name() == "details"
+                && ancestor.value().classes().any(|c| c == "top-doc")
+        });
+    if in_description {
+        (level + 1).min(6)
+    } else {
+        level
+    }
+}
+
+/// Return the section of a rustdoc section header, with its heading.
+fn rustdoc_section(node: ElementRef) -> Option<(Section, Option<&'static str>)> {
+    let elem = node.value();
+    if !elem.classes().any(|c| c == "section-header") {
+        return None;
+    }
+    Section::from_rustdoc_id(elem.id()?)
+}
+
+/// Return whether a section is rendered on a page.
+///
+/// Every section is rendered when there is no page.
+fn section_enabled(page: Option<&DocPage>, section: Section) -> bool {
+    page.is_none_or(|page| !page.skipped_sections.contains(&section))
+}
+
+/// Append the implementors of a trait found in other crates.
+///
+/// Rustdoc renders the implementors of the page's crate, and of the crates
+/// listed in the `data-ignore-extern-crates` attribute of the implementors
+/// script, into the page itself. The others are loaded from the
+/// `trait.impl` script.
+fn convert_external_implementors(node: ElementRef, output: &mut String, page: &DocPage) {
+    let Some(script) = page.implementors else {
+        return;
+    };
+
+    let ignored: Vec<&str> = node
+        .parent()
+        .into_iter()
+        .flat_map(|parent| parent.children())
+        .filter_map(ElementRef::wrap)
+        .filter_map(|sibling| sibling.value().attr("data-ignore-extern-crates"))
+        .flat_map(|crates| crates.split(','))
+        .collect();
+
+    for (krate, implementors) in parse_implementors(script) {
+        if krate == page.lib_name || ignored.contains(&krate.as_str()) {
+            continue;
+        }
+        for implementor in implementors {
+            let fragment = Html::parse_fragment(&implementor);
+            let mut header = String::new();
+            convert_children_normalized(fragment.root_element(), &mut header, None);
+            if !header.is_empty() {
+                output.push_str(&format!("### {}\n\n", header));
+            }
+        }
+    }
+}
+
+/// Parse a rustdoc `trait.impl` script into the implementor headers, as
+/// HTML, of each crate.
+///
+/// Both the `Object.fromEntries([[crate, [...]], ...])` form and the older
+/// `{"crate": [...], ...}` form are supported. Returns an empty list when the
+/// script cannot be parsed.
+fn parse_implementors(script: &str) -> Vec<(String, Vec<String>)> {
+    let Some(start) = script.find("implementors = ") else {
+        return Vec::new();
+    };
+    let rest = &script[start + "implementors = ".len()..];
+    let (rest, entries_form) = match rest.strip_prefix("Object.fromEntries(") {
+        Some(rest) => (rest, true),
+        None => (rest, false),
+    };
+
+    let mut values = serde_json::Deserializer::from_str(rest).into_iter::<serde_json::Value>();
+    let Some(Ok(value)) = values.next() else {
+        return Vec::new();
+    };
+
+    let entries: Vec<(String, &serde_json::Value)> = if entries_form {
+        value
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| {
+                let krate = entry.get(0)?.as_str()?;
+                Some((krate.to_string(), entry.get(1)?))
+            })
+            .collect()
+    } else {
+        value
+            .as_object()
+            .into_iter()
+            .flatten()
+            .map(|(krate, implementors)| (krate.clone(), implementors))
+            .collect()
+    };
+
+    entries
+        .into_iter()
+        .map(|(krate, implementors)| {
+            let headers = implementors
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|implementor| implementor.get(0)?.as_str().map(str::to_string))
+                .collect();
+            (krate, headers)
+        })
+        .collect()
+}
+
+/// Rustdoc example attributes kept as code fence info.
+const EXAMPLE_ATTRIBUTES: [&str; 3] = ["ignore", "compile_fail", "should_panic"];
+
+/// Return the info string of the code fence for a `<pre>` element.
+///
+/// Rust code is tagged `rust`, other languages keep the language of their
+/// `language-*` class and untagged blocks are tagged `text`. Example
+/// attributes are appended rustdoc-style (e.g., `rust,compile_fail` or
+/// `rust,edition2018`). Item declarations are tagged `rust,item-decl` so they
+/// can be told apart from examples.
+fn code_fence_info(node: ElementRef) -> String {
+    let classes: Vec<&str> = node.value().classes().collect();
+    if classes.contains(&"item-decl") {
+        return "rust,item-decl".to_string();
+    }
+
+    let mut info = if classes.contains(&"rust") {
+        "rust".to_string()
+    } else if let Some(language) = classes.iter().find_map(|c| c.strip_prefix("language-")) {
+        language.to_string()
+    } else {
+        "text".to_string()
+    };
+
+    // Attributes are set on the example wrapper, and on the `<pre>` itself
+    // in older rustdoc versions.
+    let wrapper = node
+        .parent()
+        .and_then(ElementRef::wrap)
+        .filter(|parent| parent.value().classes().any(|c| c == "example-wrap"));
+    let wrapper_classes: Vec<&str> = wrapper
+        .map(|wrapper| wrapper.value().classes().collect())
+        .unwrap_or_default();
+    for attribute in EXAMPLE_ATTRIBUTES {
+        if classes.contains(&attribute) || wrapper_classes.contains(&attribute) {
+            info.push(',');
+            info.push_str(attribute);
+        }
+    }
+
+    if let Some(edition) = wrapper
+        .filter(|_| wrapper_classes.contains(&"edition"))
+        .and_then(example_edition)
+    {
+        info.push_str(",edition");
+        info.push_str(&edition);
+    }
+
+    info
+}
+
+/// Return the edition of an example from the title of its tooltip, e.g.
+/// "This example runs with edition 2018".
+fn example_edition(wrapper: ElementRef) -> Option<String> {
+    let tooltip = wrapper
+        .children()
+        .filter_map(ElementRef::wrap)
+        .find(|child| child.value().classes().any(|c| c == "tooltip"))?;
+    let title = tooltip.value().attr("title")?;
+    let edition = title.rsplit(' ').next()?;
+    edition
+        .chars()
+        .all(|c| c.is_ascii_digit())
+        .then(|| edition.to_string())
+}
+
+/// Check whether a table is nested in a cell of another table.
+fn in_table_cell(node: ElementRef) -> bool {
+    node.ancestors()
+        .filter_map(ElementRef::wrap)
+        .any(|ancestor| matches!(ancestor.value().name(), "td" | "th"))
+}
+
+/// Check whether a `<tr>` belongs to `table` rather than to a table nested
+/// in one of its cells.
+fn is_row_of(row: ElementRef, table: ElementRef) -> bool {
+    row.ancestors()
+        .filter_map(ElementRef::wrap)
+        .find(|ancestor| ancestor.value().name() == "table")
+        .is_some_and(|ancestor| ancestor.id() == table.id())
+}
+
+/// Convert a table nested in a table cell to a single line.
+///
+/// Markdown tables cannot be nested, so the cells of a row are separated by
+/// ", " and the rows by "; ".
+fn convert_inline_table(node: ElementRef, output: &mut String, page: Option<&DocPage>) {
+    let rows: Vec<String> = node
+        .descendants()
+        .filter_map(ElementRef::wrap)
+        .filter(|elem| elem.value().name() == "tr" && is_row_of(*elem, node))
+        .map(|row| {
+            let cells: Vec<String> = row
+                .children()
+                .filter_map(ElementRef::wrap)
+                .filter(|cell| matches!(cell.value().name(), "td" | "th"))
+                .map(|cell| {
+                    let mut content = String::new();
+                    convert_children_normalized(cell, &mut content, page);
+                    content
+                })
+                .filter(|content| !content.is_empty())
+                .collect();
+            cells.join(", ")
+        })
+        .filter(|row| !row.is_empty())
+        .collect();
+    output.push_str(&rows.join("; "));
+}
+
+/// A table cell with its spans, collected by `convert_table`.
+struct TableCell {
+    content: String,
+    colspan: usize,
+    rowspan: usize,
+}
+
+/// Convert a table to a GitHub-flavored markdown table.
+///
+/// The first row is used as the header when it comes from `<thead>` or only
+/// contains `<th>` cells; otherwise an empty header row is emitted. Pipes in
+/// cells are escaped. Cells spanning several columns or rows keep their
+/// content in the first cell and leave the cells they cover empty.
+/// Tables nested in a cell are rendered inline, see `convert_inline_table`.
+fn convert_table(node: ElementRef, output: &mut String, page: Option<&DocPage>) {
+    let mut caption = String::new();
+    let mut rows: Vec<(bool, Vec<TableCell>)> = Vec::new();
+
+    for descendant in node.descendants() {
+        let Some(elem) = ElementRef::wrap(descendant) else {
+            continue;
+        };
+        match elem.value().name() {
+            "caption" => convert_children_normalized(elem, &mut caption, page),
+            "tr" => {
+                // Rows of nested tables are rendered inside their cell.
+                if !is_row_of(elem, node) {
+                    continue;
+                }
+
+                let in_thead = elem
+                    .parent()
+                    .and_then(|p| p.value().as_element())
+                    .is_some_and(|e| e.name() == "thead");
+                let mut all_th = true;
+                let mut cells = Vec::new();
+                for cell in elem.children().filter_map(ElementRef::wrap) {
+                    let name = cell.value().name();
+                    if name != "td" && name != "th" {
                         continue;
+                    }
+                    all_th &= name == "th";
+
+                    let span = |attr: &str| {
+                        cell.value()
+                            .attr(attr)
+                            .and_then(|v| v.trim().parse::<usize>().ok())
+                            .unwrap_or(1)
+                            .clamp(1, 1000)
                     };
-                    convert_children_normalized(dd_node, output);
-                    has_description = true;
+                    let mut content = String::new();
+                    convert_children_normalized(cell, &mut content, page);
+                    cells.push(TableCell {
+                        content: content.replace('|', "\\|"),
+                        colspan: span("colspan"),
+                        rowspan: span("rowspan"),
+                    });
+                }
+                if !cells.is_empty() {
+                    rows.push((in_thead || all_th, cells));
                 }
             }
             _ => {}
         }
     }
 
-    if current_term.is_some() && has_description {
-        output.push('\n');
+    if rows.is_empty() {
+        return;
+    }
+
+    // Lay out the cells on a grid, leaving covered cells empty.
+    let mut grid: Vec<Vec<String>> = Vec::new();
+    let mut pending_rows: Vec<usize> = Vec::new();
+    for (_, cells) in &rows {
+        let mut row = Vec::new();
+        let mut column = 0;
+        for cell in cells {
+            while pending_rows.get(column).is_some_and(|&pending| pending > 0) {
+                pending_rows[column] -= 1;
+                row.push(String::new());
+                column += 1;
+            }
+            row.push(cell.content.clone());
+            row.extend(std::iter::repeat_n(String::new(), cell.colspan - 1));
+            if pending_rows.len() < column + cell.colspan {
+                pending_rows.resize(column + cell.colspan, 0);
+            }
+            pending_rows[column..column + cell.colspan].fill(cell.rowspan - 1);
+            column += cell.colspan;
+        }
+        while column < pending_rows.len() {
+            pending_rows[column] = pending_rows[column].saturating_sub(1);
+            row.push(String::new());
+            column += 1;
+        }
+        grid.push(row);
+    }
+
+    let columns = grid.iter().map(Vec::len).max().unwrap_or(0);
+    for row in &mut grid {
+        row.resize(columns, String::new());
+    }
+
+    if !caption.is_empty() {
+        output.push_str(&format!("**{}**\n\n", caption));
+    }
+
+    let has_header = rows[0].0;
+    let header = if has_header {
+        grid.remove(0)
+    } else {
+        vec![String::new(); columns]
+    };
+
+    let format_row = |cells: &[String]| format!("| {} |\n", cells.join(" | "));
+    output.push_str(&format_row(&header));
+    output.push_str(&format_row(&vec!["---".to_string(); columns]));
+    for row in &grid {
+        output.push_str(&format_row(row));
+    }
+    output.push('\n');
+}
+
+/// Return the markdown link target of an `<a>` element, if it has one.
+///
+/// Links inside code or headings are not rendered as markdown links, since
+/// markdown does not support links there.
+fn link_target(node: ElementRef, page: &DocPage) -> Option<String> {
+    let href = node.value().attr("href")?;
+
+    let in_code_or_heading = node.ancestors().any(|ancestor| {
+        ancestor.value().as_element().is_some_and(|e| {
+            matches!(
+                e.name(),
+                "code" | "pre" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6"
+            )
+        })
+    });
+    if in_code_or_heading {
+        return None;
+    }
+
+    resolve_link(href, page)
+}
+
+/// Check whether a link points to a member of an item page, such as
+/// `trait.Read.html#tymethod.read`.
+fn links_to_member(node: ElementRef) -> bool {
+    node.value()
+        .attr("href")
+        .and_then(|href| href.split_once('#'))
+        .is_some_and(|(_, anchor)| MemberKind::from_anchor(anchor).is_some())
+}
+
+/// Resolve a link found on a rustdoc page to a markdown link target.
+///
+/// External URLs are returned unchanged. Relative links to item pages are
+/// resolved against the page location and turned into fully qualified item
+/// paths, e.g. `../de/trait.Visitor.html` on a `serde` page becomes
+/// `serde::de::Visitor`. Anchors of members are kept as the member path
+/// that show accepts, e.g. `../de/trait.Visitor.html#method.visit_str`
+/// becomes `serde::de::Visitor::visit_str`. Links to the crate root resolve
+/// to the library name. Returns `None` for same-page anchors, module pages and
+/// other non-item pages.
+fn resolve_link(href: &str, page: &DocPage) -> Option<String> {
+    if href.starts_with("http://") || href.starts_with("https://") {
+        return Some(href.to_string());
+    }
+
+    let (path, anchor) = href.split_once('#').unwrap_or((href, ""));
+    let path = path.split('?').next().unwrap_or_default();
+    if path.is_empty() || href.contains("://") {
+        return None;
+    }
+
+    let mut segments: Vec<&str> = vec![page.lib_name];
+    segments.extend(page.path.split('/'));
+    segments.pop();
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop()?;
+            }
+            _ => segments.push(segment),
+        }
+    }
+
+    let file = segments.pop()?;
+    let is_identifier =
+        |s: &&str| !s.is_empty() && s.chars().all(|c| c.is_alphanumeric() || c == '_');
+    if segments.is_empty() || segments[0] == "src" || !segments.iter().all(is_identifier) {
+        return None;
+    }
+
+    if file == "index.html" {
+        return (segments.len() == 1).then(|| segments[0].to_string());
+    }
+
+    let (kind, name) = file.strip_suffix(".html")?.split_once('.')?;
+    let is_item_kind = matches!(
+        kind,
+        "struct"
+            | "enum"
+            | "union"
+            | "trait"
+            | "traitalias"
+            | "fn"
+            | "type"
+            | "macro"
+            | "derive"
+            | "attr"
+            | "constant"
+            | "static"
+            | "primitive"
+            | "keyword"
+    );
+    if !is_item_kind || !is_identifier(&name) {
+        return None;
+    }
+
+    segments.push(name);
+    let item_path = segments.join("::");
+    match MemberKind::from_anchor(anchor) {
+        Some((_, member)) => Some(format!("{}::{}", item_path, member)),
+        None => Some(item_path),
     }
 }
 
@@ -331,10 +1167,20 @@ fn process_text_links(text: &str) -> String {
                 }
             }
 
+            // Highlighted code splits brackets across text nodes, so an
+            // unclosed bracket is plain text.
+            if bracket_count > 0 {
+                result.push('[');
+                result.push_str(&link_text);
+                continue;
+            }
+
+            let mut spaces = String::new();
             while let Some(next) = chars.peek() {
                 let next_char = *next;
                 if next_char.is_whitespace() {
                     chars.next();
+                    spaces.push(next_char);
                 } else {
                     break;
                 }
@@ -360,6 +1206,7 @@ fn process_text_links(text: &str) -> String {
                     result.push('[');
                     result.push_str(&link_text);
                     result.push(']');
+                    result.push_str(&spaces);
                 }
             }
         } else {
@@ -441,7 +1288,7 @@ mod tests {
     fn convert_code_block() {
         let html = "<main><pre><code>fn test() {}</code></pre></main>";
         let result = convert(html).unwrap();
-        assert_eq!(result, "```\nfn test() {}\n```\n\n");
+        assert_eq!(result, "```text\nfn test() {}\n```\n\n");
     }
 
     #[test]
@@ -537,6 +1384,17 @@ mod tests {
         assert_eq!(result, "- Derive tutorial and reference\n\n");
     }
 
+    #[test]
+    fn process_text_links_keeps_plain_brackets() {
+        assert_eq!(process_text_links("["), "[");
+        assert_eq!(process_text_links("[1, 2"), "[1, 2");
+        assert_eq!(process_text_links("a[i] = 1"), "a[i] = 1");
+        assert_eq!(process_text_links("see [docs] [ref] now"), "see docs now");
+
+        let html = r#"<main><pre class="rust rust-example-rendered"><code><span class="macro">vec!</span>[<span class="number">1</span>, <span class="number">2</span>];</code></pre></main>"#;
+        assert_eq!(convert(html).unwrap(), "```rust\nvec![1, 2];\n```\n\n");
+    }
+
     #[test]
     fn convert_paragraph_whitespace_normalization() {
         let html = r#"<main><p>
@@ -584,7 +1442,10 @@ mod tests {
     fn convert_code_block_with_newline() {
         let html = r#"<main><div class="example-wrap"><pre class="language-console"><code>$ cargo add clap --features derive</code></pre></div></main>"#;
         let result = convert(html).unwrap();
-        assert_eq!(result, "```\n$ cargo add clap --features derive\n```\n\n");
+        assert_eq!(
+            result,
+            "```console\n$ cargo add clap --features derive\n```\n\n"
+        );
     }
 
     #[test]
@@ -636,7 +1497,20 @@ mod tests {
     }
 
     #[test]
-    fn convert_implementors_section_skipped() {
+    fn convert_stability_versions_skipped() {
+        let html = r##"<main>
+            <div class="main-heading"><h1>Struct <span class="struct">Vec</span></h1><span class="sub-heading"><span class="since" title="Stable since Rust version 1.0.0">1.0.0</span> · <a class="src" href="../../src/alloc/vec/mod.rs.html#438">Source</a> </span></div>
+            <section id="method.push" class="method"><span class="rightside"><span class="since" title="Stable since Rust version 1.0.0, const unstable">1.0.0 (const: <a href="https://github.com/rust-lang/rust/issues/79597">unstable</a>)</span> · <a class="src" href="../../src/alloc/vec/mod.rs.html#2681">Source</a></span><h4 class="code-header">pub fn push(&amp;mut self, value: T)</h4></section>
+        </main>"##;
+        let result = convert(html).unwrap();
+        assert_eq!(
+            result,
+            "# Struct Vec\n\n#### pub fn push(&mut self, value: T)\n\n"
+        );
+    }
+
+    #[test]
+    fn convert_implementors_section() {
         let html = r##"<main>
             <p>Some content</p>
             <h2 id="implementors" class="section-header">
@@ -645,24 +1519,28 @@ mod tests {
             <p>More content</p>
         </main>"##;
         let result = convert(html).unwrap();
-        assert_eq!(result, "Some content\n\nMore content\n\n");
+        assert_eq!(
+            result,
+            "Some content\n\n## Implementors\n\nMore content\n\n"
+        );
     }
 
     #[test]
-    fn convert_implementors_list_skipped() {
-        let html = r#"<main>
-            <p>Some content</p>
+    fn convert_implementors_list() {
+        let html = r##"<main>
             <div id="implementors-list">
-                <p>This should not appear</p>
+                <section id="impl-Serialize-for-Config" class="impl">
+                    <a href="#impl-Serialize-for-Config" class="anchor">§</a>
+                    <h3 class="code-header">impl Serialize for Config</h3>
+                </section>
             </div>
-            <p>More content</p>
-        </main>"#;
+        </main>"##;
         let result = convert(html).unwrap();
-        assert_eq!(result, "Some content\n\nMore content\n\n");
+        assert_eq!(result, "### impl Serialize for Config\n\n");
     }
 
     #[test]
-    fn convert_combined_rustdoc_elements_skipped() {
+    fn convert_combined_rustdoc_elements() {
         let html = r##"<main>
             <div class="main-heading">
                 <div class="rustdoc-breadcrumbs">
@@ -686,7 +1564,534 @@ mod tests {
         let result = convert(html).unwrap();
         assert_eq!(
             result,
-            "# Trait Serializer\n\nDescription text\n\nEnd content\n\n"
+            "# Trait Serializer\n\nDescription text\n\n## Implementors\n\nImplementor details\n\nEnd content\n\n"
+        );
+    }
+
+    const SERDE_DE_PAGE: DocPage = DocPage {
+        lib_name: "serde",
+        path: "de/trait.Deserializer.html",
+        skipped_sections: &[],
+        implementors: None,
+    };
+
+    #[test]
+    fn resolve_link_intra_crate() {
+        assert_eq!(
+            resolve_link("trait.Visitor.html", &SERDE_DE_PAGE),
+            Some("serde::de::Visitor".to_string())
+        );
+        assert_eq!(
+            resolve_link("../trait.Serialize.html#tymethod.serialize", &SERDE_DE_PAGE),
+            Some("serde::Serialize::serialize".to_string())
+        );
+        assert_eq!(
+            resolve_link("value/struct.Error.html#method.custom", &SERDE_DE_PAGE),
+            Some("serde::de::value::Error::custom".to_string())
+        );
+        assert_eq!(
+            resolve_link("enum.Unexpected.html#variant.Bool", &SERDE_DE_PAGE),
+            Some("serde::de::Unexpected::Bool".to_string())
+        );
+        assert_eq!(
+            resolve_link("struct.Span.html#structfield.start", &SERDE_DE_PAGE),
+            Some("serde::de::Span::start".to_string())
+        );
+        assert_eq!(
+            resolve_link("trait.Visitor.html#associatedtype.Value", &SERDE_DE_PAGE),
+            Some("serde::de::Visitor::Value".to_string())
+        );
+        assert_eq!(
+            resolve_link(
+                "enum.Shape.html#variant.Circle.field.radius",
+                &SERDE_DE_PAGE
+            ),
+            Some("serde::de::Shape::Circle::radius".to_string())
+        );
+        assert_eq!(
+            resolve_link("trait.Visitor.html#implementors", &SERDE_DE_PAGE),
+            Some("serde::de::Visitor".to_string())
+        );
+        assert_eq!(
+            resolve_link("value/struct.Error.html", &SERDE_DE_PAGE),
+            Some("serde::de::value::Error".to_string())
+        );
+        assert_eq!(
+            resolve_link("../index.html", &SERDE_DE_PAGE),
+            Some("serde".to_string())
+        );
+    }
+
+    #[test]
+    fn resolve_link_other_crate() {
+        assert_eq!(
+            resolve_link("../../serde_core/de/trait.Error.html", &SERDE_DE_PAGE),
+            Some("serde_core::de::Error".to_string())
+        );
+        assert_eq!(
+            resolve_link("../../serde_derive/derive.Deserialize.html", &SERDE_DE_PAGE),
+            Some("serde_derive::Deserialize".to_string())
+        );
+    }
+
+    #[test]
+    fn resolve_link_external_url() {
+        let url = "https://doc.rust-lang.org/1.95.0/core/result/enum.Result.html";
+        assert_eq!(resolve_link(url, &SERDE_DE_PAGE), Some(url.to_string()));
+    }
+
+    #[test]
+    fn resolve_link_non_item_pages() {
+        assert_eq!(
+            resolve_link("#tymethod.deserialize_any", &SERDE_DE_PAGE),
+            None
+        );
+        assert_eq!(resolve_link("#", &SERDE_DE_PAGE), None);
+        assert_eq!(resolve_link("index.html", &SERDE_DE_PAGE), None);
+        assert_eq!(resolve_link("../all.html", &SERDE_DE_PAGE), None);
+        assert_eq!(
+            resolve_link("../../src/serde/de/mod.rs.html#10", &SERDE_DE_PAGE),
+            None
+        );
+        assert_eq!(
+            resolve_link("../../../../outside.html", &SERDE_DE_PAGE),
+            None
+        );
+        assert_eq!(resolve_link("../../settings.html", &SERDE_DE_PAGE), None);
+    }
+
+    #[test]
+    fn convert_page_preserves_links() {
+        let html = r#"<main><p>See <a href="trait.Visitor.html"><code>Visitor</code></a>, <a href="../trait.Serialize.html">Serialize</a> and <a href="https://serde.rs/">the book</a>.</p></main>"#;
+        let result = convert_page(html, &SERDE_DE_PAGE).unwrap();
+        assert_eq!(
+            result,
+            "See [`Visitor`](serde::de::Visitor), [Serialize](serde::Serialize) and [the book](https://serde.rs/).\n\n"
+        );
+    }
+
+    #[test]
+    fn convert_page_drops_read_more_links_to_members() {
+        let html = r##"<main><p>Deserialize a string. <a href="trait.Deserializer.html#tymethod.deserialize_str">Read more</a></p></main>"##;
+        let result = convert_page(html, &SERDE_DE_PAGE).unwrap();
+        assert_eq!(result, "Deserialize a string.\n\n");
+
+        let html = r##"<main><p>See the trait. <a href="trait.Deserializer.html">Read more</a></p></main>"##;
+        let result = convert_page(html, &SERDE_DE_PAGE).unwrap();
+        assert_eq!(result, "See the trait. Read more\n\n");
+    }
+
+    #[test]
+    fn convert_page_links_in_code_and_headings_render_text() {
+        let html = r##"<main><h3 class="code-header">impl <a href="trait.Visitor.html">Visitor</a></h3><pre><code>fn f() -&gt; <a href="trait.Visitor.html">Visitor</a></code></pre><p><a href="#method.f">f</a></p></main>"##;
+        let result = convert_page(html, &SERDE_DE_PAGE).unwrap();
+        assert_eq!(
+            result,
+            "### impl Visitor\n\n```text\nfn f() -> Visitor\n```\n\nf\n\n"
+        );
+    }
+
+    #[test]
+    fn convert_table_with_header() {
+        let html = "<main><table><thead><tr><th>Spec</th><th>Example</th></tr></thead><tbody><tr><td><code>%Y</code></td><td>2001</td></tr><tr><td><code>%m</code></td><td>07</td></tr></tbody></table></main>";
+        let result = convert(html).unwrap();
+        assert_eq!(
+            result,
+            "| Spec | Example |\n| --- | --- |\n| `%Y` | 2001 |\n| `%m` | 07 |\n\n"
+        );
+    }
+
+    #[test]
+    fn convert_table_header_from_th_cells() {
+        let html = "<main><table><tr><th>A</th><th>B</th></tr><tr><td>1</td><td>2</td></tr></table></main>";
+        let result = convert(html).unwrap();
+        assert_eq!(result, "| A | B |\n| --- | --- |\n| 1 | 2 |\n\n");
+    }
+
+    #[test]
+    fn convert_table_without_header() {
+        let html = "<main><table><tr><td>1</td><td>2</td></tr></table></main>";
+        let result = convert(html).unwrap();
+        assert_eq!(result, "|  |  |\n| --- | --- |\n| 1 | 2 |\n\n");
+    }
+
+    #[test]
+    fn convert_table_escapes_pipes() {
+        let html = "<main><table><tr><th>Syntax</th></tr><tr><td><code>x|y</code></td></tr></table></main>";
+        let result = convert(html).unwrap();
+        assert_eq!(result, "| Syntax |\n| --- |\n| `x\\|y` |\n\n");
+    }
+
+    #[test]
+    fn convert_table_colspan_and_rowspan() {
+        let html = "<main><table><tr><th colspan=\"2\">Both</th><th>C</th></tr><tr><td rowspan=\"2\">a</td><td>b</td><td>c</td></tr><tr><td>e</td><td>f</td></tr></table></main>";
+        let result = convert(html).unwrap();
+        assert_eq!(
+            result,
+            "| Both |  | C |\n| --- | --- | --- |\n| a | b | c |\n|  | e | f |\n\n"
+        );
+    }
+
+    #[test]
+    fn convert_table_nested_table_inline() {
+        let html = "<main><table><tr><th>Name</th><th>Values</th></tr><tr><td>x</td><td><table><tr><th>K</th><th>V</th></tr><tr><td>a</td><td>1</td></tr><tr><td>b</td><td><code>c|d</code></td></tr></table></td></tr></table></main>";
+        let result = convert(html).unwrap();
+        assert_eq!(
+            result,
+            "| Name | Values |\n| --- | --- |\n| x | K, V; a, 1; b, `c\\|d` |\n\n"
+        );
+    }
+
+    #[test]
+    fn convert_table_uneven_rows() {
+        let html = "<main><table><tr><th>A</th><th>B</th><th>C</th></tr><tr><td>1</td></tr></table></main>";
+        let result = convert(html).unwrap();
+        assert_eq!(
+            result,
+            "| A | B | C |\n| --- | --- | --- |\n| 1 |  |  |\n\n"
+        );
+    }
+
+    #[test]
+    fn convert_rust_example() {
+        let html = r#"<main><div class="example-wrap"><pre class="rust rust-example-rendered"><code><span class="kw">let </span>x = <span class="number">1</span>;</code></pre></div></main>"#;
+        let result = convert(html).unwrap();
+        assert_eq!(result, "```rust\nlet x = 1;\n```\n\n");
+    }
+
+    #[test]
+    fn convert_example_attributes() {
+        let html = r##"<main><div class="example-wrap compile_fail"><a href="#" class="tooltip" title="This example deliberately fails to compile">ⓘ</a><pre class="rust rust-example-rendered"><code>let x: u8 = "";</code></pre></div><div class="example-wrap should_panic"><a href="#" class="tooltip" title="This example panics">ⓘ</a><pre class="rust rust-example-rendered"><code>panic!();</code></pre></div><div class="example-wrap ignore"><pre class="rust rust-example-rendered ignore"><code>loop {}</code></pre></div></main>"##;
+        let result = convert(html).unwrap();
+        assert_eq!(
+            result,
+            "```rust,compile_fail\nlet x: u8 = \"\";\n```\n\n```rust,should_panic\npanic!();\n```\n\n```rust,ignore\nloop {}\n```\n\n"
+        );
+    }
+
+    #[test]
+    fn convert_example_edition() {
+        let html = r##"<main><div class="example-wrap edition"><a href="#" class="tooltip" title="This example runs with edition 2015">ⓘ</a><pre class="rust rust-example-rendered"><code>trait T { fn f(u8); }</code></pre></div></main>"##;
+        let result = convert(html).unwrap();
+        assert_eq!(
+            result,
+            "```rust,edition2015\ntrait T { fn f(u8); }\n```\n\n"
+        );
+    }
+
+    #[test]
+    fn convert_item_declaration() {
+        let html = r#"<main><pre class="rust item-decl"><code>pub struct Vec&lt;T&gt; { <span class="comment">/* private fields */</span> }</code></pre><div class="example-wrap"><pre class="rust rust-example-rendered"><code>let v: Vec&lt;u8&gt; = Vec::new();</code></pre></div></main>"#;
+        let result = convert(html).unwrap();
+        assert_eq!(
+            result,
+            "## Declaration\n\n```rust,item-decl\npub struct Vec<T> { /* private fields */ }\n```\n\n```rust\nlet v: Vec<u8> = Vec::new();\n```\n\n"
+        );
+    }
+
+    /// A trimmed rustdoc struct page with every section.
+    const STRUCT_PAGE: &str = r##"<main><section id="main-content" class="content">
+        <div class="main-heading"><h1>Struct <span class="struct">Error</span></h1></div>
+        <pre class="rust item-decl"><code>pub struct Error { /* private fields */ }</code></pre>
+        <details class="toggle top-doc" open><summary class="hideme"><span>Expand description</span></summary><div class="docblock"><p>The error type.</p><h2 id="display"><a class="doc-anchor" href="#display">§</a>Display</h2><p>Shows the message.</p></div></details>
+        <h2 id="implementations" class="section-header">Implementations<a href="#implementations" class="anchor">§</a></h2>
+        <div id="implementations-list"><details class="toggle implementors-toggle" open><summary><section id="impl-Error" class="impl"><h3 class="code-header">impl Error</h3></section></summary><div class="impl-items"><details class="toggle method-toggle" open><summary><section id="method.new" class="method"><h4 class="code-header">pub fn new&lt;E&gt;(error: E) -&gt; Self<div class="where">where
+    E: Send,</div></h4></section></summary><div class="docblock"><p>Create a new error.</p></div></details></div></details></div>
+        <h2 id="trait-implementations" class="section-header">Trait Implementations<a href="#trait-implementations" class="anchor">§</a></h2>
+        <div id="trait-implementations-list"><details class="toggle implementors-toggle" open><summary><section id="impl-Debug-for-Error" class="impl"><h3 class="code-header">impl Debug for Error</h3><span class="item-info"><div class="stab portability">Available on <strong>crate feature <code>std</code></strong> only.</div></span></section></summary><div class="impl-items"><details class="toggle method-toggle" open><summary><section id="method.fmt" class="method trait-impl"><h4 class="code-header">fn fmt(&amp;self, f: &amp;mut Formatter) -&gt; Result</h4></section></summary><div class='docblock'>Formats the value using the given formatter.</div></details></div></details></div>
+        <h2 id="synthetic-implementations" class="section-header">Auto Trait Implementations<a href="#synthetic-implementations" class="anchor">§</a></h2>
+        <div id="synthetic-implementations-list"><section id="impl-Send-for-Error" class="impl"><h3 class="code-header">impl Send for Error</h3></section></div>
+        <h2 id="blanket-implementations" class="section-header">Blanket Implementations<a href="#blanket-implementations" class="anchor">§</a></h2>
+        <div id="blanket-implementations-list"><details class="toggle implementors-toggle"><summary><section id="impl-From%3CT%3E-for-T" class="impl"><h3 class="code-header">impl&lt;T&gt; From&lt;T&gt; for T</h3></section></summary></details></div>
+    </section></main>"##;
+
+    #[test]
+    fn convert_page_sections() {
+        let page = DocPage {
+            lib_name: "anyhow",
+            path: "struct.Error.html",
+            skipped_sections: &[],
+            implementors: None,
+        };
+        let result = convert_page(STRUCT_PAGE, &page).unwrap();
+        assert_eq!(
+            result,
+            concat!(
+                "# Struct Error\n\n",
+                "## Declaration\n\n```rust,item-decl\npub struct Error { /* private fields */ }\n```\n\n",
+                "## Description\n\nThe error type.\n\n### Display\n\nShows the message.\n\n",
+                "## Inherent Methods\n\n### impl Error\n\n",
+                "#### pub fn new<E>(error: E) -> Self where E: Send,\n\nCreate a new error.\n\n",
+                "## Trait Implementations\n\n### impl Debug for Error\n\n",
+                "Available on **crate feature `std`** only.\n\n",
+                "#### fn fmt(&self, f: &mut Formatter) -> Result\n\n",
+                "Formats the value using the given formatter.\n\n",
+                "## Auto Trait Implementations\n\n### impl Send for Error\n\n",
+                "## Blanket Implementations\n\n### impl<T> From<T> for T\n\n",
+            )
+        );
+    }
+
+    #[test]
+    fn reexports_of_module_page() {
+        let html = r##"<main><section id="main-content" class="content">
+            <h2 id="reexports" class="section-header">Re-exports<a href="#reexports" class="anchor">§</a></h2>
+            <dl class="item-table reexports">
+                <dt id="reexport.inner"><code>pub use crate::task::<a class="mod" href="../task/nested/index.html" title="mod probe::task::nested">nested</a> as inner;</code></dt>
+                <dt id="reexport.spawn"><code>pub use crate::task::<a class="fn" href="../task/fn.spawn.html" title="fn probe::task::spawn">spawn</a>;</code></dt>
+                <dt><code>pub use crate::<a class="mod" href="../task/index.html" title="mod probe::task">task</a>::*;</code></dt>
+                <dt id="reexport.Error"><code>pub use <a class="struct" href="https://docs.rs/anyhow/latest/anyhow/struct.Error.html" title="struct anyhow::Error">anyhow::Error</a>;</code></dt>
+            </dl>
+        </section></main>"##;
+
+        let reexport = |name: Option<&str>, target: &str| Reexport {
+            module: "probe::util".to_string(),
+            name: name.map(str::to_string),
+            target: target.to_string(),
+        };
+        assert_eq!(
+            reexports(html, "probe::util").unwrap(),
+            vec![
+                reexport(Some("inner"), "probe::task::nested"),
+                reexport(Some("spawn"), "probe::task::spawn"),
+                reexport(None, "probe::task"),
+            ]
+        );
+        assert!(reexports("<main></main>", "probe").unwrap().is_empty());
+    }
+
+    #[test]
+    fn member_anchors_of_page() {
+        assert_eq!(
+            member_anchors(STRUCT_PAGE),
+            vec!["method.new", "method.fmt"]
+        );
+
+        let html = r##"<span id="structfield.size" class="structfield section-header"><code>size: usize</code></span>
+            <section id="variant.Circle" class="variant"><h3 class="code-header">Circle</h3></section>
+            <div id="variant.Circle.fields"><span id="variant.Circle.field.radius" class="section-header"><code>radius: f64</code></span></div>
+            <section id="method.fmt-1" class="method trait-impl"><h4 class="code-header">fn fmt()</h4></section>"##;
+        assert_eq!(
+            member_anchors(html),
+            vec![
+                "structfield.size",
+                "variant.Circle",
+                "variant.Circle.field.radius"
+            ]
+        );
+    }
+
+    #[test]
+    fn item_info_of_page() {
+        assert_eq!(
+            item_info(STRUCT_PAGE).unwrap(),
+            ItemInfo {
+                signature: Some("pub struct Error { /* private fields */ }".to_string()),
+                summary: Some("The error type.".to_string()),
+                ..Default::default()
+            }
+        );
+
+        let html = r##"<main><section id="main-content" class="content">
+            <pre class="rust item-decl"><code>pub fn alloc(layout: Layout) -&gt; *mut u8</code></pre>
+            <span class="item-info"><div class="stab deprecated"><span class="emoji">👎</span><span>Deprecated since 1.2.0: use
+                <code>allocate</code> instead</span></div><div class="stab unstable"><span class="emoji">🔬</span><span>This is a nightly-only experimental API. (<code>allocator_api</code>&nbsp;<a href="https://github.com/rust-lang/rust/issues/32838">#32838</a>)</span></div></span>
+            <div class="docblock"><p>Allocates memory, e.g. for a <code>Box</code>. Returns a pointer.</p></div>
+        </section></main>"##;
+        assert_eq!(
+            item_info(html).unwrap(),
+            ItemInfo {
+                kind: None,
+                signature: Some("pub fn alloc(layout: Layout) -> *mut u8".to_string()),
+                summary: Some("Allocates memory, e.g. for a `Box`.".to_string()),
+                deprecated: Some("Deprecated since 1.2.0: use `allocate` instead".to_string()),
+                stability: Stability::Unstable,
+                feature_gates: vec!["allocator_api".to_string()],
+                required_features: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn portability_of_notices() {
+        let portability = |condition: &str| {
+            let html = format!(
+                r#"<div class="stab portability">Available on <strong>{}</strong> only.</div>"#,
+                condition
+            );
+            let fragment = Html::parse_fragment(&html);
+            let notice = fragment
+                .select(&Selector::parse("div").unwrap())
+                .next()
+                .unwrap();
+            let portability = Portability::of(notice);
+            (portability.features, portability.features_only)
+        };
+
+        assert_eq!(
+            portability("crate feature <code>std</code>"),
+            (vec!["std".to_string()], true)
+        );
+        assert_eq!(
+            portability("crate features <code>rt</code> and <code>net</code>"),
+            (vec!["rt".to_string(), "net".to_string()], true)
+        );
+        assert_eq!(
+            portability(
+                "Unix and crate feature <code>gadgets</code> and crate feature <code>extra</code>"
+            ),
+            (vec!["gadgets".to_string(), "extra".to_string()], false)
+        );
+        assert_eq!(
+            portability("crate feature <code>a</code> or crate feature <code>b</code>"),
+            (Vec::new(), false)
+        );
+        assert_eq!(
+            portability("non-crate feature <code>std</code>"),
+            (Vec::new(), false)
+        );
+        assert_eq!(portability("Linux"), (Vec::new(), false));
+    }
+
+    #[test]
+    fn convert_item_portability() {
+        let html = r##"<main><section id="main-content" class="content">
+            <pre class="rust item-decl"><code>pub struct Gadget;</code></pre>
+            <span class="item-info"><div class="stab portability">Available on <strong>crate feature <code>gadgets</code></strong> only.</div></span>
+            <details class="toggle top-doc" open><div class="docblock"><p>A gadget.</p></div></details>
+            <section id="method.run" class="method"><h4 class="code-header">pub fn run(&amp;self)</h4></section>
+            <span class="item-info"><div class="stab portability">Available on <strong>Unix</strong> only.</div></span>
+        </section></main>"##;
+        let page = DocPage {
+            lib_name: "probe",
+            path: "struct.Gadget.html",
+            skipped_sections: &[],
+            implementors: None,
+        };
+        let (markdown, info) = convert_item_page(html, &page).unwrap();
+        assert!(markdown.contains("```\n\n**Required features**: `gadgets`\n\n## Description"));
+        assert!(markdown.contains("Available on **Unix** only."));
+        assert_eq!(info.required_features, vec!["gadgets"]);
+    }
+
+    #[test]
+    fn convert_page_field_headings() {
+        let html = r##"<main><section id="main-content" class="content">
+            <h2 id="fields" class="fields section-header">Fields<a href="#fields" class="anchor">§</a></h2>
+            <span id="structfield.size" class="structfield section-header"><a href="#structfield.size" class="anchor field">§</a><code>size: usize</code></span><div class="docblock"><p>Its size.</p></div>
+            <h2 id="variants" class="variants section-header">Variants<a href="#variants" class="anchor">§</a></h2>
+            <section id="variant.Circle" class="variant"><a href="#variant.Circle" class="anchor">§</a><h3 class="code-header">Circle</h3></section>
+            <div class="sub-variant" id="variant.Circle.fields"><h4>Fields</h4><div class="sub-variant-field"><span id="variant.Circle.field.radius" class="section-header"><a href="#variant.Circle.field.radius" class="anchor field">§</a><code>radius: f64</code></span><div class="docblock"><p>The radius.</p></div></div></div>
+        </section></main>"##;
+        let page = DocPage {
+            lib_name: "probe",
+            path: "struct.Widget.html",
+            skipped_sections: &[],
+            implementors: None,
+        };
+        let result = convert_page(html, &page).unwrap();
+        assert!(result.contains("## Fields\n\n### `size: usize`\n\nIts size.\n\n"));
+        assert!(result.contains("#### Fields\n\n##### `radius: f64`\n\nThe radius.\n\n"));
+    }
+
+    #[test]
+    fn convert_page_skips_sections() {
+        let page = DocPage {
+            lib_name: "anyhow",
+            path: "struct.Error.html",
+            skipped_sections: &[
+                Section::Declaration,
+                Section::TraitImplementations,
+                Section::AutoTraitImplementations,
+                Section::BlanketImplementations,
+            ],
+            implementors: None,
+        };
+        let result = convert_page(STRUCT_PAGE, &page).unwrap();
+        assert_eq!(
+            result,
+            concat!(
+                "# Struct Error\n\n",
+                "## Description\n\nThe error type.\n\n### Display\n\nShows the message.\n\n",
+                "## Inherent Methods\n\n### impl Error\n\n",
+                "#### pub fn new<E>(error: E) -> Self where E: Send,\n\nCreate a new error.\n\n",
+            )
         );
     }
+
+    #[test]
+    fn convert_page_skips_associated_item_sections() {
+        let html = r##"<main><section id="main-content">
+            <h1>Trait <span class="trait">Iterator</span></h1>
+            <h2 id="required-associated-types" class="section-header">Required Associated Types<a href="#required-associated-types" class="anchor">§</a></h2>
+            <div class="methods"><section id="associatedtype.Item" class="method"><h4 class="code-header">type Item</h4></section></div>
+            <h2 id="provided-associated-consts" class="section-header">Provided Associated Constants<a href="#provided-associated-consts" class="anchor">§</a></h2>
+            <div class="methods"><section id="associatedconstant.MAX" class="method"><h4 class="code-header">const MAX: usize = 8</h4></section></div>
+            <h2 id="required-methods" class="section-header">Required Methods<a href="#required-methods" class="anchor">§</a></h2>
+            <div class="methods"><section id="tymethod.next" class="method"><h4 class="code-header">fn next(&amp;mut self)</h4></section></div>
+        </section></main>"##;
+        let page = DocPage {
+            lib_name: "probe",
+            path: "trait.Iterator.html",
+            skipped_sections: &[Section::RequiredAssociatedTypes],
+            implementors: None,
+        };
+        let result = convert_page(html, &page).unwrap();
+        assert!(!result.contains("Required Associated Types"));
+        assert!(!result.contains("type Item"));
+        assert!(result.contains("## Provided Associated Constants\n\n"));
+        assert!(result.contains("## Required Methods\n\n"));
+    }
+
+    #[test]
+    fn convert_page_external_implementors() {
+        let html = r#"<main><section id="main-content">
+            <h1>Trait <span class="trait">Serialize</span></h1>
+            <h2 id="implementors" class="section-header">Implementors</h2>
+            <div id="implementors-list"><section id="impl-Serialize-for-Value" class="impl"><h3 class="code-header">impl Serialize for Value</h3></section></div>
+            <script src="../trait.impl/serde_core/trait.Serialize.js" data-ignore-extern-crates="std" async></script>
+        </section></main>"#;
+        let script = r#"(function() {
+    const implementors = Object.fromEntries([["app",[["impl <a class=\"trait\" href=\"serde_core/trait.Serialize.html\">Serialize</a> for <a class=\"struct\" href=\"app/struct.Config.html\">Config</a>",0]]],["serde_core",[["impl Serialize for Value",0]]],["std",[["impl Serialize for String",0]]]]);
+    if (window.register_implementors) {
+        window.register_implementors(implementors);
+    } else {
+        window.pending_implementors = implementors;
+    }
+})()"#;
+        let page = DocPage {
+            lib_name: "serde_core",
+            path: "trait.Serialize.html",
+            skipped_sections: &[],
+            implementors: Some(script),
+        };
+        let result = convert_page(html, &page).unwrap();
+        assert_eq!(
+            result,
+            "# Trait Serialize\n\n## Implementors\n\n### impl Serialize for Value\n\n### impl Serialize for Config\n\n"
+        );
+    }
+
+    #[test]
+    fn parse_implementors_object_form() {
+        let script = r#"(function() {var implementors = {
+"app":[["impl Serialize for Config",1,["app::Config"]]],
+"serde":[]};if (window.register_implementors) {window.register_implementors(implementors);} else {window.pending_implementors = implementors;}})()"#;
+        assert_eq!(
+            parse_implementors(script),
+            vec![
+                (
+                    "app".to_string(),
+                    vec!["impl Serialize for Config".to_string()]
+                ),
+                ("serde".to_string(), vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_implementors_invalid_script() {
+        assert!(parse_implementors("window.x = 1;").is_empty());
+        assert!(parse_implementors("const implementors = Object.fromEntries([[").is_empty());
+    }
 }
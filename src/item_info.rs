@@ -0,0 +1,118 @@
+//! Per-item metadata.
+//!
+//! Besides the path and file of every item, `metadata.json` records its kind,
+//! declaration, summary, deprecation and stability, so that `list` and other
+//! tools can describe items without reading their markdown pages. Both
+//! backends produce an `ItemInfo` for every item page they render.
+
+use serde::{Deserialize, Serialize};
+
+use crate::commands::list::ItemKind;
+
+/// Metadata of a documented item.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ItemInfo {
+    /// Kind of the item, `None` when unknown
+    #[serde(default)]
+    pub kind: Option<ItemKind>,
+    /// Declaration of the item
+    /// (e.g., "pub struct Error { /* private fields */ }")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// First sentence of the item documentation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// Deprecation notice of a deprecated item
+    /// (e.g., "Deprecated since 1.39.0: use `mem::MaybeUninit` instead")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<String>,
+    /// Whether the item is stable or a nightly-only experimental API
+    #[serde(default)]
+    pub stability: Stability,
+    /// Unstable features that gate the item (e.g., "allocator_api")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub feature_gates: Vec<String>,
+    /// Crate features that must all be enabled for the item to exist, from
+    /// the portability notes of docs.rs builds (e.g., "derive")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required_features: Vec<String>,
+}
+
+/// Stability of an item.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Stability {
+    /// Available on the stable toolchain
+    #[default]
+    Stable,
+    /// Nightly-only experimental API
+    Unstable,
+}
+
+/// Return the first sentence of a paragraph on a single line.
+///
+/// A sentence ends with a period followed by whitespace, outside of inline
+/// code. Periods of the abbreviations "e.g." and "i.e." do not end a
+/// sentence. Without a sentence end, the whole paragraph is returned.
+pub fn first_sentence(paragraph: &str) -> String {
+    let text = paragraph
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ");
+
+    let mut in_code = false;
+    for (index, c) in text.char_indices() {
+        match c {
+            '`' => in_code = !in_code,
+            '.' if !in_code && text[index + 1..].starts_with(' ') => {
+                let sentence = &text[..=index];
+                if !sentence.ends_with("e.g.") && !sentence.ends_with("i.e.") {
+                    return sentence.to_string();
+                }
+            }
+            _ => {}
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_sentence_of_paragraphs() {
+        assert_eq!(
+            first_sentence("A generic error.\nIt wraps any error type."),
+            "A generic error."
+        );
+        assert_eq!(
+            first_sentence("Calls `a.b()`. Then returns."),
+            "Calls `a.b()`."
+        );
+        assert_eq!(
+            first_sentence("Reads bytes, e.g. from a file. Returns the count."),
+            "Reads bytes, e.g. from a file."
+        );
+        assert_eq!(
+            first_sentence("No period at the end"),
+            "No period at the end"
+        );
+    }
+
+    #[test]
+    fn item_info_defaults_when_fields_are_missing() {
+        let info: ItemInfo = serde_json::from_str(r#"{"kind": "struct"}"#).unwrap();
+        assert_eq!(
+            info,
+            ItemInfo {
+                kind: Some(ItemKind::Struct),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            serde_json::to_string(&info).unwrap(),
+            r#"{"kind":"struct","stability":"stable"}"#
+        );
+    }
+}
@@ -0,0 +1,2339 @@
+//! Rustdoc JSON to Markdown conversion.
+//!
+//! This module renders a `rustdoc_types::Crate` into the same set of markdown
+//! documents produced from the HTML output: a crate overview, a raw list of
+//! all items, and one page per item. Signatures are rendered directly from the
+//! typed JSON instead of being scraped from HTML.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use anyhow::{Result, bail, ensure};
+use rustdoc_types::{
+    Abi, AssocItemConstraint, AssocItemConstraintKind, Crate, FunctionHeader, FunctionSignature,
+    GenericArg, GenericArgs, GenericBound, GenericParamDef, GenericParamDefKind, Generics, Id,
+    Impl, Item, ItemEnum, ItemKind, MacroKind, Path, StructKind, Term, TraitBoundModifier, Type,
+    VariantKind, Visibility, WherePredicate,
+};
+
+use crate::item_info::{self, ItemInfo};
+use crate::reexports::Reexport;
+use crate::sections::Section;
+
+/// Markdown documents rendered from rustdoc JSON.
+#[derive(Debug)]
+pub struct JsonDoc {
+    /// The root namespace name of the crate (e.g., "rustdoc_types")
+    pub lib_name: String,
+    /// The crate overview, equivalent to index.md
+    pub index: String,
+    /// The raw list of all items, equivalent to the converted all.html
+    pub all: String,
+    /// One rendered page per documented item
+    pub items: Vec<JsonItemPage>,
+    /// The public re-exports of the crate's modules
+    pub reexports: Vec<Reexport>,
+}
+
+/// A single rendered item page.
+#[derive(Debug)]
+pub struct JsonItemPage {
+    /// The item path relative to the crate root (e.g., "de::IgnoredAny")
+    pub path: String,
+    /// The markdown file path relative to the output directory
+    pub file: String,
+    /// The rendered markdown content
+    pub markdown: String,
+    /// Rustdoc anchors of the members documented on the page
+    pub members: Vec<String>,
+    /// Signature, summary and deprecation of the item, without its kind
+    pub info: ItemInfo,
+}
+
+/// Fully qualified paths that intra-doc links resolve to.
+///
+/// Items are looked up in `Crate::paths`, which already starts with the name
+/// of the crate they belong to. Fields, variants and associated items have no
+/// entry there and are recorded as the path of their parent followed by their
+/// name, like the anchors of HTML links (`serde::de::Visitor::visit_str`).
+struct LinkTargets<'a> {
+    krate: &'a Crate,
+    members: HashMap<Id, String>,
+}
+
+impl<'a> LinkTargets<'a> {
+    /// Record the paths of the members of the local crate's items.
+    fn new(krate: &'a Crate) -> Self {
+        let get = |id: &Id| krate.index.get(id);
+        let mut members = HashMap::new();
+
+        for (id, summary) in &krate.paths {
+            if summary.crate_id != 0 {
+                continue;
+            }
+            let Some(item) = get(id) else {
+                continue;
+            };
+            let (mut children, impls) = match &item.inner {
+                ItemEnum::Struct(s) => (struct_fields(&s.kind), s.impls.as_slice()),
+                ItemEnum::Union(u) => (u.fields.clone(), u.impls.as_slice()),
+                ItemEnum::Enum(e) => (e.variants.clone(), e.impls.as_slice()),
+                ItemEnum::Trait(t) => (t.items.clone(), [].as_slice()),
+                _ => continue,
+            };
+            for impl_item in impls.iter().filter_map(get) {
+                if let ItemEnum::Impl(i) = &impl_item.inner {
+                    children.extend(&i.items);
+                }
+            }
+
+            let parent = summary.path.join("::");
+            for child in &children {
+                let Some(member) = get(child) else {
+                    continue;
+                };
+                let Some(name) = member.name.as_deref() else {
+                    continue;
+                };
+                let path = format!("{}::{}", parent, name);
+                if let ItemEnum::Variant(variant) = &member.inner {
+                    for field in variant_fields(&variant.kind) {
+                        if let Some(field_name) = get(&field).and_then(|f| f.name.as_deref()) {
+                            members
+                                .entry(field)
+                                .or_insert_with(|| format!("{}::{}", path, field_name));
+                        }
+                    }
+                }
+                members.entry(*child).or_insert(path);
+            }
+        }
+
+        LinkTargets { krate, members }
+    }
+
+    /// Return the fully qualified path of a linked item.
+    fn path(&self, id: &Id) -> Option<String> {
+        match self.krate.paths.get(id) {
+            Some(summary) => Some(summary.path.join("::")),
+            None => self.members.get(id).cloned(),
+        }
+    }
+}
+
+/// Return the fields of a struct, skipping stripped tuple fields.
+fn struct_fields(kind: &StructKind) -> Vec<Id> {
+    match kind {
+        StructKind::Plain { fields, .. } => fields.clone(),
+        StructKind::Tuple(fields) => fields.iter().flatten().cloned().collect(),
+        StructKind::Unit => Vec::new(),
+    }
+}
+
+/// Return the fields of an enum variant, skipping stripped tuple fields.
+fn variant_fields(kind: &VariantKind) -> Vec<Id> {
+    match kind {
+        VariantKind::Plain => Vec::new(),
+        VariantKind::Tuple(fields) => fields.iter().flatten().cloned().collect(),
+        VariantKind::Struct { fields, .. } => fields.clone(),
+    }
+}
+
+/// Item kinds listed in all.md, in the order rustdoc renders them.
+const LISTED_KINDS: [(ItemKind, &str); 13] = [
+    (ItemKind::Primitive, "Primitives"),
+    (ItemKind::Macro, "Macros"),
+    (ItemKind::Struct, "Structs"),
+    (ItemKind::Enum, "Enums"),
+    (ItemKind::Union, "Unions"),
+    (ItemKind::Trait, "Traits"),
+    (ItemKind::TraitAlias, "Trait Aliases"),
+    (ItemKind::ProcAttribute, "Attribute Macros"),
+    (ItemKind::ProcDerive, "Derive Macros"),
+    (ItemKind::Function, "Functions"),
+    (ItemKind::TypeAlias, "Type Aliases"),
+    (ItemKind::Static, "Statics"),
+    (ItemKind::Constant, "Constants"),
+];
+
+/// Convert a rustdoc JSON crate into markdown documents.
+///
+/// Only items that belong to the local crate and are present in the index
+/// are rendered. Items are grouped and sorted the same way as all.html.
+pub fn convert(krate: &Crate, skipped_sections: &[Section]) -> Result<JsonDoc> {
+    let Some(root) = krate.index.get(&krate.root) else {
+        bail!("rustdoc JSON does not contain the crate root item");
+    };
+    let Some(lib_name) = root.name.clone() else {
+        bail!("rustdoc JSON crate root item has no name");
+    };
+
+    let reachable = reachable_items(krate);
+    let targets = LinkTargets::new(krate);
+
+    let mut grouped: BTreeMap<usize, Vec<(String, &Item, ItemKind)>> = BTreeMap::new();
+    for (id, summary) in &krate.paths {
+        if summary.crate_id != 0 || !reachable.contains(id) {
+            continue;
+        }
+        let Some(position) = LISTED_KINDS
+            .iter()
+            .position(|(kind, _)| *kind == summary.kind)
+        else {
+            continue;
+        };
+        let Some(item) = krate.index.get(id) else {
+            continue;
+        };
+        if summary.path.len() < 2 {
+            continue;
+        }
+        let path = summary.path[1..].join("::");
+        grouped
+            .entry(position)
+            .or_default()
+            .push((path, item, summary.kind));
+    }
+
+    ensure!(
+        !grouped.is_empty(),
+        concat!(
+            "failed to find items in rustdoc JSON - no items found.\n",
+            "\n",
+            "Crates that only re-export items from other crates are not inlined in ",
+            "rustdoc JSON. Use the HTML backend for these crates."
+        )
+    );
+
+    let mut all = String::from("# List of all items\n\n");
+    let mut items = Vec::new();
+
+    for (position, entries) in &mut grouped {
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+        all.push_str(&format!("### {}\n\n", LISTED_KINDS[*position].1));
+        for (path, item, kind) in entries.iter() {
+            all.push_str(&format!("- {}\n", path));
+            items.push(JsonItemPage {
+                path: path.clone(),
+                file: item_file(path, *kind),
+                markdown: render_item(krate, &targets, item, *kind, skipped_sections),
+                members: member_anchors(krate, item),
+                info: item_info(krate, item),
+            });
+        }
+        all.push('\n');
+    }
+
+    let index = render_index(krate, &targets, root, &lib_name);
+    let reexports = module_reexports(krate, &lib_name);
+
+    Ok(JsonDoc {
+        lib_name,
+        index,
+        all,
+        items,
+        reexports,
+    })
+}
+
+/// Collect the items reachable from the crate root through modules and re-exports.
+///
+/// Items that only appear in `paths` because they are used by a hidden module
+/// are not reachable and are therefore not documented, matching all.html.
+fn reachable_items(krate: &Crate) -> HashSet<Id> {
+    let mut reachable = HashSet::new();
+    let mut pending = vec![krate.root];
+
+    while let Some(id) = pending.pop() {
+        if !reachable.insert(id) {
+            continue;
+        }
+        let Some(item) = krate.index.get(&id) else {
+            continue;
+        };
+        match &item.inner {
+            ItemEnum::Module(module) => pending.extend(module.items.iter().copied()),
+            ItemEnum::Use(u) => pending.extend(u.id),
+            _ => {}
+        }
+    }
+
+    reachable
+}
+
+/// Collect the public `use` declarations of the modules reachable from the
+/// crate root.
+///
+/// Re-exports of items from other crates are left out, since their pages are
+/// not part of this crate.
+fn module_reexports(krate: &Crate, lib_name: &str) -> Vec<Reexport> {
+    let mut reexports = Vec::new();
+    let mut visited = HashSet::new();
+    let mut pending = vec![(krate.root, lib_name.to_string())];
+
+    while let Some((id, module_path)) = pending.pop() {
+        if !visited.insert(id) {
+            continue;
+        }
+        let Some(ItemEnum::Module(module)) = krate.index.get(&id).map(|item| &item.inner) else {
+            continue;
+        };
+        for child_id in &module.items {
+            let Some(child) = krate.index.get(child_id) else {
+                continue;
+            };
+            match &child.inner {
+                ItemEnum::Module(_) => {
+                    if let Some(name) = &child.name {
+                        pending.push((*child_id, format!("{}::{}", module_path, name)));
+                    }
+                }
+                ItemEnum::Use(u) if child.visibility == Visibility::Public => {
+                    let Some(target) =
+                        u.id.and_then(|id| krate.paths.get(&id))
+                            .filter(|summary| summary.crate_id == 0)
+                    else {
+                        continue;
+                    };
+                    reexports.push(Reexport {
+                        module: module_path.clone(),
+                        name: (!u.is_glob).then(|| u.name.clone()),
+                        target: target.path.join("::"),
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+
+    reexports
+}
+
+/// Build the markdown file path for an item, mirroring rustdoc HTML file names.
+///
+/// For example, `de::IgnoredAny` with kind `Struct` becomes `de/struct.IgnoredAny.md`.
+fn item_file(path: &str, kind: ItemKind) -> String {
+    let segments: Vec<&str> = path.split("::").collect();
+    let (name, modules) = match segments.split_last() {
+        Some((name, modules)) => (*name, modules),
+        None => (path, &[][..]),
+    };
+
+    let mut file = String::new();
+    for module in modules {
+        file.push_str(module);
+        file.push('/');
+    }
+    file.push_str(&format!("{}.{}.md", kind_file_prefix(kind), name));
+    file
+}
+
+/// Return the rustdoc HTML file prefix for an item kind.
+fn kind_file_prefix(kind: ItemKind) -> &'static str {
+    match kind {
+        ItemKind::Module => "mod",
+        ItemKind::Struct => "struct",
+        ItemKind::Enum => "enum",
+        ItemKind::Union => "union",
+        ItemKind::Trait => "trait",
+        ItemKind::TraitAlias => "traitalias",
+        ItemKind::Function => "fn",
+        ItemKind::TypeAlias => "type",
+        ItemKind::Constant => "constant",
+        ItemKind::Static => "static",
+        ItemKind::Macro => "macro",
+        ItemKind::ProcAttribute => "attr",
+        ItemKind::ProcDerive => "derive",
+        ItemKind::Primitive => "primitive",
+        _ => "item",
+    }
+}
+
+/// Return the human readable title for an item kind, as used in page headings.
+fn kind_title(kind: ItemKind) -> &'static str {
+    match kind {
+        ItemKind::Module => "Module",
+        ItemKind::Struct => "Struct",
+        ItemKind::Enum => "Enum",
+        ItemKind::Union => "Union",
+        ItemKind::Trait => "Trait",
+        ItemKind::TraitAlias => "Trait Alias",
+        ItemKind::Function => "Function",
+        ItemKind::TypeAlias => "Type Alias",
+        ItemKind::Constant => "Constant",
+        ItemKind::Static => "Static",
+        ItemKind::Macro => "Macro",
+        ItemKind::ProcAttribute => "Attribute Macro",
+        ItemKind::ProcDerive => "Derive Macro",
+        ItemKind::Primitive => "Primitive Type",
+        _ => "Item",
+    }
+}
+
+/// Render the crate overview page.
+///
+/// Contains the crate documentation followed by the root module's public
+/// items grouped by kind, each with its one-line summary.
+fn render_index(krate: &Crate, targets: &LinkTargets, root: &Item, lib_name: &str) -> String {
+    let mut output = format!("# Crate {}\n\n", lib_name);
+    push_docs(&mut output, targets, root, 1);
+
+    let ItemEnum::Module(module) = &root.inner else {
+        return output;
+    };
+
+    let mut grouped: BTreeMap<&str, Vec<(&str, String)>> = BTreeMap::new();
+    for id in &module.items {
+        let Some(item) = krate.index.get(id) else {
+            continue;
+        };
+        let Some(name) = item.name.as_deref() else {
+            continue;
+        };
+        let section = match &item.inner {
+            ItemEnum::Module(_) => "Modules",
+            ItemEnum::Struct(_) => "Structs",
+            ItemEnum::Enum(_) => "Enums",
+            ItemEnum::Union(_) => "Unions",
+            ItemEnum::Trait(_) => "Traits",
+            ItemEnum::TraitAlias(_) => "Trait Aliases",
+            ItemEnum::Function(_) => "Functions",
+            ItemEnum::TypeAlias(_) => "Type Aliases",
+            ItemEnum::Constant { .. } => "Constants",
+            ItemEnum::Static(_) => "Statics",
+            ItemEnum::Macro(_) => "Macros",
+            ItemEnum::ProcMacro(_) => "Macros",
+            _ => continue,
+        };
+        grouped
+            .entry(section)
+            .or_default()
+            .push((name, summary(item.docs.as_deref())));
+    }
+
+    for (section, mut entries) in grouped {
+        entries.sort();
+        output.push_str(&format!("## {}\n\n", section));
+        for (name, summary) in entries {
+            if summary.is_empty() {
+                output.push_str(&format!("- **{}**\n", name));
+            } else {
+                output.push_str(&format!("- **{}**: {}\n", name, summary));
+            }
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+/// Render a single item page.
+///
+/// The page has the same sections as item pages converted from HTML, except
+/// for the ones in `skipped_sections`.
+fn render_item(
+    krate: &Crate,
+    targets: &LinkTargets,
+    item: &Item,
+    kind: ItemKind,
+    skipped_sections: &[Section],
+) -> String {
+    let name = item.name.as_deref().unwrap_or("");
+    let mut output = format!("# {} {}\n\n", kind_title(kind), name);
+    let enabled = |section: Section| !skipped_sections.contains(&section);
+
+    if enabled(Section::Declaration) {
+        output.push_str(&format!("## {}\n\n", Section::Declaration.title()));
+        output.push_str("```rust,item-decl\n");
+        output.push_str(&render_declaration(krate, item));
+        output.push_str("\n```\n\n");
+    }
+
+    if let Some(deprecation) = &item.deprecation {
+        output.push_str("**Deprecated**");
+        if let Some(since) = &deprecation.since {
+            output.push_str(&format!(" since {}", since));
+        }
+        if let Some(note) = &deprecation.note {
+            output.push_str(&format!(": {}", note));
+        }
+        output.push_str("\n\n");
+    }
+
+    if enabled(Section::Description) && item.docs.as_deref().is_some_and(|d| !d.trim().is_empty()) {
+        output.push_str(&format!("## {}\n\n", Section::Description.title()));
+        push_docs(&mut output, targets, item, 2);
+    }
+
+    match &item.inner {
+        ItemEnum::Struct(s) => {
+            push_fields(&mut output, krate, targets, &struct_fields(&s.kind));
+            push_impls(&mut output, krate, targets, &s.impls, skipped_sections);
+        }
+        ItemEnum::Union(u) => {
+            push_fields(&mut output, krate, targets, &u.fields);
+            push_impls(&mut output, krate, targets, &u.impls, skipped_sections);
+        }
+        ItemEnum::Enum(e) => {
+            push_variants(&mut output, krate, targets, &e.variants);
+            push_impls(&mut output, krate, targets, &e.impls, skipped_sections);
+        }
+        ItemEnum::Primitive(p) => {
+            push_impls(&mut output, krate, targets, &p.impls, skipped_sections);
+        }
+        ItemEnum::Trait(t) => {
+            let assoc_items: Vec<&Item> = t
+                .items
+                .iter()
+                .filter_map(|id| krate.index.get(id))
+                .collect();
+            for section in [
+                Section::RequiredAssociatedTypes,
+                Section::ProvidedAssociatedTypes,
+                Section::RequiredAssociatedConstants,
+                Section::ProvidedAssociatedConstants,
+                Section::RequiredMethods,
+                Section::ProvidedMethods,
+            ] {
+                if !enabled(section) {
+                    continue;
+                }
+                let members: Vec<&Item> = assoc_items
+                    .iter()
+                    .copied()
+                    .filter(|assoc| trait_item_section(assoc) == Some(section))
+                    .collect();
+                push_members(&mut output, krate, targets, section.title(), &members);
+            }
+
+            let implementors: Vec<String> = t
+                .implementations
+                .iter()
+                .filter_map(|id| krate.index.get(id))
+                .filter_map(|item| match &item.inner {
+                    ItemEnum::Impl(i) => Some(render_impl_header(i)),
+                    _ => None,
+                })
+                .collect();
+            if enabled(Section::Implementors) {
+                push_list(&mut output, Section::Implementors.title(), &implementors);
+            }
+        }
+        _ => {}
+    }
+
+    output
+}
+
+/// Return the section of a trait page that lists a trait item.
+///
+/// Associated types, constants and methods are required unless the trait
+/// provides a default.
+fn trait_item_section(item: &Item) -> Option<Section> {
+    let section = match &item.inner {
+        ItemEnum::AssocType { type_: None, .. } => Section::RequiredAssociatedTypes,
+        ItemEnum::AssocType { .. } => Section::ProvidedAssociatedTypes,
+        ItemEnum::AssocConst { value: None, .. } => Section::RequiredAssociatedConstants,
+        ItemEnum::AssocConst { .. } => Section::ProvidedAssociatedConstants,
+        ItemEnum::Function(f) if f.has_body => Section::ProvidedMethods,
+        ItemEnum::Function(_) => Section::RequiredMethods,
+        _ => return None,
+    };
+    Some(section)
+}
+
+/// Append item documentation with headings shifted by the given level.
+fn push_docs(output: &mut String, targets: &LinkTargets, item: &Item, shift: usize) {
+    let Some(docs) = item.docs.as_deref().filter(|d| !d.trim().is_empty()) else {
+        return;
+    };
+    let docs = resolve_links(docs, &item.links, targets);
+    output.push_str(&shift_headings(docs.trim(), shift));
+    output.push_str("\n\n");
+}
+
+/// Return the rustdoc anchors of the members documented on an item page.
+///
+/// The anchors are the ones rustdoc gives to fields, variants, inherent
+/// methods and trait items, e.g. `structfield.size` or `tymethod.custom`.
+/// Fields of enum variants are rendered inline with their variant and have
+/// no anchor.
+fn member_anchors(krate: &Crate, item: &Item) -> Vec<String> {
+    let get =
+        |ids: &[Id]| -> Vec<&Item> { ids.iter().filter_map(|id| krate.index.get(id)).collect() };
+    let inherent = |impls: &[Id]| -> Vec<&Item> {
+        get(impls)
+            .into_iter()
+            .filter_map(|item| match &item.inner {
+                ItemEnum::Impl(i) if i.trait_.is_none() => Some(get(&i.items)),
+                _ => None,
+            })
+            .flatten()
+            .collect()
+    };
+
+    let (fields, associated) = match &item.inner {
+        ItemEnum::Struct(s) => match &s.kind {
+            StructKind::Plain { fields, .. } => (get(fields), inherent(&s.impls)),
+            StructKind::Tuple(fields) => {
+                let fields: Vec<Id> = fields.iter().flatten().cloned().collect();
+                (get(&fields), inherent(&s.impls))
+            }
+            StructKind::Unit => (Vec::new(), inherent(&s.impls)),
+        },
+        ItemEnum::Union(u) => (get(&u.fields), inherent(&u.impls)),
+        ItemEnum::Enum(e) => (get(&e.variants), inherent(&e.impls)),
+        ItemEnum::Primitive(p) => (Vec::new(), inherent(&p.impls)),
+        ItemEnum::Trait(t) => (Vec::new(), get(&t.items)),
+        _ => return Vec::new(),
+    };
+
+    fields
+        .into_iter()
+        .chain(associated)
+        .filter_map(|member| {
+            let prefix = match &member.inner {
+                ItemEnum::StructField(_) => "structfield",
+                ItemEnum::Variant(_) => "variant",
+                ItemEnum::Function(f) if f.has_body => "method",
+                ItemEnum::Function(_) => "tymethod",
+                ItemEnum::AssocType { .. } => "associatedtype",
+                ItemEnum::AssocConst { .. } => "associatedconstant",
+                _ => return None,
+            };
+            Some(format!("{}.{}", prefix, member.name.as_deref()?))
+        })
+        .collect()
+}
+
+/// Append a "Fields" section listing struct or union fields.
+fn push_fields(output: &mut String, krate: &Crate, targets: &LinkTargets, fields: &[Id]) {
+    let fields: Vec<&Item> = fields.iter().filter_map(|id| krate.index.get(id)).collect();
+    if fields.is_empty() {
+        return;
+    }
+
+    output.push_str("## Fields\n\n");
+    for field in fields {
+        let ItemEnum::StructField(ty) = &field.inner else {
+            continue;
+        };
+        let name = field.name.as_deref().unwrap_or("_");
+        output.push_str(&format!("### `{}: {}`\n\n", name, format_type(ty)));
+        push_docs(output, targets, field, 3);
+    }
+}
+
+/// Append a "Variants" section listing enum variants.
+fn push_variants(output: &mut String, krate: &Crate, targets: &LinkTargets, variants: &[Id]) {
+    let variants: Vec<&Item> = variants
+        .iter()
+        .filter_map(|id| krate.index.get(id))
+        .collect();
+    if variants.is_empty() {
+        return;
+    }
+
+    output.push_str("## Variants\n\n");
+    for variant in variants {
+        output.push_str(&format!("### `{}`\n\n", render_variant(krate, variant)));
+        push_docs(output, targets, variant, 3);
+    }
+}
+
+/// Append inherent methods and trait implementation sections, except for
+/// the ones in `skipped_sections`.
+fn push_impls(
+    output: &mut String,
+    krate: &Crate,
+    targets: &LinkTargets,
+    impls: &[Id],
+    skipped_sections: &[Section],
+) {
+    let enabled = |section: Section| !skipped_sections.contains(&section);
+    let impls: Vec<&Impl> = impls
+        .iter()
+        .filter_map(|id| krate.index.get(id))
+        .filter_map(|item| match &item.inner {
+            ItemEnum::Impl(i) => Some(i),
+            _ => None,
+        })
+        .collect();
+
+    let methods: Vec<&Item> = impls
+        .iter()
+        .filter(|i| i.trait_.is_none())
+        .flat_map(|i| i.items.iter())
+        .filter_map(|id| krate.index.get(id))
+        .collect();
+    if enabled(Section::InherentMethods) {
+        push_members(
+            output,
+            krate,
+            targets,
+            Section::InherentMethods.title(),
+            &methods,
+        );
+    }
+
+    let trait_impls: Vec<String> = impls
+        .iter()
+        .filter(|i| i.trait_.is_some() && !i.is_synthetic && i.blanket_impl.is_none())
+        .map(|i| render_impl_header(i))
+        .collect();
+    if enabled(Section::TraitImplementations) {
+        push_list(output, Section::TraitImplementations.title(), &trait_impls);
+    }
+
+    let auto_impls: Vec<String> = impls
+        .iter()
+        .filter(|i| i.is_synthetic)
+        .map(|i| render_impl_header(i))
+        .collect();
+    if enabled(Section::AutoTraitImplementations) {
+        push_list(
+            output,
+            Section::AutoTraitImplementations.title(),
+            &auto_impls,
+        );
+    }
+
+    let blanket_impls: Vec<String> = impls
+        .iter()
+        .filter(|i| i.blanket_impl.is_some())
+        .map(|i| render_impl_header(i))
+        .collect();
+    if enabled(Section::BlanketImplementations) {
+        push_list(
+            output,
+            Section::BlanketImplementations.title(),
+            &blanket_impls,
+        );
+    }
+}
+
+/// Append a section of associated items, each with its signature and docs.
+fn push_members(
+    output: &mut String,
+    krate: &Crate,
+    targets: &LinkTargets,
+    title: &str,
+    members: &[&Item],
+) {
+    if members.is_empty() {
+        return;
+    }
+
+    output.push_str(&format!("## {}\n\n", title));
+    for member in members {
+        output.push_str(&format!(
+            "### `{}`\n\n",
+            render_declaration(krate, member).replace('\n', " ")
+        ));
+        push_docs(output, targets, member, 3);
+    }
+}
+
+/// Append a section rendering each entry as an inline code list item.
+fn push_list(output: &mut String, title: &str, entries: &[String]) {
+    if entries.is_empty() {
+        return;
+    }
+
+    output.push_str(&format!("## {}\n\n", title));
+    for entry in entries {
+        output.push_str(&format!("- `{}`\n", entry));
+    }
+    output.push('\n');
+}
+
+/// Shift markdown ATX headings outside of code fences by the given level.
+fn shift_headings(docs: &str, shift: usize) -> String {
+    let mut in_fence = false;
+    let mut lines = Vec::new();
+
+    for line in docs.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        }
+
+        if !in_fence && line.starts_with('#') {
+            let level = line.chars().take_while(|c| *c == '#').count();
+            let rest = &line[level..];
+            if rest.is_empty() || rest.starts_with(' ') {
+                let level = (level + shift).min(6);
+                lines.push(format!("{}{}", "#".repeat(level), rest));
+                continue;
+            }
+        }
+
+        lines.push(line.to_string());
+    }
+
+    lines.join("\n")
+}
+
+/// Rewrite the intra-doc links of documentation to fully qualified item paths.
+///
+/// Rustdoc lists the links it resolved in `links`, keyed by their destination
+/// as written, so ``[`Widget`]`` is keyed by `` `Widget` ``. Reference
+/// definitions are inlined into the links that use them. Resolved links that
+/// point to items without a known path keep only their text, and brackets
+/// that are not links are left as they are.
+fn resolve_links(docs: &str, links: &HashMap<String, Id>, targets: &LinkTargets) -> String {
+    let mut definitions = HashMap::new();
+    let mut lines = Vec::new();
+    let mut in_fence = false;
+
+    for line in docs.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        }
+        if !in_fence && let Some((label, destination)) = reference_definition(line) {
+            definitions.insert(label, destination);
+            continue;
+        }
+        lines.push((line, in_fence));
+    }
+
+    lines
+        .into_iter()
+        .map(|(line, in_fence)| {
+            if in_fence {
+                line.to_string()
+            } else {
+                resolve_line_links(line, &definitions, links, targets)
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Rewrite the links of a single line outside of code spans.
+fn resolve_line_links(
+    line: &str,
+    definitions: &HashMap<String, &str>,
+    links: &HashMap<String, Id>,
+    targets: &LinkTargets,
+) -> String {
+    // A reference either uses a definition or is itself the intra-doc link.
+    let reference = |text: &str, label: &str| match definitions.get(&normalize_label(label)) {
+        Some(destination) => Some(
+            link_markdown(text, destination, links, targets)
+                .unwrap_or_else(|| format!("[{}]({})", text, destination)),
+        ),
+        None => link_markdown(text, label, links, targets),
+    };
+
+    let mut output = String::new();
+    let mut rest = line;
+    while let Some(start) = rest.find(['[', '`', '\\']) {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if rest.starts_with('\\') {
+            let length = 1 + rest[1..].chars().next().map_or(0, char::len_utf8);
+            output.push_str(&rest[..length]);
+            rest = &rest[length..];
+            continue;
+        }
+        if rest.starts_with('`') {
+            let length = code_span_len(rest);
+            output.push_str(&rest[..length]);
+            rest = &rest[length..];
+            continue;
+        }
+
+        let Some(text_end) = closing_bracket(rest) else {
+            output.push('[');
+            rest = &rest[1..];
+            continue;
+        };
+        let text = &rest[1..text_end];
+        let after = &rest[text_end + 1..];
+        let (link, length) = if let Some(inline) = after.strip_prefix('(')
+            && let Some(end) = inline.find(')')
+        {
+            let destination = inline[..end].split_whitespace().next().unwrap_or_default();
+            (
+                link_markdown(text, destination, links, targets),
+                text_end + end + 3,
+            )
+        } else if let Some(label) = after.strip_prefix('[')
+            && let Some(end) = label.find(']')
+        {
+            let label = if end == 0 { text } else { &label[..end] };
+            (reference(text, label), text_end + end + 3)
+        } else {
+            (reference(text, text), text_end + 1)
+        };
+
+        match link {
+            Some(link) => {
+                output.push_str(&link);
+                rest = &rest[length..];
+            }
+            None => {
+                output.push('[');
+                rest = &rest[1..];
+            }
+        }
+    }
+    output.push_str(rest);
+
+    output
+}
+
+/// Render a link whose destination rustdoc resolved.
+///
+/// Returns `None` when the destination is not an intra-doc link.
+fn link_markdown(
+    text: &str,
+    destination: &str,
+    links: &HashMap<String, Id>,
+    targets: &LinkTargets,
+) -> Option<String> {
+    let id = links.get(destination)?;
+    Some(match targets.path(id) {
+        Some(path) => format!("[{}]({})", text, path),
+        None => text.to_string(),
+    })
+}
+
+/// Parse a reference definition line such as ``[`Widget`]: crate::Widget``.
+fn reference_definition(line: &str) -> Option<(String, &str)> {
+    let trimmed = line.trim_start_matches(' ');
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let (label, destination) = trimmed.strip_prefix('[')?.split_once("]:")?;
+    if label.trim().is_empty() || label.contains(['[', ']']) {
+        return None;
+    }
+    let destination = destination.split_whitespace().next()?;
+    let destination = destination
+        .strip_prefix('<')
+        .and_then(|d| d.strip_suffix('>'))
+        .unwrap_or(destination);
+    Some((normalize_label(label), destination))
+}
+
+/// Normalize a link label the way markdown matches them: case-insensitive,
+/// with whitespace collapsed.
+fn normalize_label(label: &str) -> String {
+    label
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Return the byte offset of the bracket closing the one `text` starts with.
+fn closing_bracket(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut skip_until = 0;
+    for (index, c) in text.char_indices() {
+        if index < skip_until {
+            continue;
+        }
+        match c {
+            '`' => skip_until = index + code_span_len(&text[index..]),
+            '\\' => skip_until = index + 2,
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Return the length of the code span `text` starts with, or of its opening
+/// backticks when the span is not closed.
+fn code_span_len(text: &str) -> usize {
+    let ticks = text.len() - text.trim_start_matches('`').len();
+    let fence = &text[..ticks];
+    let mut offset = ticks;
+    while let Some(found) = text[offset..].find(fence) {
+        let start = offset + found;
+        let end = text.len() - text[start..].trim_start_matches('`').len();
+        if end - start == ticks {
+            return end;
+        }
+        offset = end;
+    }
+    ticks
+}
+
+/// Collect the signature, summary and deprecation notice of an item.
+///
+/// Rustdoc JSON does not record stability, so items are always stable.
+fn item_info(krate: &Crate, item: &Item) -> ItemInfo {
+    let summary = item_info::first_sentence(&summary(item.docs.as_deref()));
+    let deprecated = item.deprecation.as_ref().map(|deprecation| {
+        let mut notice = String::from("Deprecated");
+        if let Some(since) = &deprecation.since {
+            notice.push_str(&format!(" since {}", since));
+        }
+        if let Some(note) = &deprecation.note {
+            notice.push_str(&format!(": {}", note));
+        }
+        notice
+    });
+    ItemInfo {
+        signature: Some(render_declaration(krate, item)),
+        summary: (!summary.is_empty()).then_some(summary),
+        deprecated,
+        ..Default::default()
+    }
+}
+
+/// Extract the first paragraph of documentation as a single line.
+fn summary(docs: Option<&str>) -> String {
+    let Some(docs) = docs else {
+        return String::new();
+    };
+    docs.trim()
+        .split("\n\n")
+        .next()
+        .unwrap_or("")
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+/// Render the Rust declaration of an item.
+fn render_declaration(krate: &Crate, item: &Item) -> String {
+    let name = item.name.as_deref().unwrap_or("_");
+    let vis = format_visibility(&item.visibility);
+
+    match &item.inner {
+        ItemEnum::Struct(s) => {
+            let mut decl = format!(
+                "{}struct {}{}",
+                vis,
+                name,
+                format_generic_params(&s.generics.params)
+            );
+            match &s.kind {
+                StructKind::Unit => {
+                    decl.push_str(&format_where_clause(&s.generics));
+                    decl.push(';');
+                }
+                StructKind::Tuple(fields) => {
+                    let fields: Vec<String> = fields
+                        .iter()
+                        .map(|field| match field.and_then(|id| krate.index.get(&id)) {
+                            Some(field) => render_field(field, false),
+                            None => "/* private field */".to_string(),
+                        })
+                        .collect();
+                    decl.push_str(&format!("({})", fields.join(", ")));
+                    decl.push_str(&format_where_clause(&s.generics));
+                    decl.push(';');
+                }
+                StructKind::Plain {
+                    fields,
+                    has_stripped_fields,
+                } => {
+                    decl.push_str(&format_where_clause(&s.generics));
+                    decl.push_str(&render_field_block(krate, fields, *has_stripped_fields));
+                }
+            }
+            decl
+        }
+        ItemEnum::Union(u) => format!(
+            "{}union {}{}{}{}",
+            vis,
+            name,
+            format_generic_params(&u.generics.params),
+            format_where_clause(&u.generics),
+            render_field_block(krate, &u.fields, u.has_stripped_fields)
+        ),
+        ItemEnum::Enum(e) => {
+            let mut decl = format!(
+                "{}enum {}{}{}",
+                vis,
+                name,
+                format_generic_params(&e.generics.params),
+                format_where_clause(&e.generics)
+            );
+            let mut variants: Vec<String> = e
+                .variants
+                .iter()
+                .filter_map(|id| krate.index.get(id))
+                .map(|variant| format!("    {},", render_variant(krate, variant)))
+                .collect();
+            if e.has_stripped_variants {
+                variants.push("    // some variants omitted".to_string());
+            }
+            if variants.is_empty() {
+                decl.push_str(" {}");
+            } else {
+                decl.push_str(&format!(" {{\n{}\n}}", variants.join("\n")));
+            }
+            decl
+        }
+        ItemEnum::Function(f) => format!(
+            "{}{}fn {}{}{}{}",
+            vis,
+            format_header(&f.header),
+            name,
+            format_generic_params(&f.generics.params),
+            format_signature(&f.sig),
+            format_where_clause(&f.generics)
+        ),
+        ItemEnum::Trait(t) => {
+            let mut decl = format!(
+                "{}{}{}trait {}{}",
+                vis,
+                if t.is_unsafe { "unsafe " } else { "" },
+                if t.is_auto { "auto " } else { "" },
+                name,
+                format_generic_params(&t.generics.params)
+            );
+            if !t.bounds.is_empty() {
+                decl.push_str(&format!(": {}", format_bounds(&t.bounds)));
+            }
+            decl.push_str(&format_where_clause(&t.generics));
+            let members: Vec<String> = t
+                .items
+                .iter()
+                .filter_map(|id| krate.index.get(id))
+                .map(|member| format!("    {};", render_declaration(krate, member)))
+                .collect();
+            if members.is_empty() {
+                decl.push_str(" {}");
+            } else {
+                decl.push_str(&format!(" {{\n{}\n}}", members.join("\n")));
+            }
+            decl
+        }
+        ItemEnum::TraitAlias(t) => format!(
+            "{}trait {}{} = {}{};",
+            vis,
+            name,
+            format_generic_params(&t.generics.params),
+            format_bounds(&t.params),
+            format_where_clause(&t.generics)
+        ),
+        ItemEnum::TypeAlias(t) => format!(
+            "{}type {}{}{} = {};",
+            vis,
+            name,
+            format_generic_params(&t.generics.params),
+            format_where_clause(&t.generics),
+            format_type(&t.type_)
+        ),
+        ItemEnum::Constant { type_, const_ } => format!(
+            "{}const {}: {} = {};",
+            vis,
+            name,
+            format_type(type_),
+            const_.expr
+        ),
+        ItemEnum::Static(s) => format!(
+            "{}{}static {}{}: {};",
+            vis,
+            if s.is_unsafe { "unsafe " } else { "" },
+            if s.is_mutable { "mut " } else { "" },
+            name,
+            format_type(&s.type_)
+        ),
+        ItemEnum::Macro(source) => source.clone(),
+        ItemEnum::ProcMacro(p) => match p.kind {
+            MacroKind::Bang => format!("{}!() {{ /* proc-macro */ }}", name),
+            MacroKind::Attr => format!("#[{}]", name),
+            MacroKind::Derive => {
+                if p.helpers.is_empty() {
+                    format!("#[derive({})]", name)
+                } else {
+                    format!(
+                        "#[derive({})]\n// helper attributes: {}",
+                        name,
+                        p.helpers.join(", ")
+                    )
+                }
+            }
+        },
+        ItemEnum::Primitive(p) => format!("primitive {}", p.name),
+        ItemEnum::StructField(_) => render_field(item, true),
+        ItemEnum::Variant(_) => render_variant(krate, item),
+        ItemEnum::AssocConst { type_, value } => match value {
+            Some(value) => format!("const {}: {} = {}", name, format_type(type_), value),
+            None => format!("const {}: {}", name, format_type(type_)),
+        },
+        ItemEnum::AssocType {
+            generics,
+            bounds,
+            type_,
+        } => {
+            let mut decl = format!("type {}{}", name, format_generic_params(&generics.params));
+            if !bounds.is_empty() {
+                decl.push_str(&format!(": {}", format_bounds(bounds)));
+            }
+            decl.push_str(&format_where_clause(generics));
+            if let Some(ty) = type_ {
+                decl.push_str(&format!(" = {}", format_type(ty)));
+            }
+            decl
+        }
+        ItemEnum::Impl(i) => render_impl_header(i),
+        ItemEnum::Module(_) => format!("{}mod {}", vis, name),
+        ItemEnum::ExternType => format!("{}type {};", vis, name),
+        ItemEnum::ExternCrate { name, rename } => match rename {
+            Some(rename) => format!("{}extern crate {} as {};", vis, name, rename),
+            None => format!("{}extern crate {};", vis, name),
+        },
+        ItemEnum::Use(u) => {
+            if u.is_glob {
+                format!("{}use {}::*;", vis, u.source)
+            } else {
+                format!("{}use {};", vis, u.source)
+            }
+        }
+    }
+}
+
+/// Render a braced block of named fields.
+fn render_field_block(krate: &Crate, fields: &[Id], has_stripped_fields: bool) -> String {
+    let mut lines: Vec<String> = fields
+        .iter()
+        .filter_map(|id| krate.index.get(id))
+        .map(|field| format!("    {},", render_field(field, true)))
+        .collect();
+    if has_stripped_fields {
+        lines.push("    /* private fields */".to_string());
+    }
+    if lines.is_empty() {
+        return " {}".to_string();
+    }
+    format!(" {{\n{}\n}}", lines.join("\n"))
+}
+
+/// Render a struct field, optionally including its name.
+fn render_field(field: &Item, named: bool) -> String {
+    let ItemEnum::StructField(ty) = &field.inner else {
+        return String::new();
+    };
+    let vis = format_visibility(&field.visibility);
+    match (named, field.name.as_deref()) {
+        (true, Some(name)) => format!("{}{}: {}", vis, name, format_type(ty)),
+        _ => format!("{}{}", vis, format_type(ty)),
+    }
+}
+
+/// Render an enum variant with its fields and discriminant.
+fn render_variant(krate: &Crate, variant: &Item) -> String {
+    let name = variant.name.as_deref().unwrap_or("_");
+    let ItemEnum::Variant(v) = &variant.inner else {
+        return name.to_string();
+    };
+
+    let mut output = name.to_string();
+    match &v.kind {
+        VariantKind::Plain => {}
+        VariantKind::Tuple(fields) => {
+            let fields: Vec<String> = fields
+                .iter()
+                .map(|field| match field.and_then(|id| krate.index.get(&id)) {
+                    Some(field) => render_field(field, false),
+                    None => "_".to_string(),
+                })
+                .collect();
+            output.push_str(&format!("({})", fields.join(", ")));
+        }
+        VariantKind::Struct {
+            fields,
+            has_stripped_fields,
+        } => {
+            let mut fields: Vec<String> = fields
+                .iter()
+                .filter_map(|id| krate.index.get(id))
+                .map(|field| render_field(field, true))
+                .collect();
+            if *has_stripped_fields {
+                fields.push("/* private fields */".to_string());
+            }
+            output.push_str(&format!(" {{ {} }}", fields.join(", ")));
+        }
+    }
+    if let Some(discriminant) = &v.discriminant {
+        output.push_str(&format!(" = {}", discriminant.expr));
+    }
+    output
+}
+
+/// Render the header line of an impl block (e.g., `impl<T> Trait for Type<T>`).
+fn render_impl_header(i: &Impl) -> String {
+    let mut output = String::new();
+    if i.is_unsafe {
+        output.push_str("unsafe ");
+    }
+    output.push_str("impl");
+    output.push_str(&format_generic_params(&i.generics.params));
+    output.push(' ');
+    if let Some(trait_) = &i.trait_ {
+        if i.is_negative {
+            output.push('!');
+        }
+        output.push_str(&format_path(trait_));
+        output.push_str(" for ");
+    }
+    output.push_str(&format_type(&i.for_));
+    output.push_str(&format_where_clause(&i.generics));
+    output
+}
+
+/// Format an item visibility as a declaration prefix.
+fn format_visibility(visibility: &Visibility) -> String {
+    match visibility {
+        Visibility::Public => "pub ".to_string(),
+        Visibility::Default => String::new(),
+        Visibility::Crate => "pub(crate) ".to_string(),
+        Visibility::Restricted { path, .. } => format!("pub(in {}) ", path),
+    }
+}
+
+/// Format function qualifiers such as `const`, `async`, `unsafe` and `extern`.
+fn format_header(header: &FunctionHeader) -> String {
+    let mut output = String::new();
+    if header.is_const {
+        output.push_str("const ");
+    }
+    if header.is_async {
+        output.push_str("async ");
+    }
+    if header.is_unsafe {
+        output.push_str("unsafe ");
+    }
+    let abi = match &header.abi {
+        Abi::Rust => None,
+        Abi::C { unwind } => Some(("C", *unwind)),
+        Abi::Cdecl { unwind } => Some(("cdecl", *unwind)),
+        Abi::Stdcall { unwind } => Some(("stdcall", *unwind)),
+        Abi::Fastcall { unwind } => Some(("fastcall", *unwind)),
+        Abi::Aapcs { unwind } => Some(("aapcs", *unwind)),
+        Abi::Win64 { unwind } => Some(("win64", *unwind)),
+        Abi::SysV64 { unwind } => Some(("sysv64", *unwind)),
+        Abi::System { unwind } => Some(("system", *unwind)),
+        Abi::Other(other) => {
+            output.push_str(&format!("extern \"{}\" ", other));
+            None
+        }
+    };
+    if let Some((name, unwind)) = abi {
+        if unwind {
+            output.push_str(&format!("extern \"{}-unwind\" ", name));
+        } else {
+            output.push_str(&format!("extern \"{}\" ", name));
+        }
+    }
+    output
+}
+
+/// Format a function signature's parameter list and return type.
+fn format_signature(sig: &FunctionSignature) -> String {
+    let mut inputs: Vec<String> = sig
+        .inputs
+        .iter()
+        .map(|(name, ty)| format_input(name, ty))
+        .collect();
+    if sig.is_c_variadic {
+        inputs.push("...".to_string());
+    }
+
+    let mut output = format!("({})", inputs.join(", "));
+    if let Some(ty) = &sig.output {
+        output.push_str(&format!(" -> {}", format_type(ty)));
+    }
+    output
+}
+
+/// Format a single function parameter, using shorthand for `self` receivers.
+fn format_input(name: &str, ty: &Type) -> String {
+    if name == "self" {
+        match ty {
+            Type::Generic(generic) if generic == "Self" => return "self".to_string(),
+            Type::BorrowedRef {
+                lifetime,
+                is_mutable,
+                type_,
+            } if matches!(type_.as_ref(), Type::Generic(generic) if generic == "Self") => {
+                let mut output = String::from("&");
+                if let Some(lifetime) = lifetime {
+                    output.push_str(&format!("{} ", lifetime));
+                }
+                if *is_mutable {
+                    output.push_str("mut ");
+                }
+                output.push_str("self");
+                return output;
+            }
+            _ => {}
+        }
+    }
+    format!("{}: {}", name, format_type(ty))
+}
+
+/// Format the pointee of a reference or raw pointer.
+///
+/// `dyn` and `impl` types with several bounds are parenthesized, since
+/// `&dyn Error + 'static` does not parse.
+fn format_pointee(ty: &Type) -> String {
+    let needs_parens = match ty {
+        Type::DynTrait(dyn_trait) => dyn_trait.traits.len() > 1 || dyn_trait.lifetime.is_some(),
+        Type::ImplTrait(bounds) => bounds.len() > 1,
+        _ => false,
+    };
+    if needs_parens {
+        format!("({})", format_type(ty))
+    } else {
+        format_type(ty)
+    }
+}
+
+/// Format a type as Rust source.
+fn format_type(ty: &Type) -> String {
+    match ty {
+        Type::ResolvedPath(path) => format_path(path),
+        Type::DynTrait(dyn_trait) => {
+            let mut parts: Vec<String> = dyn_trait
+                .traits
+                .iter()
+                .map(|poly| {
+                    format!(
+                        "{}{}",
+                        format_higher_ranked(&poly.generic_params),
+                        format_path(&poly.trait_)
+                    )
+                })
+                .collect();
+            if let Some(lifetime) = &dyn_trait.lifetime {
+                parts.push(lifetime.clone());
+            }
+            format!("dyn {}", parts.join(" + "))
+        }
+        Type::Generic(name) | Type::Primitive(name) => name.clone(),
+        Type::FunctionPointer(fp) => format!(
+            "{}{}fn{}",
+            format_higher_ranked(&fp.generic_params),
+            format_header(&fp.header),
+            format_signature(&fp.sig)
+        ),
+        Type::Tuple(types) => {
+            let types: Vec<String> = types.iter().map(format_type).collect();
+            if types.len() == 1 {
+                format!("({},)", types[0])
+            } else {
+                format!("({})", types.join(", "))
+            }
+        }
+        Type::Slice(ty) => format!("[{}]", format_type(ty)),
+        Type::Array { type_, len } => format!("[{}; {}]", format_type(type_), len),
+        Type::Pat { type_, .. } => format_type(type_),
+        Type::ImplTrait(bounds) => format!("impl {}", format_bounds(bounds)),
+        Type::Infer => "_".to_string(),
+        Type::RawPointer { is_mutable, type_ } => format!(
+            "*{} {}",
+            if *is_mutable { "mut" } else { "const" },
+            format_pointee(type_)
+        ),
+        Type::BorrowedRef {
+            lifetime,
+            is_mutable,
+            type_,
+        } => {
+            let mut output = String::from("&");
+            if let Some(lifetime) = lifetime {
+                output.push_str(&format!("{} ", lifetime));
+            }
+            if *is_mutable {
+                output.push_str("mut ");
+            }
+            output.push_str(&format_pointee(type_));
+            output
+        }
+        Type::QualifiedPath {
+            name,
+            args,
+            self_type,
+            trait_,
+        } => {
+            let args = args.as_deref().map(format_generic_args).unwrap_or_default();
+            match trait_ {
+                Some(trait_) => format!(
+                    "<{} as {}>::{}{}",
+                    format_type(self_type),
+                    format_path(trait_),
+                    name,
+                    args
+                ),
+                None => format!("{}::{}{}", format_type(self_type), name, args),
+            }
+        }
+    }
+}
+
+/// Format a resolved path with its generic arguments.
+fn format_path(path: &Path) -> String {
+    let args = path
+        .args
+        .as_deref()
+        .map(format_generic_args)
+        .unwrap_or_default();
+    format!("{}{}", path.path, args)
+}
+
+/// Format generic arguments such as `<T, Item = U>` or `(A) -> B`.
+fn format_generic_args(args: &GenericArgs) -> String {
+    match args {
+        GenericArgs::AngleBracketed { args, constraints } => {
+            let mut parts: Vec<String> = args
+                .iter()
+                .map(|arg| match arg {
+                    GenericArg::Lifetime(lifetime) => lifetime.clone(),
+                    GenericArg::Type(ty) => format_type(ty),
+                    GenericArg::Const(constant) => constant.expr.clone(),
+                    GenericArg::Infer => "_".to_string(),
+                })
+                .collect();
+            parts.extend(constraints.iter().map(format_constraint));
+            if parts.is_empty() {
+                String::new()
+            } else {
+                format!("<{}>", parts.join(", "))
+            }
+        }
+        GenericArgs::Parenthesized { inputs, output } => {
+            let inputs: Vec<String> = inputs.iter().map(format_type).collect();
+            match output {
+                Some(output) => format!("({}) -> {}", inputs.join(", "), format_type(output)),
+                None => format!("({})", inputs.join(", ")),
+            }
+        }
+        GenericArgs::ReturnTypeNotation => "(..)".to_string(),
+    }
+}
+
+/// Format an associated item constraint (e.g., `Item = T` or `Item: Clone`).
+fn format_constraint(constraint: &AssocItemConstraint) -> String {
+    let args = constraint
+        .args
+        .as_deref()
+        .map(format_generic_args)
+        .unwrap_or_default();
+    match &constraint.binding {
+        AssocItemConstraintKind::Equality(term) => {
+            format!("{}{} = {}", constraint.name, args, format_term(term))
+        }
+        AssocItemConstraintKind::Constraint(bounds) => {
+            format!("{}{}: {}", constraint.name, args, format_bounds(bounds))
+        }
+    }
+}
+
+/// Format a type or constant term.
+fn format_term(term: &Term) -> String {
+    match term {
+        Term::Type(ty) => format_type(ty),
+        Term::Constant(constant) => constant.expr.clone(),
+    }
+}
+
+/// Format a list of generic bounds joined with `+`.
+fn format_bounds(bounds: &[GenericBound]) -> String {
+    let bounds: Vec<String> = bounds
+        .iter()
+        .map(|bound| match bound {
+            GenericBound::TraitBound {
+                trait_,
+                generic_params,
+                modifier,
+            } => {
+                let modifier = match modifier {
+                    TraitBoundModifier::None => "",
+                    TraitBoundModifier::Maybe => "?",
+                    TraitBoundModifier::MaybeConst => "~const ",
+                };
+                format!(
+                    "{}{}{}",
+                    format_higher_ranked(generic_params),
+                    modifier,
+                    format_path(trait_)
+                )
+            }
+            GenericBound::Outlives(lifetime) => lifetime.clone(),
+            GenericBound::Use(args) => {
+                let args: Vec<&str> = args
+                    .iter()
+                    .map(|arg| match arg {
+                        rustdoc_types::PreciseCapturingArg::Lifetime(name)
+                        | rustdoc_types::PreciseCapturingArg::Param(name) => name.as_str(),
+                    })
+                    .collect();
+                format!("use<{}>", args.join(", "))
+            }
+        })
+        .collect();
+    bounds.join(" + ")
+}
+
+/// Format a higher-ranked binder such as `for<'a> `.
+fn format_higher_ranked(params: &[GenericParamDef]) -> String {
+    if params.is_empty() {
+        return String::new();
+    }
+    let params: Vec<String> = params.iter().map(format_generic_param).collect();
+    format!("for<{}> ", params.join(", "))
+}
+
+/// Format generic parameter declarations, skipping synthetic `impl Trait` params.
+fn format_generic_params(params: &[GenericParamDef]) -> String {
+    let params: Vec<String> = params
+        .iter()
+        .filter(|param| {
+            !matches!(
+                param.kind,
+                GenericParamDefKind::Type {
+                    is_synthetic: true,
+                    ..
+                }
+            )
+        })
+        .map(format_generic_param)
+        .collect();
+    if params.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", params.join(", "))
+    }
+}
+
+/// Format a single generic parameter declaration.
+fn format_generic_param(param: &GenericParamDef) -> String {
+    match &param.kind {
+        GenericParamDefKind::Lifetime { outlives } => {
+            if outlives.is_empty() {
+                param.name.clone()
+            } else {
+                format!("{}: {}", param.name, outlives.join(" + "))
+            }
+        }
+        GenericParamDefKind::Type {
+            bounds, default, ..
+        } => {
+            let mut output = param.name.clone();
+            if !bounds.is_empty() {
+                output.push_str(&format!(": {}", format_bounds(bounds)));
+            }
+            if let Some(default) = default {
+                output.push_str(&format!(" = {}", format_type(default)));
+            }
+            output
+        }
+        GenericParamDefKind::Const { type_, default } => {
+            let mut output = format!("const {}: {}", param.name, format_type(type_));
+            if let Some(default) = default {
+                output.push_str(&format!(" = {}", default));
+            }
+            output
+        }
+    }
+}
+
+/// Format a where clause, or an empty string when there are no predicates.
+fn format_where_clause(generics: &Generics) -> String {
+    if generics.where_predicates.is_empty() {
+        return String::new();
+    }
+
+    let predicates: Vec<String> = generics
+        .where_predicates
+        .iter()
+        .map(|predicate| match predicate {
+            WherePredicate::BoundPredicate {
+                type_,
+                bounds,
+                generic_params,
+            } => format!(
+                "{}{}: {}",
+                format_higher_ranked(generic_params),
+                format_type(type_),
+                format_bounds(bounds)
+            ),
+            WherePredicate::LifetimePredicate { lifetime, outlives } => {
+                format!("{}: {}", lifetime, outlives.join(" + "))
+            }
+            WherePredicate::EqPredicate { lhs, rhs } => {
+                format!("{} = {}", format_type(lhs), format_term(rhs))
+            }
+        })
+        .collect();
+
+    format!(" where {}", predicates.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use rustdoc_types::{
+        Deprecation, DynTrait, Enum, Function, ItemSummary, Module, PolyTrait, Struct, Target,
+        Trait, Variant,
+    };
+
+    use super::*;
+
+    fn item(id: u32, name: &str, docs: Option<&str>, inner: ItemEnum) -> Item {
+        Item {
+            id: Id(id),
+            crate_id: 0,
+            name: Some(name.to_string()),
+            span: None,
+            visibility: Visibility::Public,
+            docs: docs.map(str::to_string),
+            links: HashMap::new(),
+            attrs: Vec::new(),
+            deprecation: None,
+            inner,
+        }
+    }
+
+    fn generics() -> Generics {
+        Generics {
+            params: Vec::new(),
+            where_predicates: Vec::new(),
+        }
+    }
+
+    fn resolved(path: &str) -> Type {
+        Type::ResolvedPath(Path {
+            path: path.to_string(),
+            id: Id(999),
+            args: None,
+        })
+    }
+
+    fn function(inputs: Vec<(&str, Type)>, output: Option<Type>, has_body: bool) -> ItemEnum {
+        ItemEnum::Function(Function {
+            sig: FunctionSignature {
+                inputs: inputs
+                    .into_iter()
+                    .map(|(name, ty)| (name.to_string(), ty))
+                    .collect(),
+                output,
+                is_c_variadic: false,
+            },
+            generics: generics(),
+            header: FunctionHeader {
+                is_const: false,
+                is_unsafe: false,
+                is_async: false,
+                abi: Abi::Rust,
+            },
+            has_body,
+        })
+    }
+
+    fn sample_crate() -> Crate {
+        let mut index = HashMap::new();
+        let mut paths = HashMap::new();
+
+        index.insert(
+            Id(0),
+            item(
+                0,
+                "sample",
+                Some("# Sample\n\nA sample crate.\n\n# Examples\n\n```\n# hidden\n```"),
+                ItemEnum::Module(Module {
+                    is_crate: true,
+                    items: vec![Id(1), Id(2), Id(10)],
+                    is_stripped: false,
+                }),
+            ),
+        );
+        paths.insert(
+            Id(0),
+            ItemSummary {
+                crate_id: 0,
+                path: vec!["sample".to_string()],
+                kind: ItemKind::Module,
+            },
+        );
+
+        index.insert(
+            Id(1),
+            item(
+                1,
+                "Config",
+                Some("Configuration values.\n\nMore details."),
+                ItemEnum::Struct(Struct {
+                    kind: StructKind::Plain {
+                        fields: vec![Id(3)],
+                        has_stripped_fields: true,
+                    },
+                    generics: generics(),
+                    impls: vec![Id(4)],
+                }),
+            ),
+        );
+        paths.insert(
+            Id(1),
+            ItemSummary {
+                crate_id: 0,
+                path: vec!["sample".to_string(), "Config".to_string()],
+                kind: ItemKind::Struct,
+            },
+        );
+        index.insert(
+            Id(3),
+            item(
+                3,
+                "name",
+                Some("The name."),
+                ItemEnum::StructField(resolved("String")),
+            ),
+        );
+        index.insert(
+            Id(4),
+            Item {
+                name: None,
+                ..item(
+                    4,
+                    "",
+                    None,
+                    ItemEnum::Impl(Impl {
+                        is_unsafe: false,
+                        generics: generics(),
+                        provided_trait_methods: Vec::new(),
+                        trait_: None,
+                        for_: resolved("Config"),
+                        items: vec![Id(5)],
+                        is_negative: false,
+                        is_synthetic: false,
+                        blanket_impl: None,
+                    }),
+                )
+            },
+        );
+        index.insert(
+            Id(5),
+            item(
+                5,
+                "name",
+                Some("Returns the name."),
+                function(
+                    vec![(
+                        "self",
+                        Type::BorrowedRef {
+                            lifetime: None,
+                            is_mutable: false,
+                            type_: Box::new(Type::Generic("Self".to_string())),
+                        },
+                    )],
+                    Some(Type::BorrowedRef {
+                        lifetime: None,
+                        is_mutable: false,
+                        type_: Box::new(Type::Primitive("str".to_string())),
+                    }),
+                    true,
+                ),
+            ),
+        );
+
+        index.insert(
+            Id(2),
+            item(
+                2,
+                "Render",
+                Some("Things that can be rendered."),
+                ItemEnum::Trait(Trait {
+                    is_auto: false,
+                    is_unsafe: false,
+                    is_dyn_compatible: true,
+                    items: vec![Id(6)],
+                    generics: generics(),
+                    bounds: Vec::new(),
+                    implementations: Vec::new(),
+                }),
+            ),
+        );
+        paths.insert(
+            Id(2),
+            ItemSummary {
+                crate_id: 0,
+                path: vec!["sample".to_string(), "Render".to_string()],
+                kind: ItemKind::Trait,
+            },
+        );
+        index.insert(
+            Id(6),
+            Item {
+                visibility: Visibility::Default,
+                ..item(
+                    6,
+                    "render",
+                    None,
+                    function(
+                        vec![(
+                            "self",
+                            Type::BorrowedRef {
+                                lifetime: None,
+                                is_mutable: false,
+                                type_: Box::new(Type::Generic("Self".to_string())),
+                            },
+                        )],
+                        Some(resolved("String")),
+                        false,
+                    ),
+                )
+            },
+        );
+
+        index.insert(
+            Id(10),
+            item(
+                10,
+                "Mode",
+                None,
+                ItemEnum::Enum(Enum {
+                    generics: generics(),
+                    has_stripped_variants: false,
+                    variants: vec![Id(11), Id(12)],
+                    impls: Vec::new(),
+                }),
+            ),
+        );
+        paths.insert(
+            Id(10),
+            ItemSummary {
+                crate_id: 0,
+                path: vec![
+                    "sample".to_string(),
+                    "inner".to_string(),
+                    "Mode".to_string(),
+                ],
+                kind: ItemKind::Enum,
+            },
+        );
+        index.insert(
+            Id(11),
+            item(
+                11,
+                "Fast",
+                None,
+                ItemEnum::Variant(Variant {
+                    kind: VariantKind::Plain,
+                    discriminant: None,
+                }),
+            ),
+        );
+        index.insert(
+            Id(12),
+            item(
+                12,
+                "Custom",
+                None,
+                ItemEnum::Variant(Variant {
+                    kind: VariantKind::Tuple(vec![Some(Id(13))]),
+                    discriminant: None,
+                }),
+            ),
+        );
+        index.insert(
+            Id(13),
+            Item {
+                visibility: Visibility::Default,
+                ..item(
+                    13,
+                    "0",
+                    None,
+                    ItemEnum::StructField(Type::Primitive("u8".to_string())),
+                )
+            },
+        );
+
+        paths.insert(
+            Id(100),
+            ItemSummary {
+                crate_id: 1,
+                path: vec![
+                    "std".to_string(),
+                    "string".to_string(),
+                    "String".to_string(),
+                ],
+                kind: ItemKind::Struct,
+            },
+        );
+
+        Crate {
+            root: Id(0),
+            crate_version: Some("0.1.0".to_string()),
+            includes_private: false,
+            index,
+            paths,
+            external_crates: HashMap::new(),
+            target: Target {
+                triple: "x86_64-unknown-linux-gnu".to_string(),
+                target_features: Vec::new(),
+            },
+            format_version: rustdoc_types::FORMAT_VERSION,
+        }
+    }
+
+    #[test]
+    fn convert_lists_local_items_grouped_by_kind() {
+        let doc = convert(&sample_crate(), &[]).unwrap();
+        assert_eq!(doc.lib_name, "sample");
+        assert_eq!(
+            doc.all,
+            "# List of all items\n\n### Structs\n\n- Config\n\n### Enums\n\n- inner::Mode\n\n### Traits\n\n- Render\n\n"
+        );
+        assert_eq!(doc.items.len(), 3);
+        assert!(!doc.all.contains("String"));
+    }
+
+    #[test]
+    fn convert_uses_rustdoc_file_names() {
+        let doc = convert(&sample_crate(), &[]).unwrap();
+        let files: Vec<(&str, &str)> = doc
+            .items
+            .iter()
+            .map(|page| (page.path.as_str(), page.file.as_str()))
+            .collect();
+        assert!(files.contains(&("Config", "struct.Config.md")));
+        assert!(files.contains(&("inner::Mode", "inner/enum.Mode.md")));
+        assert!(files.contains(&("Render", "trait.Render.md")));
+    }
+
+    #[test]
+    fn convert_renders_struct_page() {
+        let doc = convert(&sample_crate(), &[]).unwrap();
+        let page = doc.items.iter().find(|p| p.path == "Config").unwrap();
+        assert_eq!(
+            page.markdown,
+            concat!(
+                "# Struct Config\n\n",
+                "## Declaration\n\n",
+                "```rust,item-decl\npub struct Config {\n    pub name: String,\n    /* private fields */\n}\n```\n\n",
+                "## Description\n\n",
+                "Configuration values.\n\nMore details.\n\n",
+                "## Fields\n\n",
+                "### `name: String`\n\nThe name.\n\n",
+                "## Inherent Methods\n\n",
+                "### `pub fn name(&self) -> &str`\n\nReturns the name.\n\n",
+            )
+        );
+    }
+
+    #[test]
+    fn convert_lists_member_anchors() {
+        let doc = convert(&sample_crate(), &[]).unwrap();
+        let members = |path: &str| {
+            doc.items
+                .iter()
+                .find(|p| p.path == path)
+                .map(|p| p.members.clone())
+                .unwrap()
+        };
+        assert_eq!(members("Config"), vec!["structfield.name", "method.name"]);
+        assert_eq!(members("Render"), vec!["tymethod.render"]);
+        assert_eq!(
+            members("inner::Mode"),
+            vec!["variant.Fast", "variant.Custom"]
+        );
+    }
+
+    #[test]
+    fn convert_resolves_intra_doc_links() {
+        let mut krate = sample_crate();
+        if let Some(config) = krate.index.get_mut(&Id(1)) {
+            config.docs = Some(
+                concat!(
+                    "Rendered by [`Render`], see [`Render::render`], [the name][name] ",
+                    "and [`Mode::Custom`].\n\n",
+                    "Keeps [`Missing`], `[Render]`, [`Gone`] and [a string](String).\n\n",
+                    "```\nlet [`Render`] = x;\n```\n\n",
+                    "[name]: Config::name",
+                )
+                .to_string(),
+            );
+            config.links = HashMap::from([
+                ("`Render`".to_string(), Id(2)),
+                ("`Render::render`".to_string(), Id(6)),
+                ("Config::name".to_string(), Id(3)),
+                ("`Mode::Custom`".to_string(), Id(12)),
+                ("`Gone`".to_string(), Id(500)),
+                ("String".to_string(), Id(100)),
+            ]);
+        }
+        let doc = convert(&krate, &[]).unwrap();
+        let page = doc.items.iter().find(|p| p.path == "Config").unwrap();
+        assert!(page.markdown.contains(concat!(
+            "## Description\n\n",
+            "Rendered by [`Render`](sample::Render), see [`Render::render`](sample::Render::render), ",
+            "[the name](sample::Config::name) and [`Mode::Custom`](sample::inner::Mode::Custom).\n\n",
+            "Keeps [`Missing`], `[Render]`, `Gone` and [a string](std::string::String).\n\n",
+            "```\nlet [`Render`] = x;\n```\n\n",
+            "## Fields",
+        )));
+    }
+
+    #[test]
+    fn convert_records_item_info() {
+        let mut krate = sample_crate();
+        if let Some(config) = krate.index.get_mut(&Id(1)) {
+            config.deprecation = Some(Deprecation {
+                since: Some("0.2.0".to_string()),
+                note: Some("use `Settings`".to_string()),
+            });
+        }
+        let doc = convert(&krate, &[]).unwrap();
+        let info = &doc.items.iter().find(|p| p.path == "Config").unwrap().info;
+        assert!(
+            info.signature
+                .as_ref()
+                .unwrap()
+                .starts_with("pub struct Config {")
+        );
+        assert_eq!(info.summary.as_deref(), Some("Configuration values."));
+        assert_eq!(
+            info.deprecated.as_deref(),
+            Some("Deprecated since 0.2.0: use `Settings`")
+        );
+        assert_eq!(info.kind, None);
+    }
+
+    #[test]
+    fn convert_skips_sections() {
+        let doc = convert(
+            &sample_crate(),
+            &[Section::Declaration, Section::InherentMethods],
+        )
+        .unwrap();
+        let page = doc.items.iter().find(|p| p.path == "Config").unwrap();
+        assert!(!page.markdown.contains("## Declaration"));
+        assert!(!page.markdown.contains("## Inherent Methods"));
+        assert!(page.markdown.contains("## Description\n\n"));
+        assert!(page.markdown.contains("## Fields\n\n"));
+    }
+
+    #[test]
+    fn convert_renders_trait_and_enum_declarations() {
+        let doc = convert(&sample_crate(), &[]).unwrap();
+        let page = doc.items.iter().find(|p| p.path == "Render").unwrap();
+        assert!(page.markdown.contains(
+            "```rust,item-decl\npub trait Render {\n    fn render(&self) -> String;\n}\n```"
+        ));
+        assert!(page.markdown.contains("## Required Methods\n\n"));
+
+        let page = doc.items.iter().find(|p| p.path == "inner::Mode").unwrap();
+        assert!(
+            page.markdown
+                .contains("pub enum Mode {\n    Fast,\n    Custom(u8),\n}")
+        );
+        assert!(page.markdown.contains("## Variants\n\n### `Fast`"));
+    }
+
+    #[test]
+    fn convert_splits_trait_items_into_sections() {
+        let mut krate = sample_crate();
+        let assoc_type = |type_: Option<Type>| ItemEnum::AssocType {
+            generics: generics(),
+            bounds: Vec::new(),
+            type_,
+        };
+        let assoc_const = |value: Option<&str>| ItemEnum::AssocConst {
+            type_: Type::Primitive("usize".to_string()),
+            value: value.map(str::to_string),
+        };
+        for (id, name, inner) in [
+            (20, "Output", assoc_type(None)),
+            (21, "Error", assoc_type(Some(resolved("String")))),
+            (22, "LIMIT", assoc_const(None)),
+            (23, "WIDTH", assoc_const(Some("80"))),
+        ] {
+            krate.index.insert(Id(id), item(id, name, None, inner));
+        }
+        let ItemEnum::Trait(render) = &mut krate.index.get_mut(&Id(2)).unwrap().inner else {
+            unreachable!();
+        };
+        render.items.extend([Id(20), Id(21), Id(22), Id(23)]);
+
+        let doc = convert(&krate, &[]).unwrap();
+        let page = doc.items.iter().find(|p| p.path == "Render").unwrap();
+        let sections: Vec<&str> = page
+            .markdown
+            .lines()
+            .filter_map(|line| line.strip_prefix("## "))
+            .collect();
+        assert_eq!(
+            sections,
+            vec![
+                "Declaration",
+                "Description",
+                "Required Associated Types",
+                "Provided Associated Types",
+                "Required Associated Constants",
+                "Provided Associated Constants",
+                "Required Methods",
+            ]
+        );
+        assert!(
+            page.markdown
+                .contains("## Required Associated Types\n\n### `type Output`\n\n")
+        );
+        assert!(
+            page.markdown
+                .contains("## Provided Associated Types\n\n### `type Error = String`\n\n")
+        );
+        assert!(
+            page.markdown
+                .contains("## Required Associated Constants\n\n### `const LIMIT: usize`\n\n")
+        );
+        assert!(
+            page.markdown
+                .contains("## Provided Associated Constants\n\n### `const WIDTH: usize = 80`\n\n")
+        );
+        assert!(
+            page.markdown
+                .contains("## Required Methods\n\n### `fn render(&self) -> String`\n\n")
+        );
+
+        let doc = convert(&krate, &[Section::RequiredAssociatedTypes]).unwrap();
+        let page = doc.items.iter().find(|p| p.path == "Render").unwrap();
+        assert!(!page.markdown.contains("## Required Associated Types"));
+        assert!(page.markdown.contains("## Provided Associated Types"));
+    }
+
+    #[test]
+    fn convert_renders_index_with_shifted_headings() {
+        let doc = convert(&sample_crate(), &[]).unwrap();
+        assert!(doc.index.starts_with(
+            "# Crate sample\n\n## Sample\n\nA sample crate.\n\n## Examples\n\n```\n# hidden\n```"
+        ));
+        assert!(
+            doc.index
+                .contains("## Structs\n\n- **Config**: Configuration values.\n")
+        );
+        assert!(
+            doc.index
+                .contains("## Traits\n\n- **Render**: Things that can be rendered.\n")
+        );
+        assert!(doc.index.contains("## Enums\n\n- **Mode**\n"));
+    }
+
+    #[test]
+    fn convert_fails_without_items() {
+        let mut krate = sample_crate();
+        krate
+            .paths
+            .retain(|_, summary| summary.kind == ItemKind::Module);
+        let result = convert(&krate, &[]);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("no items found"));
+    }
+
+    #[test]
+    fn format_type_renders_references_and_generics() {
+        let ty = Type::BorrowedRef {
+            lifetime: Some("'a".to_string()),
+            is_mutable: true,
+            type_: Box::new(Type::ResolvedPath(Path {
+                path: "Vec".to_string(),
+                id: Id(1),
+                args: Some(Box::new(GenericArgs::AngleBracketed {
+                    args: vec![GenericArg::Type(Type::Generic("T".to_string()))],
+                    constraints: Vec::new(),
+                })),
+            })),
+        };
+        assert_eq!(format_type(&ty), "&'a mut Vec<T>");
+
+        let ty = Type::Tuple(vec![Type::Primitive("u8".to_string())]);
+        assert_eq!(format_type(&ty), "(u8,)");
+
+        let ty = Type::Array {
+            type_: Box::new(Type::Primitive("u8".to_string())),
+            len: "4".to_string(),
+        };
+        assert_eq!(format_type(&ty), "[u8; 4]");
+    }
+
+    #[test]
+    fn format_type_renders_qualified_paths() {
+        let ty = Type::QualifiedPath {
+            name: "Ok".to_string(),
+            args: None,
+            self_type: Box::new(Type::Generic("S".to_string())),
+            trait_: Some(Path {
+                path: "Serializer".to_string(),
+                id: Id(1),
+                args: None,
+            }),
+        };
+        assert_eq!(format_type(&ty), "<S as Serializer>::Ok");
+    }
+
+    #[test]
+    fn format_type_parenthesizes_dyn_pointees_with_several_bounds() {
+        let dyn_trait = |lifetime: Option<&str>| {
+            Type::DynTrait(DynTrait {
+                traits: vec![PolyTrait {
+                    trait_: Path {
+                        path: "StdError".to_string(),
+                        id: Id(1),
+                        args: None,
+                    },
+                    generic_params: Vec::new(),
+                }],
+                lifetime: lifetime.map(str::to_string),
+            })
+        };
+
+        let ty = Type::BorrowedRef {
+            lifetime: None,
+            is_mutable: false,
+            type_: Box::new(dyn_trait(Some("'static"))),
+        };
+        assert_eq!(format_type(&ty), "&(dyn StdError + 'static)");
+
+        let ty = Type::RawPointer {
+            is_mutable: true,
+            type_: Box::new(dyn_trait(Some("'static"))),
+        };
+        assert_eq!(format_type(&ty), "*mut (dyn StdError + 'static)");
+
+        let ty = Type::BorrowedRef {
+            lifetime: None,
+            is_mutable: false,
+            type_: Box::new(dyn_trait(None)),
+        };
+        assert_eq!(format_type(&ty), "&dyn StdError");
+    }
+
+    #[test]
+    fn shift_headings_skips_code_fences() {
+        let docs = "# Title\n\n```\n# not a heading\n```\n\n## Sub";
+        assert_eq!(
+            shift_headings(docs, 1),
+            "## Title\n\n```\n# not a heading\n```\n\n### Sub"
+        );
+    }
+
+    #[test]
+    fn summary_returns_first_paragraph_on_one_line() {
+        assert_eq!(
+            summary(Some("First line\ncontinues.\n\nSecond paragraph.")),
+            "First line continues."
+        );
+        assert_eq!(summary(None), "");
+    }
+}
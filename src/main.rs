@@ -3,15 +3,28 @@
 //! This tool converts rustdoc HTML output into markdown documentation designed
 //! for coding agents to browse and understand crate APIs.
 
+use std::io;
+
 use anyhow::Result;
+use cargo::DocOptions;
 use clap::{Parser, Subcommand};
 use clap_verbosity_flag::{InfoLevel, Verbosity};
-use commands::{build, list, show};
+use commands::build::{Backend, BuildScope};
+use commands::list::{ItemKind, ListFilter};
+use commands::show::ShowSection;
+use commands::{OutputFormat, build, build_all, ignore_broken_pipe, list, mcp, search, show};
+use sections::Section;
 use tracing::level_filters::LevelFilter;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
 
 mod cargo;
 mod commands;
 mod html2md;
+mod item_info;
+mod json2md;
+mod reexports;
+mod search_index;
+mod sections;
 
 /// A cargo doc for coding agents
 #[derive(Parser)]
@@ -29,26 +42,157 @@ struct Args {
 
 #[derive(Subcommand)]
 enum Command {
-    /// Generate markdown documentation from rustdoc HTML for coding agents.
+    /// Generate markdown documentation from rustdoc output for coding agents.
     Build {
-        /// Crate name to build documentation for
-        #[arg(value_name = "CRATE")]
-        crate_name: String,
+        /// Crate name to build documentation for (a dependency, a workspace member or std, core, alloc, proc_macro, test)
+        #[arg(value_name = "CRATE", required_unless_present = "all")]
+        crate_name: Option<String>,
+
+        /// Build documentation for the workspace members and their dependencies
+        #[arg(long, conflicts_with = "crate_name")]
+        all: bool,
+
+        /// With --all, only build the direct dependencies of the workspace members
+        #[arg(long, requires = "all", conflicts_with = "include_transitive")]
+        direct_only: bool,
+
+        /// With --all, also build every transitive dependency
+        #[arg(long, requires = "all")]
+        include_transitive: bool,
+
+        /// Workspace member whose dependencies are used to resolve the crate
+        #[arg(short, long, value_name = "SPEC")]
+        package: Option<String>,
+
+        /// Documentation source to convert
+        #[arg(long, value_enum, default_value_t = Backend::Html)]
+        backend: Backend,
+
+        /// Document private items, useful for workspace member crates
+        #[arg(long)]
+        document_private_items: bool,
+
+        /// Features of the crate to document (can be repeated or comma-separated)
+        #[arg(
+            short = 'F',
+            long,
+            value_name = "FEATURES",
+            value_delimiter = ',',
+            conflicts_with = "all"
+        )]
+        features: Vec<String>,
+
+        /// Document every feature of the crate
+        #[arg(long, conflicts_with = "all")]
+        all_features: bool,
+
+        /// Do not enable the default feature of the crate
+        #[arg(long, conflicts_with = "all")]
+        no_default_features: bool,
+
+        /// Document for a target triple (e.g., 'x86_64-pc-windows-msvc')
+        #[arg(long, value_name = "TRIPLE")]
+        target: Option<String>,
+
+        /// Build like docs.rs, with the crate's [package.metadata.docs.rs] settings and `--cfg docsrs` (requires nightly)
+        #[arg(long, conflicts_with = "all")]
+        docsrs: bool,
+
+        /// Leave a section out of item pages (can be repeated or comma-separated)
+        #[arg(long, value_enum, value_name = "SECTION", value_delimiter = ',')]
+        skip_section: Vec<Section>,
+
+        /// Output format of the build summary
+        #[arg(long, value_enum, default_value_t = OutputFormat::Markdown)]
+        format: OutputFormat,
     },
 
     /// Show and display crate documentation.
     Show {
-        /// Item identifier (e.g., 'serde', 'serde::Error', 'serde::ser::StdError')
+        /// Item identifier (e.g., 'serde', 'serde::Error', 'serde::ser::StdError',
+        /// 'serde::Serializer::serialize_str' or 'syn@1::Item')
         #[arg(value_name = "ITEM")]
         item_identifier: String,
+
+        /// Only print these sections of the page (can be repeated or comma-separated)
+        #[arg(long, value_enum, value_name = "SECTION", value_delimiter = ',')]
+        section: Vec<ShowSection>,
+
+        /// Only print signatures: the declaration of an item, or the
+        /// signatures of a member or of the selected sections
+        #[arg(long)]
+        signature_only: bool,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Markdown)]
+        format: OutputFormat,
     },
 
-    /// List all items in a library.
+    /// List all items in a library, or the items of a module.
     List {
-        /// Library name (e.g., 'serde')
-        #[arg(value_name = "LIBRARY")]
-        lib_name: String,
+        /// Library name or module path (e.g., 'serde', 'serde::de' or 'syn@1')
+        #[arg(value_name = "PATH")]
+        path: String,
+
+        /// Print the module hierarchy with the number of items of each module
+        #[arg(long)]
+        tree: bool,
+
+        /// Only list items of these kinds (can be repeated or comma-separated)
+        #[arg(long, value_enum, value_name = "KIND", value_delimiter = ',')]
+        kind: Vec<ItemKind>,
+
+        /// Only list items whose name matches this glob (e.g., '*Stream*')
+        #[arg(long, value_name = "PATTERN")]
+        filter: Option<String>,
+
+        /// Interpret the --filter pattern as a regular expression
+        #[arg(long, requires = "filter")]
+        regex: bool,
+
+        /// Only list items below this module (e.g., 'io' or 'tokio::io')
+        #[arg(long, value_name = "MODULE")]
+        module: Option<String>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Markdown)]
+        format: OutputFormat,
     },
+
+    /// Search item paths and documentation across built libraries.
+    Search {
+        /// Words or identifier to search for (e.g., 'backpressure', 'AsyncRead')
+        #[arg(value_name = "QUERY")]
+        query: String,
+
+        /// Only search this library (e.g., 'tokio' or 'syn@1')
+        #[arg(short, long, value_name = "LIBRARY")]
+        lib: Option<String>,
+
+        /// Maximum number of results to print
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Markdown)]
+        format: OutputFormat,
+    },
+
+    /// Serve build, list, show and search over the Model Context Protocol on stdio.
+    Mcp,
+}
+
+impl Command {
+    /// Return the output format of the command.
+    fn format(&self) -> OutputFormat {
+        match self {
+            Command::Build { format, .. }
+            | Command::Show { format, .. }
+            | Command::List { format, .. }
+            | Command::Search { format, .. } => *format,
+            Command::Mcp => OutputFormat::Markdown,
+        }
+    }
 }
 
 fn main() -> Result<()> {
@@ -60,8 +204,14 @@ fn main() -> Result<()> {
 
     let args = Args::parse_from(&args);
 
+    // Keep stdout for the JSON document, or for the MCP messages.
+    let writer = match (&args.command, args.command.format()) {
+        (Command::Mcp, _) | (_, OutputFormat::Json) => BoxMakeWriter::new(std::io::stderr),
+        (_, OutputFormat::Markdown) => BoxMakeWriter::new(std::io::stdout),
+    };
     if args.verbosity.tracing_level_filter() == LevelFilter::TRACE {
         tracing_subscriber::fmt()
+            .with_writer(writer)
             .with_max_level(args.verbosity)
             .init();
     } else {
@@ -69,14 +219,109 @@ fn main() -> Result<()> {
             .compact()
             .without_time()
             .with_target(false)
+            .with_writer(writer)
             .with_max_level(args.verbosity)
             .init();
     }
 
-    match args.command {
-        Command::Build { crate_name } => build(&crate_name)?,
-        Command::Show { item_identifier } => show(&item_identifier)?,
-        Command::List { lib_name } => list(&lib_name)?,
+    // A closed stdout, as in `cargo txt list serde | head`, is not an error.
+    ignore_broken_pipe(run(args.command))
+}
+
+/// Run a subcommand.
+fn run(command: Command) -> Result<()> {
+    match command {
+        Command::Build {
+            crate_name,
+            all,
+            direct_only,
+            include_transitive,
+            package,
+            backend,
+            document_private_items,
+            features,
+            all_features,
+            no_default_features,
+            target,
+            docsrs,
+            skip_section,
+            format,
+        } => {
+            let doc_options = DocOptions {
+                document_private_items,
+                features,
+                all_features,
+                no_default_features,
+                target,
+                docsrs,
+                ..Default::default()
+            };
+            match crate_name {
+                Some(crate_name) if !all => build(
+                    &crate_name,
+                    package.as_deref(),
+                    backend,
+                    &skip_section,
+                    &doc_options,
+                    format,
+                    &mut io::stdout(),
+                )?,
+                _ => {
+                    let scope = if direct_only {
+                        BuildScope::DirectOnly
+                    } else if include_transitive {
+                        BuildScope::Transitive
+                    } else {
+                        BuildScope::Workspace
+                    };
+                    build_all(
+                        package.as_deref(),
+                        scope,
+                        backend,
+                        &skip_section,
+                        &doc_options,
+                        format,
+                        &mut io::stdout(),
+                    )?
+                }
+            }
+        }
+        Command::Show {
+            item_identifier,
+            section,
+            signature_only,
+            format,
+        } => show(
+            &item_identifier,
+            &section,
+            signature_only,
+            format,
+            &mut io::stdout(),
+        )?,
+        Command::List {
+            path,
+            tree,
+            kind,
+            filter,
+            regex,
+            module,
+            format,
+        } => {
+            let filter = ListFilter {
+                kinds: kind,
+                pattern: filter,
+                regex,
+                module,
+            };
+            list(&path, tree, &filter, format, &mut io::stdout())?
+        }
+        Command::Search {
+            query,
+            lib,
+            limit,
+            format,
+        } => search(&query, lib.as_deref(), limit, format, &mut io::stdout())?,
+        Command::Mcp => mcp()?,
     }
 
     Ok(())
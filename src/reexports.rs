@@ -0,0 +1,204 @@
+//! Re-export resolution.
+//!
+//! Items are documented under their canonical path, but can be reachable
+//! under other public paths through `pub use` re-exports, renames and glob
+//! imports, such as `tokio::spawn` for `tokio::task::spawn`. This module
+//! turns the re-exports declared by modules into a map from every such path
+//! to the canonical item path.
+
+use std::collections::{BTreeMap, HashMap};
+
+use tracing::trace;
+
+/// A `pub use` declaration of a module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reexport {
+    /// Full path of the module declaring the re-export (e.g., "tokio")
+    pub module: String,
+    /// Name the target is re-exported under, or `None` for a glob import
+    pub name: Option<String>,
+    /// Full canonical path of the re-exported item or module
+    /// (e.g., "tokio::task::spawn")
+    pub target: String,
+}
+
+/// Maximum number of chained re-exports that are followed.
+///
+/// Module re-exports can form cycles, such as a module re-exporting its
+/// parent, which would otherwise create ever longer paths.
+const MAX_DEPTH: usize = 8;
+
+/// Build the map of alias paths to canonical item paths.
+///
+/// `item_paths` are the canonical paths of the documented items. A named
+/// re-export of an item adds the path it is re-exported under; a named
+/// re-export of a module, or a glob import, adds every item below the
+/// module under the re-exporting module. Re-exports of re-exports are
+/// followed, up to `MAX_DEPTH` hops. Paths of documented items are never
+/// aliases, and when several re-exports create the same path the first one
+/// wins.
+pub fn alias_map<'a>(
+    item_paths: impl IntoIterator<Item = &'a String>,
+    reexports: &[Reexport],
+) -> HashMap<String, String> {
+    let mut known: BTreeMap<String, String> = item_paths
+        .into_iter()
+        .map(|path| (path.clone(), path.clone()))
+        .collect();
+    let mut aliases = HashMap::new();
+
+    for depth in 0..MAX_DEPTH {
+        let mut added = Vec::new();
+        for reexport in reexports {
+            let prefix = match &reexport.name {
+                Some(name) => format!("{}::{}", reexport.module, name),
+                None => reexport.module.clone(),
+            };
+            if reexport.name.is_some()
+                && let Some(canonical) = known.get(&reexport.target)
+            {
+                added.push((prefix.clone(), canonical.clone()));
+            }
+
+            // Re-exporting a module from inside itself would nest it forever.
+            let target_prefix = format!("{}::", reexport.target);
+            if reexport.module == reexport.target || reexport.module.starts_with(&target_prefix) {
+                continue;
+            }
+            for (path, canonical) in known
+                .range(target_prefix.clone()..)
+                .take_while(|(path, _)| path.starts_with(&target_prefix))
+            {
+                let alias = format!("{}::{}", prefix, &path[target_prefix.len()..]);
+                added.push((alias, canonical.clone()));
+            }
+        }
+
+        let mut changed = false;
+        for (alias, canonical) in added {
+            if !known.contains_key(&alias) {
+                known.insert(alias.clone(), canonical.clone());
+                aliases.insert(alias, canonical);
+                changed = true;
+            }
+        }
+        trace!(
+            "Resolved {} aliases after {} hops",
+            aliases.len(),
+            depth + 1
+        );
+        if !changed {
+            break;
+        }
+    }
+
+    aliases
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reexport(module: &str, name: Option<&str>, target: &str) -> Reexport {
+        Reexport {
+            module: module.to_string(),
+            name: name.map(str::to_string),
+            target: target.to_string(),
+        }
+    }
+
+    fn items() -> Vec<String> {
+        [
+            "probe::Widget",
+            "probe::task::spawn",
+            "probe::task::nested::Deep",
+        ]
+        .iter()
+        .map(|path| path.to_string())
+        .collect()
+    }
+
+    #[test]
+    fn alias_map_named_reexports() {
+        let aliases = alias_map(
+            &items(),
+            &[
+                reexport("probe", Some("spawn"), "probe::task::spawn"),
+                reexport("probe::util", Some("Gadget"), "probe::Widget"),
+            ],
+        );
+        assert_eq!(
+            aliases,
+            HashMap::from([
+                ("probe::spawn".to_string(), "probe::task::spawn".to_string()),
+                (
+                    "probe::util::Gadget".to_string(),
+                    "probe::Widget".to_string()
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn alias_map_glob_and_module_reexports() {
+        let aliases = alias_map(
+            &items(),
+            &[
+                reexport("probe::util", None, "probe::task"),
+                reexport("probe::util", Some("inner"), "probe::task::nested"),
+            ],
+        );
+        assert_eq!(
+            aliases,
+            HashMap::from([
+                (
+                    "probe::util::spawn".to_string(),
+                    "probe::task::spawn".to_string()
+                ),
+                (
+                    "probe::util::nested::Deep".to_string(),
+                    "probe::task::nested::Deep".to_string()
+                ),
+                (
+                    "probe::util::inner::Deep".to_string(),
+                    "probe::task::nested::Deep".to_string()
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn alias_map_follows_chained_reexports() {
+        let aliases = alias_map(
+            &items(),
+            &[
+                reexport("probe", None, "probe::prelude"),
+                reexport("probe::prelude", Some("Deep"), "probe::task::nested::Deep"),
+            ],
+        );
+        assert_eq!(
+            aliases.get("probe::Deep").map(String::as_str),
+            Some("probe::task::nested::Deep")
+        );
+    }
+
+    #[test]
+    fn alias_map_keeps_items_and_stops_on_cycles() {
+        let aliases = alias_map(
+            &items(),
+            &[
+                reexport("probe::task", Some("Widget"), "probe::Widget"),
+                reexport("probe", Some("Widget"), "probe::task::spawn"),
+                reexport("probe::task", Some("parent"), "probe"),
+                reexport("probe::task", None, "probe"),
+            ],
+        );
+        assert_eq!(
+            aliases,
+            HashMap::from([(
+                "probe::task::Widget".to_string(),
+                "probe::Widget".to_string()
+            )])
+        );
+    }
+}
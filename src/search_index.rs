@@ -0,0 +1,373 @@
+//! Search index over generated markdown documentation.
+//!
+//! This module builds a small inverted index for each documented crate, stored
+//! as `search.json` next to `metadata.json`, and ranks items against a query.
+//! Items are matched both by identifier, against their item path, and by full
+//! text, against the words of their markdown page.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, trace};
+
+use crate::commands::build::CrateDocMetadata;
+
+/// File name of the search index inside a `docmd/<lib_name>` directory.
+pub const INDEX_FILE: &str = "search.json";
+
+/// Maximum length of a summary or snippet, in characters.
+const SNIPPET_LEN: usize = 160;
+
+/// Inverted index of the items of a documented crate.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SearchIndex {
+    /// Indexed items, referenced by position from `terms`
+    pub items: Vec<IndexedItem>,
+    /// Postings of each lowercase term as `(item index, occurrences)` pairs
+    pub terms: BTreeMap<String, Vec<(usize, usize)>>,
+}
+
+/// An item of the search index.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IndexedItem {
+    /// The full item path (e.g., "serde::de::Visitor")
+    pub path: String,
+    /// The markdown file path relative to the crate's output directory
+    pub file: String,
+    /// The first line of prose of the item page
+    pub summary: String,
+}
+
+/// A ranked search result.
+#[derive(Debug, PartialEq)]
+pub struct SearchHit {
+    /// The full item path (e.g., "serde::de::Visitor")
+    pub path: String,
+    /// The markdown file path relative to the crate's output directory
+    pub file: String,
+    /// The relevance score, higher is better
+    pub score: f64,
+    /// The item summary, used as a fallback snippet
+    pub summary: String,
+}
+
+impl SearchIndex {
+    /// Build the search index of a crate from its generated markdown.
+    ///
+    /// Every item listed in `metadata.item_map` is read from `output_dir`.
+    pub fn build(output_dir: &Path, metadata: &CrateDocMetadata) -> Result<Self> {
+        let mut index = SearchIndex::default();
+
+        let mut item_paths: Vec<(&String, &String)> = metadata.item_map.iter().collect();
+        item_paths.sort();
+
+        for (position, (path, file)) in item_paths.into_iter().enumerate() {
+            let md_path = output_dir.join(file);
+            let markdown = fs::read_to_string(&md_path)
+                .with_context(|| format!("failed to read markdown file '{}'", md_path.display()))?;
+
+            let mut counts: HashMap<String, usize> = HashMap::new();
+            for term in tokenize(&markdown) {
+                *counts.entry(term).or_default() += 1;
+            }
+            for (term, count) in counts {
+                index.terms.entry(term).or_default().push((position, count));
+            }
+
+            index.items.push(IndexedItem {
+                path: path.clone(),
+                file: file.clone(),
+                summary: summary(&markdown),
+            });
+        }
+
+        for postings in index.terms.values_mut() {
+            postings.sort_unstable();
+        }
+
+        debug!(
+            "Indexed {} items and {} terms",
+            index.items.len(),
+            index.terms.len()
+        );
+
+        Ok(index)
+    }
+
+    /// Build the search index of a crate and write it to `search.json`.
+    pub fn write(output_dir: &Path, metadata: &CrateDocMetadata) -> Result<()> {
+        let index = Self::build(output_dir, metadata)?;
+        let index_path = output_dir.join(INDEX_FILE);
+        let content =
+            serde_json::to_string(&index).with_context(|| "failed to serialize search index")?;
+        fs::write(&index_path, content)
+            .with_context(|| format!("failed to write file '{}'", index_path.display()))?;
+        Ok(())
+    }
+
+    /// Load the search index of a crate, building it if `search.json` is missing.
+    pub fn load(output_dir: &Path, metadata: &CrateDocMetadata) -> Result<Self> {
+        let index_path = output_dir.join(INDEX_FILE);
+        if !index_path.exists() {
+            debug!(
+                "Search index '{}' not found, building it",
+                index_path.display()
+            );
+            return Self::build(output_dir, metadata);
+        }
+
+        let content = fs::read_to_string(&index_path)
+            .with_context(|| format!("failed to read file '{}'", index_path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("failed to parse search index '{}'", index_path.display()))
+    }
+
+    /// Rank the items of the index against a query.
+    ///
+    /// Identifier matches against the item path rank first: an exact match on
+    /// the item name, then a name containing the query, then a path containing
+    /// it. Full-text matches are scored with TF-IDF and require every query
+    /// term to appear in the page. Results are sorted best first.
+    pub fn search(&self, query: &str) -> Vec<SearchHit> {
+        let query_lower = query.trim().to_lowercase();
+        let terms: Vec<String> = tokenize(query)
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let item_count = self.items.len() as f64;
+
+        let mut scores: HashMap<usize, f64> = HashMap::new();
+
+        for (position, item) in self.items.iter().enumerate() {
+            let score = identifier_score(&item.path, &query_lower);
+            if score > 0.0 {
+                scores.insert(position, score);
+            }
+        }
+
+        let mut text_scores: Option<HashMap<usize, f64>> = None;
+        for term in &terms {
+            let Some(postings) = self.terms.get(term) else {
+                text_scores = Some(HashMap::new());
+                break;
+            };
+            let idf = (item_count / postings.len() as f64).ln() + 1.0;
+            let term_scores: HashMap<usize, f64> = postings
+                .iter()
+                .map(|&(position, count)| (position, (1.0 + (count as f64).ln()) * idf))
+                .collect();
+            text_scores = Some(match text_scores {
+                None => term_scores,
+                Some(previous) => previous
+                    .into_iter()
+                    .filter_map(|(position, score)| {
+                        term_scores.get(&position).map(|s| (position, score + s))
+                    })
+                    .collect(),
+            });
+        }
+        for (position, score) in text_scores.unwrap_or_default() {
+            *scores.entry(position).or_default() += score;
+        }
+
+        trace!("{} items match '{}'", scores.len(), query);
+
+        let mut hits: Vec<SearchHit> = scores
+            .into_iter()
+            .map(|(position, score)| {
+                let item = &self.items[position];
+                SearchHit {
+                    path: item.path.clone(),
+                    file: item.file.clone(),
+                    score,
+                    summary: item.summary.clone(),
+                }
+            })
+            .collect();
+        hits.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then(a.path.len().cmp(&b.path.len()))
+                .then(a.path.cmp(&b.path))
+        });
+        hits
+    }
+}
+
+/// Score an item path against a lowercase query.
+fn identifier_score(path: &str, query_lower: &str) -> f64 {
+    if query_lower.is_empty() {
+        return 0.0;
+    }
+
+    let path_lower = path.to_lowercase();
+    let name = path_lower.rsplit("::").next().unwrap_or_default();
+
+    if name == query_lower || path_lower == query_lower {
+        1000.0
+    } else if path_lower.ends_with(&format!("::{}", query_lower)) {
+        800.0
+    } else if name.contains(query_lower) {
+        500.0
+    } else if path_lower.contains(query_lower) {
+        200.0
+    } else {
+        0.0
+    }
+}
+
+/// Split text into lowercase terms of letters, digits and underscores.
+fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|word| word.len() >= 2 && word.len() <= 64)
+        .map(str::to_lowercase)
+}
+
+/// Extract the first line of prose from an item page.
+///
+/// Headings, code blocks and empty lines are skipped.
+pub fn summary(markdown: &str) -> String {
+    let mut in_code_block = false;
+    for line in markdown.lines() {
+        let line = line.trim();
+        if line.starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block || line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        return truncate(line);
+    }
+    String::new()
+}
+
+/// Find the first line of prose in a page that mentions one of the query terms.
+///
+/// Returns `None` if no line outside of code blocks mentions a query term.
+pub fn snippet(markdown: &str, query: &str) -> Option<String> {
+    let terms: Vec<String> = tokenize(query).collect();
+    if terms.is_empty() {
+        return None;
+    }
+
+    let mut in_code_block = false;
+    for line in markdown.lines() {
+        let line = line.trim();
+        if line.starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block || line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut words = tokenize(line);
+        if words.any(|word| terms.contains(&word)) {
+            return Some(truncate(line));
+        }
+    }
+    None
+}
+
+/// Truncate a line to `SNIPPET_LEN` characters, marking the cut with `...`.
+fn truncate(line: &str) -> String {
+    if line.chars().count() <= SNIPPET_LEN {
+        return line.to_string();
+    }
+    let truncated: String = line.chars().take(SNIPPET_LEN).collect();
+    format!("{}...", truncated.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_index() -> SearchIndex {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let pages = [
+            (
+                "tokio::io::AsyncRead",
+                "trait.AsyncRead.md",
+                "# Trait AsyncRead\n\n```\npub trait AsyncRead {}\n```\n\nReads bytes asynchronously.\n",
+            ),
+            (
+                "tokio::net::TcpStream",
+                "struct.TcpStream.md",
+                "# Struct TcpStream\n\nA TCP stream.\n\n## Trait Implementations\n\n### impl AsyncRead for TcpStream\n",
+            ),
+            (
+                "tokio::sync::mpsc::channel",
+                "fn.channel.md",
+                "# Function channel\n\nCreates a bounded channel with backpressure.\n\nThe channel applies backpressure once the buffer is full.\n",
+            ),
+        ];
+        let mut item_map = HashMap::new();
+        for (path, file, markdown) in pages {
+            fs::write(temp_dir.path().join(file), markdown).unwrap();
+            item_map.insert(path.to_string(), file.to_string());
+        }
+        let metadata = CrateDocMetadata {
+            crate_name: "tokio".to_string(),
+            lib_name: "tokio".to_string(),
+            item_map,
+            ..Default::default()
+        };
+        SearchIndex::build(temp_dir.path(), &metadata).unwrap()
+    }
+
+    #[test]
+    fn search_identifier_ranks_first() {
+        let index = test_index();
+        let hits = index.search("AsyncRead");
+        let paths: Vec<&str> = hits.iter().map(|hit| hit.path.as_str()).collect();
+        assert_eq!(paths, vec!["tokio::io::AsyncRead", "tokio::net::TcpStream"]);
+    }
+
+    #[test]
+    fn search_full_text() {
+        let index = test_index();
+        let hits = index.search("backpressure");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].path, "tokio::sync::mpsc::channel");
+        assert_eq!(
+            hits[0].summary,
+            "Creates a bounded channel with backpressure."
+        );
+    }
+
+    #[test]
+    fn search_requires_every_term() {
+        let index = test_index();
+        assert_eq!(index.search("bounded channel").len(), 1);
+        assert!(index.search("bounded stream").is_empty());
+    }
+
+    #[test]
+    fn summary_skips_headings_and_code() {
+        let markdown = "# Trait AsyncRead\n\n```\npub trait AsyncRead {}\n```\n\nReads bytes.\n";
+        assert_eq!(summary(markdown), "Reads bytes.");
+        assert_eq!(summary("# Only a title\n"), "");
+    }
+
+    #[test]
+    fn snippet_finds_matching_line() {
+        let markdown =
+            "# Function channel\n\nCreates a channel.\n\nApplies backpressure when full.\n";
+        assert_eq!(
+            snippet(markdown, "Backpressure"),
+            Some("Applies backpressure when full.".to_string())
+        );
+        assert_eq!(snippet(markdown, "missing"), None);
+    }
+
+    #[test]
+    fn truncate_long_lines() {
+        let line = "word ".repeat(100);
+        let truncated = truncate(&line);
+        assert!(truncated.ends_with("..."));
+        assert!(truncated.chars().count() <= SNIPPET_LEN + 3);
+    }
+}
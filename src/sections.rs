@@ -0,0 +1,732 @@
+//! Sections of item documentation pages.
+//!
+//! Both backends render item pages with the same `##` sections, so agents
+//! can rely on their names. Each section can be left out at build time with
+//! `--skip-section`, and the functions of this module extract sections,
+//! members and examples from the generated markdown for `show`.
+
+use serde::{Deserialize, Serialize};
+
+/// A section of an item page.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    clap::ValueEnum,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum Section {
+    /// The item declaration
+    Declaration,
+    /// The item documentation
+    Description,
+    /// Trait associated types without a default
+    RequiredAssociatedTypes,
+    /// Trait associated types with a default
+    ProvidedAssociatedTypes,
+    /// Trait associated constants without a default value
+    RequiredAssociatedConstants,
+    /// Trait associated constants with a default value
+    ProvidedAssociatedConstants,
+    /// Trait methods without a default implementation
+    RequiredMethods,
+    /// Trait methods with a default implementation
+    ProvidedMethods,
+    /// Methods of inherent impl blocks
+    InherentMethods,
+    /// Implementations of traits for the item
+    TraitImplementations,
+    /// Implementations of auto traits such as `Send` and `Sync`
+    AutoTraitImplementations,
+    /// Implementations that apply to every type, such as `impl<T> From<T> for T`
+    BlanketImplementations,
+    /// Types implementing a trait
+    Implementors,
+}
+
+impl Section {
+    /// Heading of the section in item pages.
+    pub fn title(self) -> &'static str {
+        match self {
+            Section::Declaration => "Declaration",
+            Section::Description => "Description",
+            Section::RequiredAssociatedTypes => "Required Associated Types",
+            Section::ProvidedAssociatedTypes => "Provided Associated Types",
+            Section::RequiredAssociatedConstants => "Required Associated Constants",
+            Section::ProvidedAssociatedConstants => "Provided Associated Constants",
+            Section::RequiredMethods => "Required Methods",
+            Section::ProvidedMethods => "Provided Methods",
+            Section::InherentMethods => "Inherent Methods",
+            Section::TraitImplementations => "Trait Implementations",
+            Section::AutoTraitImplementations => "Auto Trait Implementations",
+            Section::BlanketImplementations => "Blanket Implementations",
+            Section::Implementors => "Implementors",
+        }
+    }
+
+    /// Return the section of a rustdoc section header, with the heading to
+    /// render if rustdoc uses a different one.
+    ///
+    /// Sections without a counterpart, such as "Fields", return `None`.
+    pub fn from_rustdoc_id(id: &str) -> Option<(Section, Option<&'static str>)> {
+        let section = match id {
+            "required-associated-types" => Section::RequiredAssociatedTypes,
+            "provided-associated-types" => Section::ProvidedAssociatedTypes,
+            "required-associated-consts" => Section::RequiredAssociatedConstants,
+            "provided-associated-consts" => Section::ProvidedAssociatedConstants,
+            "required-methods" => Section::RequiredMethods,
+            "provided-methods" => Section::ProvidedMethods,
+            "implementations" => Section::InherentMethods,
+            "trait-implementations" => Section::TraitImplementations,
+            "synthetic-implementations" => Section::AutoTraitImplementations,
+            "blanket-implementations" => Section::BlanketImplementations,
+            "implementors" => Section::Implementors,
+            // Sections that keep the rustdoc heading but are toggled together
+            // with a related section.
+            "foreign-impls" | "synthetic-implementors" => {
+                return Some((Section::Implementors, None));
+            }
+            _ if id.starts_with("deref-methods") => return Some((Section::InherentMethods, None)),
+            _ => return None,
+        };
+        Some((section, Some(section.title())))
+    }
+}
+
+/// Kind of a member of an item, such as a method or an enum variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MemberKind {
+    /// A method, provided or required
+    Method,
+    /// An associated type
+    AssociatedType,
+    /// An associated constant
+    AssociatedConstant,
+    /// An enum variant
+    Variant,
+    /// A struct, union or variant field
+    Field,
+}
+
+impl MemberKind {
+    /// Parse a rustdoc anchor into a member kind and the member path
+    /// relative to the item.
+    ///
+    /// For example `method.push` gives `push` and
+    /// `variant.Circle.field.radius` gives `Circle::radius`. Anchors of
+    /// duplicate members, which rustdoc suffixes with `-1`, `-2`, ..., and
+    /// anchors that are not members return `None`.
+    pub fn from_anchor(anchor: &str) -> Option<(MemberKind, String)> {
+        let (prefix, rest) = anchor.split_once('.')?;
+        let is_name =
+            |name: &str| !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+
+        let (kind, path) = match prefix {
+            "method" | "tymethod" => (MemberKind::Method, rest.to_string()),
+            "associatedtype" => (MemberKind::AssociatedType, rest.to_string()),
+            "associatedconstant" => (MemberKind::AssociatedConstant, rest.to_string()),
+            "structfield" => (MemberKind::Field, rest.to_string()),
+            "variant" => match rest.split_once(".field.") {
+                Some((variant, field)) if is_name(variant) => {
+                    (MemberKind::Field, format!("{}::{}", variant, field))
+                }
+                Some(_) => return None,
+                None => (MemberKind::Variant, rest.to_string()),
+            },
+            _ => return None,
+        };
+
+        path.split("::").all(is_name).then_some((kind, path))
+    }
+
+    /// Return whether a heading declares the member `name` of this kind.
+    fn declared_by(self, heading: &str, name: &str) -> bool {
+        let signature = heading.trim_matches('`');
+        match self {
+            MemberKind::Method => declares_keyword(signature, "fn ", name),
+            MemberKind::AssociatedType => declares_keyword(signature, "type ", name),
+            MemberKind::AssociatedConstant => declares_keyword(signature, "const ", name),
+            MemberKind::Variant => signature.strip_prefix(name).is_some_and(|after| {
+                after
+                    .chars()
+                    .next()
+                    .is_none_or(|c| matches!(c, '(' | ' ' | '{'))
+            }),
+            MemberKind::Field => signature
+                .trim_start_matches("pub ")
+                .strip_prefix(name)
+                .is_some_and(|after| after.starts_with(':')),
+        }
+    }
+}
+
+/// A markdown heading outside of code blocks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Heading<'a> {
+    /// Index of the heading line
+    line: usize,
+    /// Number of `#` characters
+    level: usize,
+    /// Heading text without the `#` characters
+    text: &'a str,
+}
+
+/// Return the headings of a markdown document, skipping code blocks.
+fn headings<'a>(lines: &[&'a str]) -> Vec<Heading<'a>> {
+    let mut headings = Vec::new();
+    let mut in_code = false;
+    for (index, line) in lines.iter().enumerate() {
+        if line.starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            continue;
+        }
+        let level = line.chars().take_while(|&c| c == '#').count();
+        if (1..=6).contains(&level)
+            && let Some(text) = line[level..].strip_prefix(' ')
+        {
+            headings.push(Heading {
+                line: index,
+                level,
+                text: text.trim(),
+            });
+        }
+    }
+    headings
+}
+
+/// Return the line index where the block of `headings[index]` ends, that is
+/// the next heading of the same or a higher level, or the end of the document.
+fn block_end(headings: &[Heading], index: usize, line_count: usize) -> usize {
+    let level = headings[index].level;
+    headings[index + 1..]
+        .iter()
+        .find(|heading| heading.level <= level)
+        .map_or(line_count, |heading| heading.line)
+}
+
+/// Join lines into a markdown block ending with a blank line.
+fn join_block(lines: &[&str]) -> String {
+    let mut block = lines.join("\n").trim_end().to_string();
+    block.push_str("\n\n");
+    block
+}
+
+/// Return the `#` title line of a page, if any.
+pub fn page_title(markdown: &str) -> Option<&str> {
+    markdown.lines().find(|line| line.starts_with("# "))
+}
+
+/// Return the titles of the `##` sections of a page, in document order.
+pub fn section_titles(markdown: &str) -> Vec<&str> {
+    let lines: Vec<&str> = markdown.lines().collect();
+    headings(&lines)
+        .into_iter()
+        .filter(|heading| heading.level == 2)
+        .map(|heading| heading.text)
+        .collect()
+}
+
+/// Return the `##` sections whose heading matches `wanted`, each with its
+/// heading, in document order.
+pub fn extract_sections(markdown: &str, wanted: impl Fn(&str) -> bool) -> Vec<String> {
+    let lines: Vec<&str> = markdown.lines().collect();
+    let headings = headings(&lines);
+    headings
+        .iter()
+        .enumerate()
+        .filter(|(_, heading)| heading.level == 2 && wanted(heading.text))
+        .map(|(index, heading)| {
+            join_block(&lines[heading.line..block_end(&headings, index, lines.len())])
+        })
+        .collect()
+}
+
+/// Return the signature headings of a section: its impl blocks and members,
+/// below its `##` heading. Headings of the documentation are left out.
+pub fn signatures(section: &str) -> String {
+    let lines: Vec<&str> = section.lines().collect();
+    let mut output = String::new();
+    for heading in headings(&lines) {
+        let line = lines[heading.line];
+        if heading.level <= 2 {
+            output.push_str(line);
+            output.push_str("\n\n");
+        } else if is_signature(heading.text) {
+            output.push_str(line);
+            output.push('\n');
+        }
+    }
+    output
+}
+
+/// Return whether a heading is the signature of an impl block or a member.
+fn is_signature(heading: &str) -> bool {
+    let signature = heading.trim_matches('`');
+    signature.starts_with("impl")
+        || ["fn ", "type ", "const "].iter().any(|keyword| {
+            signature
+                .match_indices(keyword)
+                .any(|(start, _)| at_word_start(signature, start))
+        })
+}
+
+/// Return whether `index` is the start of a word of a signature.
+fn at_word_start(signature: &str, index: usize) -> bool {
+    signature[..index]
+        .chars()
+        .next_back()
+        .is_none_or(|c| c == ' ')
+}
+
+/// Return the declaration code block of an item page, if any.
+pub fn declaration(markdown: &str) -> Option<String> {
+    let mut in_declaration = false;
+    let mut block: Vec<&str> = Vec::new();
+    for line in markdown.lines() {
+        if in_declaration {
+            block.push(line);
+            if line == "```" {
+                return Some(join_block(&block));
+            }
+        } else if line.starts_with("```rust,item-decl") {
+            in_declaration = true;
+            block.push(line);
+        }
+    }
+    None
+}
+
+/// Return the example code blocks of a page, each under the heading it
+/// appears in. Declarations are not examples.
+pub fn examples(markdown: &str) -> String {
+    let mut output = String::new();
+    let mut heading: Option<&str> = None;
+    let mut printed_heading: Option<&str> = None;
+    // Lines of the current code block, and whether it is an example.
+    let mut block: Option<(Vec<&str>, bool)> = None;
+
+    for line in markdown.lines() {
+        match &mut block {
+            Some((lines, is_example)) => {
+                lines.push(line);
+                if line != "```" {
+                    continue;
+                }
+                if *is_example {
+                    if let Some(heading) = heading
+                        && printed_heading != Some(heading)
+                    {
+                        output.push_str(heading);
+                        output.push_str("\n\n");
+                        printed_heading = Some(heading);
+                    }
+                    output.push_str(&join_block(lines));
+                }
+                block = None;
+            }
+            None if line.starts_with("```") => {
+                block = Some((vec![line], !line.starts_with("```rust,item-decl")));
+            }
+            None if line.starts_with('#') => heading = Some(line),
+            None => {}
+        }
+    }
+    output
+}
+
+/// Return the documentation block of a member of an item page, such as a
+/// method or an enum variant, by its path relative to the item.
+///
+/// A member is found by the heading of its signature (e.g.
+/// `#### pub fn worker_threads(&mut self, val: usize) -> &mut Self`). Without
+/// a kind, methods, associated types and associated constants are looked up.
+/// Fields of enum variants are given as `Variant::field`. Members declared by
+/// the item itself, in inherent impls or in the trait definition, are
+/// preferred. Otherwise every trait implementation providing the member is
+/// returned, each under its `impl` heading.
+pub fn extract_member(markdown: &str, name: &str, kind: Option<MemberKind>) -> Option<String> {
+    if kind == Some(MemberKind::Field)
+        && let Some((variant, field)) = name.split_once("::")
+    {
+        let variant = extract_member(markdown, variant, Some(MemberKind::Variant))?;
+        return extract_member(&variant, field, kind);
+    }
+
+    let declares = |heading: &str| match kind {
+        Some(kind) => kind.declared_by(heading, name),
+        None => [
+            MemberKind::Method,
+            MemberKind::AssociatedType,
+            MemberKind::AssociatedConstant,
+        ]
+        .iter()
+        .any(|kind| kind.declared_by(heading, name)),
+    };
+
+    let lines: Vec<&str> = markdown.lines().collect();
+    let headings = headings(&lines);
+
+    let mut own = Vec::new();
+    let mut implemented = Vec::new();
+    let mut section = "";
+    for (index, heading) in headings.iter().enumerate() {
+        if heading.level <= 2 {
+            section = heading.text;
+            continue;
+        }
+        if !declares(heading.text) {
+            continue;
+        }
+        let block = join_block(&lines[heading.line..block_end(&headings, index, lines.len())]);
+        let in_trait_impls = matches!(
+            section,
+            "Trait Implementations"
+                | "Auto Trait Implementations"
+                | "Blanket Implementations"
+                | "Implementors"
+                | "Implementations on Foreign Types"
+        );
+        if !in_trait_impls {
+            own.push(block);
+            continue;
+        }
+        let impl_heading = headings[..index]
+            .iter()
+            .rev()
+            .find(|parent| parent.level < heading.level)
+            .filter(|parent| parent.level > 2);
+        match impl_heading {
+            Some(parent) => implemented.push(format!("{}\n\n{}", lines[parent.line], block)),
+            None => implemented.push(block),
+        }
+    }
+
+    let blocks = if own.is_empty() { implemented } else { own };
+    if blocks.is_empty() {
+        return None;
+    }
+    Some(blocks.concat())
+}
+
+/// Return whether a signature declares `name` after `keyword`, as in
+/// `pub fn name(...)` for the keyword `fn `.
+fn declares_keyword(signature: &str, keyword: &str, name: &str) -> bool {
+    signature.match_indices(keyword).any(|(start, _)| {
+        let rest = &signature[start + keyword.len()..];
+        at_word_start(signature, start)
+            && rest.strip_prefix(name).is_some_and(|after| {
+                after
+                    .chars()
+                    .next()
+                    .is_none_or(|c| matches!(c, '(' | '<' | ':' | ' ' | '='))
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = "# Struct Builder
+
+## Declaration
+
+```rust,item-decl
+pub struct Builder { /* private fields */ }
+```
+
+## Description
+
+Builds a runtime.
+
+### Examples
+
+```rust
+let rt = Builder::new_multi_thread().build();
+```
+
+## Inherent Methods
+
+### impl Builder
+
+#### pub fn new_multi_thread() -> Builder
+
+Returns a new builder.
+
+#### pub fn worker_threads(&mut self, val: usize) -> &mut Self
+
+Sets the number of worker threads.
+
+##### Examples
+
+```rust
+# fn main() {}
+builder.worker_threads(4);
+```
+
+#### pub fn worker_threads_max(&mut self) -> usize
+
+Not the one.
+
+## Trait Implementations
+
+### impl Debug for Builder
+
+#### fn fmt(&self, f: &mut Formatter<'_>) -> Result
+
+Formats the value.
+
+### impl Display for Builder
+
+#### fn fmt(&self, f: &mut Formatter<'_>) -> Result
+
+Displays the value.
+
+## Auto Trait Implementations
+
+### impl Send for Builder
+";
+
+    #[test]
+    fn from_rustdoc_id_renames_sections() {
+        assert_eq!(
+            Section::from_rustdoc_id("implementations"),
+            Some((Section::InherentMethods, Some("Inherent Methods")))
+        );
+        assert_eq!(
+            Section::from_rustdoc_id("synthetic-implementations"),
+            Some((
+                Section::AutoTraitImplementations,
+                Some("Auto Trait Implementations")
+            ))
+        );
+        assert_eq!(
+            Section::from_rustdoc_id("deref-methods-%5BT%5D"),
+            Some((Section::InherentMethods, None))
+        );
+        assert_eq!(
+            Section::from_rustdoc_id("foreign-impls"),
+            Some((Section::Implementors, None))
+        );
+        assert_eq!(
+            Section::from_rustdoc_id("required-associated-types"),
+            Some((
+                Section::RequiredAssociatedTypes,
+                Some("Required Associated Types")
+            ))
+        );
+        assert_eq!(
+            Section::from_rustdoc_id("provided-associated-consts"),
+            Some((
+                Section::ProvidedAssociatedConstants,
+                Some("Provided Associated Constants")
+            ))
+        );
+        assert_eq!(Section::from_rustdoc_id("fields"), None);
+    }
+
+    #[test]
+    fn section_serializes_as_kebab_case() {
+        let json = serde_json::to_string(&Section::AutoTraitImplementations).unwrap();
+        assert_eq!(json, "\"auto-trait-implementations\"");
+    }
+
+    #[test]
+    fn page_title_of_page() {
+        assert_eq!(page_title(PAGE), Some("# Struct Builder"));
+        assert_eq!(page_title("no title"), None);
+    }
+
+    #[test]
+    fn extract_sections_by_heading() {
+        let sections = extract_sections(PAGE, |title| title == "Description");
+        assert_eq!(
+            sections,
+            vec![
+                "## Description\n\nBuilds a runtime.\n\n### Examples\n\n```rust\nlet rt = Builder::new_multi_thread().build();\n```\n\n"
+            ]
+        );
+
+        let sections = extract_sections(PAGE, |title| title.ends_with("Implementations"));
+        assert_eq!(sections.len(), 2);
+        assert!(sections[1].starts_with("## Auto Trait Implementations"));
+    }
+
+    #[test]
+    fn signatures_of_section() {
+        let sections = extract_sections(PAGE, |title| title == "Inherent Methods");
+        assert_eq!(
+            signatures(&sections[0]),
+            concat!(
+                "## Inherent Methods\n\n",
+                "### impl Builder\n",
+                "#### pub fn new_multi_thread() -> Builder\n",
+                "#### pub fn worker_threads(&mut self, val: usize) -> &mut Self\n",
+                "#### pub fn worker_threads_max(&mut self) -> usize\n",
+            )
+        );
+    }
+
+    #[test]
+    fn declaration_of_page() {
+        assert_eq!(
+            declaration(PAGE).as_deref(),
+            Some("```rust,item-decl\npub struct Builder { /* private fields */ }\n```\n\n")
+        );
+        assert_eq!(declaration("# Crate serde\n"), None);
+    }
+
+    #[test]
+    fn examples_under_their_heading() {
+        assert_eq!(
+            examples(PAGE),
+            concat!(
+                "### Examples\n\n",
+                "```rust\nlet rt = Builder::new_multi_thread().build();\n```\n\n",
+                "##### Examples\n\n",
+                "```rust\n# fn main() {}\nbuilder.worker_threads(4);\n```\n\n",
+            )
+        );
+    }
+
+    #[test]
+    fn extract_member_inherent_method() {
+        assert_eq!(
+            extract_member(PAGE, "worker_threads", None).as_deref(),
+            Some(concat!(
+                "#### pub fn worker_threads(&mut self, val: usize) -> &mut Self\n\n",
+                "Sets the number of worker threads.\n\n",
+                "##### Examples\n\n",
+                "```rust\n# fn main() {}\nbuilder.worker_threads(4);\n```\n\n",
+            ))
+        );
+    }
+
+    #[test]
+    fn extract_member_from_trait_implementations() {
+        assert_eq!(
+            extract_member(PAGE, "fmt", None).as_deref(),
+            Some(concat!(
+                "### impl Debug for Builder\n\n",
+                "#### fn fmt(&self, f: &mut Formatter<'_>) -> Result\n\nFormats the value.\n\n",
+                "### impl Display for Builder\n\n",
+                "#### fn fmt(&self, f: &mut Formatter<'_>) -> Result\n\nDisplays the value.\n\n",
+            ))
+        );
+    }
+
+    #[test]
+    fn extract_member_missing() {
+        assert_eq!(extract_member(PAGE, "worker", None), None);
+        assert_eq!(extract_member(PAGE, "Builder", None), None);
+        assert_eq!(
+            extract_member(PAGE, "worker_threads", Some(MemberKind::AssociatedType)),
+            None
+        );
+    }
+
+    #[test]
+    fn declared_by_kinds() {
+        assert!(MemberKind::Method.declared_by("pub fn new<E>(error: E) -> Self", "new"));
+        assert!(MemberKind::Method.declared_by("`pub fn name(&self) -> &str`", "name"));
+        assert!(MemberKind::Method.declared_by("pub async fn run(self)", "run"));
+        assert!(!MemberKind::Method.declared_by("pub fn renew(self)", "new"));
+        assert!(!MemberKind::Method.declared_by("pub fn new_in(self)", "new"));
+        assert!(!MemberKind::Method.declared_by("impl From<T> for T", "T"));
+        assert!(MemberKind::AssociatedType.declared_by("type Target = dyn Error", "Target"));
+        assert!(MemberKind::AssociatedConstant.declared_by("const MAX: usize", "MAX"));
+        assert!(MemberKind::Variant.declared_by("Circle", "Circle"));
+        assert!(MemberKind::Variant.declared_by("Square(f64)", "Square"));
+        assert!(MemberKind::Variant.declared_by("`Custom(u8)`", "Custom"));
+        assert!(MemberKind::Variant.declared_by("Point { x: i32 }", "Point"));
+        assert!(!MemberKind::Variant.declared_by("SquareRoot(f64)", "Square"));
+        assert!(MemberKind::Field.declared_by("`size: usize`", "size"));
+        assert!(!MemberKind::Field.declared_by("`sizes: usize`", "size"));
+    }
+
+    #[test]
+    fn member_kind_from_anchor() {
+        assert_eq!(
+            MemberKind::from_anchor("method.push"),
+            Some((MemberKind::Method, "push".to_string()))
+        );
+        assert_eq!(
+            MemberKind::from_anchor("tymethod.serialize_str"),
+            Some((MemberKind::Method, "serialize_str".to_string()))
+        );
+        assert_eq!(
+            MemberKind::from_anchor("associatedtype.Output"),
+            Some((MemberKind::AssociatedType, "Output".to_string()))
+        );
+        assert_eq!(
+            MemberKind::from_anchor("associatedconstant.MAX"),
+            Some((MemberKind::AssociatedConstant, "MAX".to_string()))
+        );
+        assert_eq!(
+            MemberKind::from_anchor("variant.Circle"),
+            Some((MemberKind::Variant, "Circle".to_string()))
+        );
+        assert_eq!(
+            MemberKind::from_anchor("variant.Circle.field.radius"),
+            Some((MemberKind::Field, "Circle::radius".to_string()))
+        );
+        assert_eq!(
+            MemberKind::from_anchor("structfield.size"),
+            Some((MemberKind::Field, "size".to_string()))
+        );
+        assert_eq!(MemberKind::from_anchor("method.fmt-1"), None);
+        assert_eq!(MemberKind::from_anchor("variant.Circle.fields"), None);
+        assert_eq!(MemberKind::from_anchor("impl-Debug-for-Error"), None);
+        assert_eq!(MemberKind::from_anchor("main-content"), None);
+    }
+
+    const ENUM_PAGE: &str = "# Enum Shape
+
+## Variants
+
+### Circle
+
+A circle.
+
+#### Fields
+
+##### `radius: f64`
+
+The radius.
+
+### Square(f64)
+
+A square.
+
+## Auto Trait Implementations
+
+### impl Send for Shape
+";
+
+    #[test]
+    fn extract_member_variant_and_field() {
+        assert_eq!(
+            extract_member(ENUM_PAGE, "Square", Some(MemberKind::Variant)).as_deref(),
+            Some("### Square(f64)\n\nA square.\n\n")
+        );
+        assert_eq!(
+            extract_member(ENUM_PAGE, "Circle::radius", Some(MemberKind::Field)).as_deref(),
+            Some("##### `radius: f64`\n\nThe radius.\n\n")
+        );
+        assert_eq!(
+            extract_member(ENUM_PAGE, "Circle::size", Some(MemberKind::Field)),
+            None
+        );
+    }
+}